@@ -0,0 +1,383 @@
+use std::io::Read;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use tauri::{AppHandle, Manager};
+use tiny_http::{Header, Method, Request, Response, Server};
+use crate::commands::{create_task_from_request, notify_tasks_changed};
+use crate::error::{AppError, AppResult, ErrorCode};
+use crate::i18n::{fill, Msg};
+use crate::models::{ApiResponse, ApiStatus, CreateTaskRequest, Priority, Settings, Task, UpdateTaskRequest};
+use crate::storage::Storage;
+
+// 请求体大小上限
+const MAX_BODY_BYTES: usize = 1024 * 1024;
+
+/// 运行中的接口服务，drop 时停止监听
+struct RunningServer {
+    port: u16,
+    server: Arc<Server>,
+    // 重置令牌时直接替换，无需重启服务
+    token: Arc<Mutex<String>>,
+}
+
+impl Drop for RunningServer {
+    fn drop(&mut self) {
+        // 不等待线程退出：它可能正等待存储锁，而调用方此时正持有该锁
+        self.server.unblock();
+        log::info!("🌐 本地接口已停止");
+    }
+}
+
+/// 托管在应用状态中，随设置开启、关闭或切换端口
+#[derive(Default)]
+pub struct ApiServerState {
+    running: Mutex<Option<RunningServer>>,
+    last_error: Mutex<Option<String>>,
+}
+
+/// 按设置启动、重启或关闭本地接口
+pub fn apply_settings(app: &AppHandle, storage: &Storage, settings: &Settings) -> AppResult<()> {
+    let state = app.state::<ApiServerState>();
+    let mut running = state.running.lock()?;
+
+    let wanted = settings.api_enabled.then_some(settings.api_port);
+    if running.as_ref().map(|server| server.port) == wanted {
+        return Ok(());
+    }
+
+    *running = None;
+    *state.last_error.lock()? = None;
+
+    if let Some(port) = wanted {
+        match start(app.clone(), storage, port) {
+            Ok(server) => *running = Some(server),
+            Err(e) => {
+                *state.last_error.lock()? = Some(e.to_string());
+                return Err(e);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// 重新生成访问令牌，运行中的服务立即改用新令牌
+pub fn reset_token(app: &AppHandle, storage: &Storage) -> AppResult<String> {
+    let token = storage.reset_api_token()?;
+
+    let state = app.state::<ApiServerState>();
+    if let Some(server) = state.running.lock()?.as_ref() {
+        *server.token.lock()? = token.clone();
+    }
+    Ok(token)
+}
+
+pub fn status(app: &AppHandle, storage: &Storage, settings: &Settings) -> AppResult<ApiStatus> {
+    let state = app.state::<ApiServerState>();
+    let running = state.running.lock()?.is_some();
+    let error = state.last_error.lock()?.clone();
+    Ok(ApiStatus {
+        running,
+        url: format!("http://127.0.0.1:{}/api", settings.api_port),
+        token: storage.load_or_create_api_token()?,
+        error,
+    })
+}
+
+fn start(app: AppHandle, storage: &Storage, port: u16) -> AppResult<RunningServer> {
+    let token = Arc::new(Mutex::new(storage.load_or_create_api_token()?));
+
+    // 只监听回环地址，不对局域网开放
+    let server = Server::http(("127.0.0.1", port))
+        .map_err(|e| AppError::Io(std::io::Error::other(e.to_string())))?;
+    let server = Arc::new(server);
+    log::info!("🌐 本地接口已启动: http://127.0.0.1:{}/api", port);
+
+    let listener = server.clone();
+    let current_token = token.clone();
+    thread::spawn(move || {
+        for mut request in listener.incoming_requests() {
+            let token = current_token.lock().map(|t| t.clone()).unwrap_or_default();
+            let (status, body) = handle_request(&app, &token, &mut request);
+            let response = Response::from_string(body)
+                .with_status_code(status)
+                .with_header(Header::from_bytes("Content-Type", "application/json; charset=utf-8").unwrap());
+            if let Err(e) = request.respond(response) {
+                log::warn!("Failed to send local API response: {}", e);
+            }
+        }
+    });
+
+    Ok(RunningServer { port, server, token })
+}
+
+/// 接口路由
+#[derive(Debug, PartialEq)]
+enum Route {
+    Health,
+    ListTasks,
+    CreateTask,
+    GetTask(String),
+    UpdateTask(String),
+    DeleteTask(String),
+    Stats,
+}
+
+impl Route {
+    fn resolve(method: &Method, path: &str) -> Option<Self> {
+        let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+        let route = match (method, segments.as_slice()) {
+            (Method::Get, ["api", "health"]) => Route::Health,
+            (Method::Get, ["api", "tasks"]) => Route::ListTasks,
+            (Method::Post, ["api", "tasks"]) => Route::CreateTask,
+            (Method::Get, ["api", "tasks", id]) => Route::GetTask(id.to_string()),
+            (Method::Patch | Method::Put, ["api", "tasks", id]) => Route::UpdateTask(id.to_string()),
+            (Method::Delete, ["api", "tasks", id]) => Route::DeleteTask(id.to_string()),
+            (Method::Get, ["api", "stats"]) => Route::Stats,
+            _ => return None,
+        };
+        Some(route)
+    }
+
+    fn failure_msg(&self) -> Msg {
+        match self {
+            Route::Health => Msg::ApiRequestFailed,
+            Route::ListTasks | Route::GetTask(_) => Msg::LoadTasksFailed,
+            Route::CreateTask => Msg::CreateTaskFailed,
+            Route::UpdateTask(_) => Msg::UpdateTaskFailed,
+            Route::DeleteTask(_) => Msg::DeleteTaskFailed,
+            Route::Stats => Msg::StatsFailed,
+        }
+    }
+
+    fn mutates(&self) -> bool {
+        matches!(self, Route::CreateTask | Route::UpdateTask(_) | Route::DeleteTask(_))
+    }
+}
+
+/// 处理单个请求，返回状态码与 JSON 响应体（格式同前端命令的 ApiResponse）
+fn handle_request(app: &AppHandle, token: &str, request: &mut Request) -> (u16, String) {
+    let url = request.url().to_string();
+    let (path, query) = url.split_once('?').unwrap_or((url.as_str(), ""));
+
+    let Some(route) = Route::resolve(request.method(), path) else {
+        return respond_error(Msg::ApiRequestFailed, AppError::not_found(Msg::EntityRoute, path));
+    };
+
+    // 健康检查不需要令牌，便于脚本探测应用是否在运行
+    if !matches!(route, Route::Health) && !is_authorized(request, token) {
+        return respond_error(route.failure_msg(), AppError::Unauthorized);
+    }
+
+    let result = read_body(request).and_then(|body| execute(app, &route, query, &body));
+    match result {
+        Ok(data) => {
+            if route.mutates() {
+                notify_tasks_changed(app);
+            }
+            let status = if matches!(route, Route::CreateTask) { 201 } else { 200 };
+            (status, serde_json::to_string(&ApiResponse::success(data)).unwrap_or_default())
+        }
+        Err(e) => respond_error(route.failure_msg(), e),
+    }
+}
+
+fn respond_error(action: Msg, err: AppError) -> (u16, String) {
+    let status = match err.code() {
+        ErrorCode::NotFound => 404,
+        ErrorCode::InvalidInput => 400,
+        ErrorCode::Unauthorized => 401,
+        _ => 500,
+    };
+    let response = ApiResponse::<Value>::error(action, err);
+    (status, serde_json::to_string(&response).unwrap_or_default())
+}
+
+/// 校验 Authorization: Bearer <token>，认证方案不区分大小写
+fn is_authorized(request: &Request, token: &str) -> bool {
+    request.headers()
+        .iter()
+        .find(|header| header.field.equiv("Authorization"))
+        .and_then(|header| header.value.as_str().trim().split_once(' '))
+        .filter(|(scheme, _)| scheme.eq_ignore_ascii_case("Bearer"))
+        .is_some_and(|(_, provided)| constant_time_eq(provided.trim().as_bytes(), token.as_bytes()))
+}
+
+// 逐字节比较全部内容，避免通过响应时间猜测令牌
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+fn read_body(request: &mut Request) -> AppResult<String> {
+    let mut body = String::new();
+    request.as_reader()
+        .take(MAX_BODY_BYTES as u64 + 1)
+        .read_to_string(&mut body)
+        .map_err(|e| AppError::invalid_input(e.to_string()))?;
+
+    if body.len() > MAX_BODY_BYTES {
+        return Err(AppError::invalid_input(fill(Msg::BodyTooLarge.text(), &[&MAX_BODY_BYTES])));
+    }
+    Ok(body)
+}
+
+fn parse_json<T: DeserializeOwned>(body: &str) -> AppResult<T> {
+    serde_json::from_str(body).map_err(|e| AppError::invalid_input(e.to_string()))
+}
+
+fn execute(app: &AppHandle, route: &Route, query: &str, body: &str) -> AppResult<Value> {
+    let state = app.state::<Mutex<Storage>>();
+    let storage = state.lock()?;
+
+    let data = match route {
+        Route::Health => serde_json::json!({
+            "name": crate::APP_NAME,
+            "version": env!("CARGO_PKG_VERSION"),
+        }),
+        Route::ListTasks => serde_json::to_value(list_tasks(storage.load_tasks()?, query)?)?,
+        Route::CreateTask => serde_json::to_value(create_task_from_request(&storage, parse_json::<CreateTaskRequest>(body)?)?)?,
+        Route::GetTask(id) => {
+            let task = storage.load_tasks()?
+                .into_iter()
+                .find(|task| &task.id == id)
+                .ok_or_else(|| AppError::not_found(Msg::EntityTask, id))?;
+            serde_json::to_value(task)?
+        }
+        Route::UpdateTask(id) => serde_json::to_value(storage.update_task(id, &parse_json::<UpdateTaskRequest>(body)?)?)?,
+        Route::DeleteTask(id) => {
+            storage.delete_task(id)?;
+            Value::Bool(true)
+        }
+        Route::Stats => {
+            let tasks = storage.load_tasks()?;
+            serde_json::to_value(storage.get_task_stats(&tasks))?
+        }
+    };
+    Ok(data)
+}
+
+/// 列表与搜索：?q=关键字&completed=true|false&priority=high
+fn list_tasks(mut tasks: Vec<Task>, query: &str) -> AppResult<Vec<Task>> {
+    for (key, value) in url::form_urlencoded::parse(query.as_bytes()) {
+        match key.as_ref() {
+            "q" => tasks.retain(|task| task.matches_query(&value)),
+            "completed" => {
+                let completed = value.parse::<bool>()
+                    .map_err(|_| AppError::invalid_input(format!("completed={}", value)))?;
+                tasks.retain(|task| task.completed == completed);
+            }
+            "priority" => {
+                let priority = Priority::parse(&value)
+                    .ok_or_else(|| AppError::invalid_input(Msg::InvalidPriority.text()))?;
+                tasks.retain(|task| task.priority == priority);
+            }
+            _ => {}
+        }
+    }
+    Ok(tasks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tiny_http::TestRequest;
+
+    const TOKEN: &str = "secret-token";
+
+    fn authorized(header: Option<&str>) -> bool {
+        let mut request = TestRequest::new().with_path("/api/tasks");
+        if let Some(value) = header {
+            request = request.with_header(Header::from_bytes("Authorization", value).unwrap());
+        }
+        is_authorized(&request.into(), TOKEN)
+    }
+
+    fn task(title: &str, completed: bool, priority: Priority) -> Task {
+        let mut task = Task::new(title.to_string(), None, Some(priority), None, None);
+        task.completed = completed;
+        task
+    }
+
+    fn titles(query: &str) -> AppResult<Vec<String>> {
+        let tasks = vec![
+            task("Buy milk", false, Priority::High),
+            task("Pay rent", true, Priority::High),
+            task("Buy stamps", true, Priority::Low),
+        ];
+        Ok(list_tasks(tasks, query)?.into_iter().map(|t| t.title).collect())
+    }
+
+    #[test]
+    fn resolves_routes() {
+        assert_eq!(Route::resolve(&Method::Get, "/api/health"), Some(Route::Health));
+        assert_eq!(Route::resolve(&Method::Get, "/api/tasks/"), Some(Route::ListTasks));
+        assert_eq!(Route::resolve(&Method::Post, "/api/tasks"), Some(Route::CreateTask));
+        assert_eq!(Route::resolve(&Method::Get, "/api/tasks/abc/"), Some(Route::GetTask("abc".to_string())));
+        assert_eq!(Route::resolve(&Method::Patch, "/api/tasks/abc"), Some(Route::UpdateTask("abc".to_string())));
+        assert_eq!(Route::resolve(&Method::Put, "/api/tasks/abc"), Some(Route::UpdateTask("abc".to_string())));
+        assert_eq!(Route::resolve(&Method::Delete, "/api/tasks/abc"), Some(Route::DeleteTask("abc".to_string())));
+        assert_eq!(Route::resolve(&Method::Get, "/api/stats"), Some(Route::Stats));
+    }
+
+    #[test]
+    fn rejects_unknown_routes_and_methods() {
+        for (method, path) in [
+            (Method::Delete, "/api/tasks"),
+            (Method::Post, "/api/tasks/abc"),
+            (Method::Head, "/api/health"),
+            (Method::Options, "/api/tasks"),
+            (Method::NonStandard("PURGE".parse().unwrap()), "/api/tasks"),
+            (Method::Get, "/api"),
+            (Method::Get, "/tasks"),
+            (Method::Get, "/api//tasks"),
+            (Method::Get, "/api/tasks/abc/def"),
+        ] {
+            assert_eq!(Route::resolve(&method, path), None, "{} {}", method, path);
+        }
+    }
+
+    #[test]
+    fn requires_bearer_token() {
+        assert!(authorized(Some("Bearer secret-token")));
+        assert!(authorized(Some("bearer secret-token")));
+        assert!(authorized(Some("  Bearer  secret-token ")));
+
+        assert!(!authorized(None));
+        assert!(!authorized(Some("secret-token")));
+        assert!(!authorized(Some("Bearer")));
+        assert!(!authorized(Some("Bearer ")));
+        assert!(!authorized(Some("Basic secret-token")));
+        assert!(!authorized(Some("Bearersecret-token")));
+        assert!(!authorized(Some("Bearer secret-tokeN")));
+        assert!(!authorized(Some("Bearer secret-token-and-more")));
+    }
+
+    #[test]
+    fn compares_tokens_of_any_length() {
+        assert!(constant_time_eq(b"abc", b"abc"));
+        assert!(constant_time_eq(b"", b""));
+        assert!(!constant_time_eq(b"abc", b"abd"));
+        assert!(!constant_time_eq(b"abc", b"ab"));
+        assert!(!constant_time_eq(b"", b"abc"));
+    }
+
+    #[test]
+    fn filters_task_list_by_query() {
+        assert_eq!(titles("").unwrap(), ["Buy milk", "Pay rent", "Buy stamps"]);
+        assert_eq!(titles("q=buy&completed=true").unwrap(), ["Buy stamps"]);
+        assert_eq!(titles("completed=false").unwrap(), ["Buy milk"]);
+        assert_eq!(titles("priority=HIGH&unknown=1").unwrap(), ["Buy milk", "Pay rent"]);
+        assert_eq!(titles("q=Buy%20m").unwrap(), ["Buy milk"]);
+    }
+
+    #[test]
+    fn rejects_invalid_filters() {
+        for query in ["completed=yes", "completed=", "priority=urgent", "priority="] {
+            let err = titles(query).unwrap_err();
+            assert_eq!(err.code(), ErrorCode::InvalidInput, "{}", query);
+            assert_eq!(respond_error(Msg::LoadTasksFailed, err).0, 400);
+        }
+        assert_eq!(respond_error(Msg::LoadTasksFailed, AppError::Unauthorized).0, 401);
+    }
+}
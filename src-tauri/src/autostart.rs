@@ -0,0 +1,33 @@
+use tauri::{AppHandle, Runtime, plugin::TauriPlugin};
+use tauri_plugin_autostart::ManagerExt;
+use crate::error::{AppError, AppResult};
+use crate::models::Settings;
+
+// 开机启动时附带的参数：只显示托盘图标，不弹出主窗口
+pub const MINIMIZED_ARG: &str = "--minimized";
+
+pub fn plugin<R: Runtime>() -> TauriPlugin<R> {
+    tauri_plugin_autostart::Builder::new()
+        .app_name(crate::APP_NAME)
+        .arg(MINIMIZED_ARG)
+        .build()
+}
+
+pub fn is_minimized_launch(args: &[String]) -> bool {
+    args.iter().any(|arg| arg == MINIMIZED_ARG)
+}
+
+/// 按设置注册或移除开机启动项（Linux 为 XDG autostart，Windows 为注册表 Run 键，macOS 为 LaunchAgent）
+///
+/// 开启时每次都重新写入，应用被移动位置后启动项也会指向新路径
+pub fn apply_settings(app: &AppHandle, settings: &Settings) -> AppResult<()> {
+    let launcher = app.autolaunch();
+    let result = if settings.launch_at_login {
+        launcher.enable()
+    } else if launcher.is_enabled().unwrap_or(false) {
+        launcher.disable()
+    } else {
+        Ok(())
+    };
+    result.map_err(|e| AppError::Internal(e.to_string()))
+}
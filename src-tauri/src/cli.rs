@@ -0,0 +1,456 @@
+use std::sync::Mutex;
+use chrono::Local;
+use clap::{Parser, Subcommand};
+use serde_json::Value;
+use tauri::{AppHandle, Manager};
+use crate::commands::{create_task_from_request, notify_tasks_changed, parse_due_argument};
+use crate::stats::{is_due_today, is_overdue};
+use crate::error::{AppError, AppResult};
+use crate::i18n::{self, fill, Msg};
+use crate::models::{ApiResponse, CreateTaskRequest, Priority, SyncStatus, Task, TaskStats, UpdateTaskRequest};
+use crate::single_instance::{lock_or_forward, Coordination, ForwardedArgs};
+use crate::storage::Storage;
+use crate::sync;
+
+// 第一个参数是这些值时进入命令行模式，其余情况（无参数、深链接等）启动图形界面
+const CLI_ENTRY_ARGS: &[&str] = &[
+    "add", "list", "done", "update", "stats", "sync", "help", "--help", "-h", "--version", "-V",
+];
+
+// 可以写在子命令之前的全局选项，判断是否为命令行调用时跳过
+const CLI_GLOBAL_FLAGS: &[&str] = &["--json"];
+
+// 列表中显示的 id 长度，done / update 命令接受任意唯一前缀
+const SHORT_ID_LEN: usize = 8;
+
+#[derive(Debug, Parser)]
+#[command(name = "semidone", version, about = "SemiDone command line interface")]
+pub struct Cli {
+    /// Print the result as JSON ({ success, data, error, code })
+    #[arg(long, global = true)]
+    json: bool,
+
+    #[command(subcommand)]
+    command: CliCommand,
+}
+
+#[derive(Debug, Subcommand)]
+enum CliCommand {
+    /// Add a task
+    Add {
+        title: String,
+        /// Due date: today, tomorrow, +3d, mon..sun, YYYY-MM-DD or "YYYY-MM-DD HH:MM"
+        #[arg(long)]
+        due: Option<String>,
+        /// high, medium or low
+        #[arg(long, short, value_parser = parse_priority)]
+        priority: Option<Priority>,
+        #[arg(long, short)]
+        description: Option<String>,
+    },
+    /// List pending tasks
+    List {
+        /// Only tasks due today
+        #[arg(long)]
+        today: bool,
+        /// Only overdue tasks
+        #[arg(long)]
+        overdue: bool,
+        /// Include completed tasks
+        #[arg(long, short, conflicts_with = "completed")]
+        all: bool,
+        /// Only completed tasks
+        #[arg(long)]
+        completed: bool,
+        /// Only tasks whose title or description contains this text
+        #[arg(long, short)]
+        query: Option<String>,
+    },
+    /// Mark a task as completed (a unique id prefix is enough)
+    Done { id: String },
+    /// Change a task's title, description, due date or priority
+    Update {
+        id: String,
+        #[arg(long)]
+        title: Option<String>,
+        #[arg(long, short)]
+        description: Option<String>,
+        #[arg(long)]
+        due: Option<String>,
+        #[arg(long, short, value_parser = parse_priority)]
+        priority: Option<Priority>,
+    },
+    /// Show task statistics
+    Stats,
+    /// Merge changes with other devices through the sync folder
+    Sync,
+}
+
+fn parse_priority(value: &str) -> Result<Priority, String> {
+    Priority::parse(value).ok_or_else(|| Msg::InvalidPriority.text().to_string())
+}
+
+/// 启动参数是否为命令行调用
+pub fn is_cli_invocation(args: &[String]) -> bool {
+    args.iter()
+        .find(|arg| !CLI_GLOBAL_FLAGS.contains(&arg.as_str()))
+        .is_some_and(|arg| CLI_ENTRY_ARGS.contains(&arg.as_str()))
+}
+
+impl Cli {
+    pub fn parse_args(args: &[String]) -> Result<Self, clap::Error> {
+        Cli::try_parse_from(std::iter::once("semidone".to_string()).chain(args.iter().cloned()))
+    }
+
+    fn mutates(&self) -> bool {
+        matches!(
+            self.command,
+            CliCommand::Add { .. } | CliCommand::Done { .. } | CliCommand::Update { .. } | CliCommand::Sync
+        )
+    }
+
+    /// 执行命令，结果与前端命令的返回格式一致
+    fn execute(&self, storage: &Storage) -> ApiResponse<Value> {
+        let (action, result) = match &self.command {
+            CliCommand::Add { title, due, priority, description } => (
+                Msg::CreateTaskFailed,
+                add_task(storage, title, due.as_deref(), *priority, description.clone()),
+            ),
+            CliCommand::List { today, overdue, all, completed, query } => (
+                Msg::LoadTasksFailed,
+                list_tasks(storage, &ListFilter {
+                    today: *today,
+                    overdue: *overdue,
+                    all: *all,
+                    completed: *completed,
+                    query: query.as_deref(),
+                }),
+            ),
+            CliCommand::Done { id } => {
+                let updates = UpdateTaskRequest { completed: Some(true), ..Default::default() };
+                (Msg::UpdateTaskFailed, update_task(storage, id, updates))
+            }
+            CliCommand::Update { id, title, description, due, priority } => {
+                let result = due.as_deref()
+                    .map(parse_due_argument)
+                    .transpose()
+                    .and_then(|due_date| {
+                        let updates = UpdateTaskRequest {
+                            title: title.clone(),
+                            description: description.clone(),
+                            priority: *priority,
+                            due_date,
+                            ..Default::default()
+                        };
+                        update_task(storage, id, updates)
+                    });
+                (Msg::UpdateTaskFailed, result)
+            }
+            CliCommand::Stats => (
+                Msg::StatsFailed,
+                storage.load_tasks().and_then(|tasks| Ok(serde_json::to_value(storage.get_task_stats(&tasks))?)),
+            ),
+            CliCommand::Sync => (
+                Msg::SyncFailed,
+                sync::sync(storage).and_then(|_| Ok(serde_json::to_value(sync::status(storage)?)?)),
+            ),
+        };
+
+        match result {
+            Ok(data) => ApiResponse::success(data),
+            Err(e) => ApiResponse::error(action, e),
+        }
+    }
+
+    /// 以人类可读的形式输出结果
+    fn print_human(&self, data: Value) -> AppResult<()> {
+        match &self.command {
+            CliCommand::Add { .. } => {
+                let task: Task = serde_json::from_value(data)?;
+                println!("{}", fill(Msg::CliTaskAdded.text(), &[&short_id(&task.id), &task.title]));
+            }
+            CliCommand::Done { .. } => {
+                let task: Task = serde_json::from_value(data)?;
+                println!("{}", fill(Msg::CliTaskCompleted.text(), &[&short_id(&task.id), &task.title]));
+            }
+            CliCommand::Update { .. } => {
+                let task: Task = serde_json::from_value(data)?;
+                println!("{}", fill(Msg::CliTaskUpdated.text(), &[&short_id(&task.id), &task.title]));
+            }
+            CliCommand::List { .. } => {
+                let tasks: Vec<Task> = serde_json::from_value(data)?;
+                if tasks.is_empty() {
+                    println!("{}", Msg::CliNoTasks.text());
+                }
+                for task in tasks {
+                    println!(
+                        "{}  [{}]  {:<6}  {:<16}  {}",
+                        short_id(&task.id),
+                        if task.completed { "x" } else { " " },
+                        task.priority.as_str(),
+                        task.due_date.as_deref().unwrap_or("-"),
+                        task.title,
+                    );
+                }
+            }
+            CliCommand::Stats => {
+                let stats: TaskStats = serde_json::from_value(data)?;
+                println!(
+                    "{}",
+                    fill(Msg::CliStats.text(), &[&stats.total, &stats.completed, &stats.pending, &stats.overdue, &stats.today])
+                );
+            }
+            CliCommand::Sync => {
+                let status: SyncStatus = serde_json::from_value(data)?;
+                println!("{}", fill(Msg::CliSynced.text(), &[&status.devices.len(), &status.conflicts]));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn short_id(id: &str) -> &str {
+    id.get(..SHORT_ID_LEN).unwrap_or(id)
+}
+
+fn add_task(
+    storage: &Storage,
+    title: &str,
+    due: Option<&str>,
+    priority: Option<Priority>,
+    description: Option<String>,
+) -> AppResult<Value> {
+    let due_date = due.map(parse_due_argument).transpose()?;
+
+    let request = CreateTaskRequest {
+        title: title.trim().to_string(),
+        description,
+        priority,
+        due_date,
+        attachments: None,
+        estimated_minutes: None,
+    };
+    Ok(serde_json::to_value(create_task_from_request(storage, request)?)?)
+}
+
+/// list 命令的筛选条件
+struct ListFilter<'a> {
+    today: bool,
+    overdue: bool,
+    all: bool,
+    completed: bool,
+    query: Option<&'a str>,
+}
+
+fn list_tasks(storage: &Storage, filter: &ListFilter) -> AppResult<Value> {
+    let now = Local::now();
+    let tasks: Vec<Task> = storage.load_tasks()?
+        .into_iter()
+        .filter(|task| filter.all || task.completed == filter.completed)
+        .filter(|task| filter.query.map_or(true, |query| task.matches_query(query)))
+        .filter(|task| !filter.today || is_due_today(task, &now))
+        .filter(|task| !filter.overdue || is_overdue(task, &now))
+        .collect();
+    Ok(serde_json::to_value(tasks)?)
+}
+
+/// 按完整 id 或唯一前缀找到待办
+fn resolve_task_id(storage: &Storage, id_prefix: &str) -> AppResult<String> {
+    let tasks = storage.load_tasks()?;
+    let mut matches = tasks.iter().filter(|task| task.id.starts_with(id_prefix));
+
+    match (matches.next(), matches.next()) {
+        (Some(task), None) if !id_prefix.is_empty() => Ok(task.id.clone()),
+        (Some(_), _) => Err(AppError::invalid_input(fill(Msg::AmbiguousTaskId.text(), &[&id_prefix]))),
+        (None, _) => Err(AppError::not_found(Msg::EntityTask, id_prefix)),
+    }
+}
+
+fn update_task(storage: &Storage, id_prefix: &str, updates: UpdateTaskRequest) -> AppResult<Value> {
+    let id = resolve_task_id(storage, id_prefix)?;
+    Ok(serde_json::to_value(storage.update_task(&id, &updates)?)?)
+}
+
+/// 执行命令并返回 ApiResponse 格式的 JSON
+///
+/// 已有实例运行时转发给它执行（共用同一把存储锁并刷新界面），否则短暂持有单实例锁直接读写数据文件
+pub fn dispatch(args: &[String]) -> Result<Value, String> {
+    let cli = Cli::parse_args(args).map_err(|e| e.to_string())?;
+    dispatch_parsed(&cli, args)
+}
+
+fn dispatch_parsed(cli: &Cli, args: &[String]) -> Result<Value, String> {
+    let forwarded = ForwardedArgs {
+        args: args.to_vec(),
+        cwd: None,
+    };
+
+    match lock_or_forward(&crate::instance_name(), &forwarded)? {
+        Coordination::Local(_lock) => {
+            let storage = Storage::new().map_err(|e| e.to_string())?;
+            serde_json::to_value(cli.execute(&storage)).map_err(|e| e.to_string())
+        }
+        Coordination::Forwarded(reply) => Ok(reply),
+    }
+}
+
+/// 按设置中的语言输出命令行与 MCP 的文案
+pub fn init_locale() {
+    if let Ok(settings) = Storage::new().and_then(|storage| storage.load_settings()) {
+        i18n::set_locale(settings.locale);
+    }
+}
+
+/// 命令行入口，返回进程退出码
+pub fn run(args: &[String]) -> i32 {
+    // release 版本是 Windows 子系统程序，需要挂到启动它的终端上才能输出
+    #[cfg(target_os = "windows")]
+    unsafe {
+        use windows::Win32::System::Console::{AttachConsole, ATTACH_PARENT_PROCESS};
+        let _ = AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+
+    let cli = match Cli::parse_args(args) {
+        Ok(cli) => cli,
+        // --help / --version 也走这里，由 clap 决定输出位置与退出码
+        Err(e) => e.exit(),
+    };
+
+    init_locale();
+
+    let response = match dispatch_parsed(&cli, args) {
+        Ok(response) => response,
+        Err(e) => {
+            eprintln!("{}", e);
+            return 1;
+        }
+    };
+
+    if cli.json {
+        println!("{}", serde_json::to_string_pretty(&response).unwrap_or_default());
+        return if response["success"] == Value::Bool(true) { 0 } else { 1 };
+    }
+
+    match serde_json::from_value::<ApiResponse<Value>>(response) {
+        Ok(ApiResponse { success: true, data, .. }) => match cli.print_human(data.unwrap_or(Value::Null)) {
+            Ok(()) => 0,
+            Err(e) => {
+                eprintln!("{}", e);
+                1
+            }
+        },
+        Ok(ApiResponse { error, .. }) => {
+            eprintln!("{}", error.unwrap_or_default());
+            1
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            1
+        }
+    }
+}
+
+/// 主实例执行其他进程转发来的命令行命令，返回值作为回复发回
+pub fn handle_forwarded(app: &AppHandle, args: &[String]) -> Value {
+    let cli = match Cli::parse_args(args) {
+        Ok(cli) => cli,
+        Err(e) => {
+            let response = ApiResponse::<Value>::error(Msg::ParseCommandFailed, AppError::invalid_input(e.to_string()));
+            return serde_json::to_value(response).unwrap_or(Value::Null);
+        }
+    };
+
+    let state = app.state::<Mutex<Storage>>();
+    let response = match state.lock() {
+        Ok(storage) => cli.execute(&storage),
+        Err(e) => ApiResponse::error(Msg::LoadTasksFailed, e.into()),
+    };
+
+    if response.success && cli.mutates() {
+        notify_tasks_changed(app);
+    }
+    serde_json::to_value(response).unwrap_or(Value::Null)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    fn parse(values: &[&str]) -> Cli {
+        Cli::parse_args(&args(values)).unwrap()
+    }
+
+    #[test]
+    fn detects_cli_invocation_after_global_flags() {
+        assert!(is_cli_invocation(&args(&["list"])));
+        assert!(is_cli_invocation(&args(&["--json", "list"])));
+        assert!(is_cli_invocation(&args(&["--json", "--json", "done", "abc"])));
+        assert!(is_cli_invocation(&args(&["--version"])));
+
+        // 图形界面的启动参数
+        assert!(!is_cli_invocation(&args(&[])));
+        assert!(!is_cli_invocation(&args(&["--json"])));
+        assert!(!is_cli_invocation(&args(&["--minimized"])));
+        assert!(!is_cli_invocation(&args(&["semidone://add?title=x"])));
+        assert!(!is_cli_invocation(&args(&["--minimized", "list"])));
+    }
+
+    #[test]
+    fn parses_add_with_options() {
+        let cli = parse(&["add", "Buy milk", "--due", "tomorrow", "-p", "HIGH", "-d", "2 liters", "--json"]);
+        assert!(cli.json);
+        assert!(cli.mutates());
+        match cli.command {
+            CliCommand::Add { title, due, priority, description } => {
+                assert_eq!(title, "Buy milk");
+                assert_eq!(due.as_deref(), Some("tomorrow"));
+                assert_eq!(priority, Some(Priority::High));
+                assert_eq!(description.as_deref(), Some("2 liters"));
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(Cli::parse_args(&args(&["add", "x", "--priority", "urgent"])).is_err());
+        assert!(Cli::parse_args(&args(&["add"])).is_err());
+    }
+
+    #[test]
+    fn parses_list_filters() {
+        let cli = parse(&["--json", "list", "--today", "-q", "milk"]);
+        assert!(cli.json);
+        assert!(!cli.mutates());
+        match cli.command {
+            CliCommand::List { today, overdue, all, completed, query } => {
+                assert!(today && !overdue && !all && !completed);
+                assert_eq!(query.as_deref(), Some("milk"));
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(Cli::parse_args(&args(&["list", "--all", "--completed"])).is_err());
+    }
+
+    #[test]
+    fn parses_done_and_update() {
+        match parse(&["done", "1a2b"]).command {
+            CliCommand::Done { id } => assert_eq!(id, "1a2b"),
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(Cli::parse_args(&args(&["done"])).is_err());
+
+        let cli = parse(&["update", "1a2b", "--title=-5 push-ups", "--due", "+3d", "-p", "low"]);
+        assert!(cli.mutates());
+        match cli.command {
+            CliCommand::Update { id, title, description, due, priority } => {
+                assert_eq!(id, "1a2b");
+                assert_eq!(title.as_deref(), Some("-5 push-ups"));
+                assert_eq!(description, None);
+                assert_eq!(due.as_deref(), Some("+3d"));
+                assert_eq!(priority, Some(Priority::Low));
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+}
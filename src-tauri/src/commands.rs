@@ -1,42 +1,90 @@
-use tauri::State;
+use tauri::{Emitter, State};
+use tauri_plugin_dialog::DialogExt;
 use std::sync::Mutex;
 use crate::models::*;
 use crate::storage::Storage;
+use crate::tray;
+use crate::api_server;
+use crate::quick_add;
+use crate::autostart;
+use crate::usage;
+use crate::pomodoro;
+use crate::time_tracking;
+use crate::report::{self, ReportSource};
+use crate::task_csv;
+use crate::importers;
+use crate::task_list;
+use crate::sync;
+use crate::window::PendingRoute;
+use crate::datetime::{parse_date, parse_due_input, DUE_FORMAT};
+use crate::error::{AppError, AppResult};
+use crate::i18n::{self, fill, Msg};
+use crate::stats::MAX_TREND_DAYS;
+use chrono::{Duration, Local};
 
 type StorageState<'a> = State<'a, Mutex<Storage>>;
 
+// 待办在前端之外（命令行等）被修改时通知前端重新加载
+pub const TASKS_CHANGED_EVENT: &str = "tasks-changed";
+
+pub fn notify_tasks_changed(app: &tauri::AppHandle) {
+    if let Err(e) = app.emit(TASKS_CHANGED_EVENT, ()) {
+        log::warn!("Failed to emit {}: {}", TASKS_CHANGED_EVENT, e);
+    }
+    tray::refresh_tray(app);
+}
+
 #[tauri::command]
-pub async fn get_tasks(storage: StorageState<'_>) -> Result<ApiResponse<Vec<Task>>, String> {
-    let storage = storage.lock().map_err(|e| e.to_string())?;
-    
+pub async fn get_tasks(storage: StorageState<'_>) -> AppResult<ApiResponse<Vec<Task>>> {
+    let storage = storage.lock()?;
+
     match storage.load_tasks() {
         Ok(tasks) => Ok(ApiResponse::success(tasks)),
-        Err(e) => Ok(ApiResponse::error(format!("加载待办失败: {}", e))),
+        Err(e) => Ok(ApiResponse::error(Msg::LoadTasksFailed, e)),
     }
 }
 
-#[tauri::command]
-pub async fn create_task(
-    request: CreateTaskRequest,
-    storage: StorageState<'_>,
-) -> Result<ApiResponse<Task>, String> {
-    let storage = storage.lock().map_err(|e| e.to_string())?;
-    
-    let priority = request.priority
-        .map(|p| Priority::from_string(&p))
-        .unwrap_or(Priority::Medium);
-    
-    let task = Task::new(
+/// 将命令行、链接中的截止时间（today、+3d 等）转换为前端使用的格式
+pub fn parse_due_argument(value: &str) -> AppResult<String> {
+    parse_due_input(value, Local::now().date_naive())
+        .map(|due| due.format(DUE_FORMAT).to_string())
+        .ok_or_else(|| AppError::invalid_input(fill(Msg::InvalidDue.text(), &[&value])))
+}
+
+/// 校验并保存新待办，前端、命令行与深链接共用
+pub fn create_task_from_request(storage: &Storage, request: CreateTaskRequest) -> AppResult<Task> {
+    if request.title.trim().is_empty() {
+        return Err(AppError::invalid_input(Msg::TitleRequired.text()));
+    }
+
+    let priority = request.priority.unwrap_or(Priority::Medium);
+
+    let mut task = Task::new(
         request.title,
         request.description,
         Some(priority),
         request.due_date,
         request.attachments,
     );
-    
-    match storage.add_task(task) {
-        Ok(task) => Ok(ApiResponse::success(task)),
-        Err(e) => Ok(ApiResponse::error(format!("创建待办失败: {}", e))),
+    task.estimated_minutes = request.estimated_minutes.filter(|m| *m > 0);
+
+    storage.add_task(task)
+}
+
+#[tauri::command]
+pub async fn create_task(
+    request: CreateTaskRequest,
+    app: tauri::AppHandle,
+    storage: StorageState<'_>,
+) -> AppResult<ApiResponse<Task>> {
+    let storage = storage.lock()?;
+
+    match create_task_from_request(&storage, request) {
+        Ok(task) => {
+            tray::refresh_tray(&app);
+            Ok(ApiResponse::success(task))
+        }
+        Err(e) => Ok(ApiResponse::error(Msg::CreateTaskFailed, e)),
     }
 }
 
@@ -44,111 +92,664 @@ pub async fn create_task(
 pub async fn update_task(
     id: String,
     updates: UpdateTaskRequest,
+    app: tauri::AppHandle,
     storage: StorageState<'_>,
-) -> Result<ApiResponse<Option<Task>>, String> {
-    let storage = storage.lock().map_err(|e| e.to_string())?;
-    
+) -> AppResult<ApiResponse<Task>> {
+    let storage = storage.lock()?;
+
     match storage.update_task(&id, &updates) {
-        Ok(task) => Ok(ApiResponse::success(task)),
-        Err(e) => Ok(ApiResponse::error(format!("更新待办失败: {}", e))),
+        Ok(task) => {
+            tray::refresh_tray(&app);
+            Ok(ApiResponse::success(task))
+        }
+        Err(e) => Ok(ApiResponse::error(Msg::UpdateTaskFailed, e)),
     }
 }
 
 #[tauri::command]
 pub async fn delete_task(
     id: String,
+    app: tauri::AppHandle,
     storage: StorageState<'_>,
-) -> Result<ApiResponse<bool>, String> {
-    let storage = storage.lock().map_err(|e| e.to_string())?;
-    
+) -> AppResult<ApiResponse<bool>> {
+    let storage = storage.lock()?;
+
     match storage.delete_task(&id) {
-        Ok(deleted) => {
-            if deleted {
-                Ok(ApiResponse::success(true))
-            } else {
-                Ok(ApiResponse::error("待办不存在".to_string()))
-            }
+        Ok(()) => {
+            tray::refresh_tray(&app);
+            Ok(ApiResponse::success(true))
         }
-        Err(e) => Ok(ApiResponse::error(format!("删除待办失败: {}", e))),
+        Err(e) => Ok(ApiResponse::error(Msg::DeleteTaskFailed, e)),
     }
 }
 
 #[tauri::command]
-pub async fn get_task_stats(storage: StorageState<'_>) -> Result<ApiResponse<TaskStats>, String> {
-    let storage = storage.lock().map_err(|e| e.to_string())?;
-    
+pub async fn get_task_stats(storage: StorageState<'_>) -> AppResult<ApiResponse<TaskStats>> {
+    let storage = storage.lock()?;
+
     match storage.load_tasks() {
         Ok(tasks) => {
             let stats = storage.get_task_stats(&tasks);
             Ok(ApiResponse::success(stats))
         }
-        Err(e) => Ok(ApiResponse::error(format!("获取统计信息失败: {}", e))),
+        Err(e) => Ok(ApiResponse::error(Msg::StatsFailed, e)),
+    }
+}
+
+/// 解析趋势统计的日期区间，默认最近 7 天（含今天）
+fn trend_range(start_date: Option<&str>, end_date: Option<&str>) -> AppResult<(chrono::NaiveDate, chrono::NaiveDate)> {
+    date_range(start_date, end_date, 7)
+}
+
+/// 解析并校验日期区间，结束日期默认为今天，开始日期默认使区间共 `default_days` 天
+fn date_range(start_date: Option<&str>, end_date: Option<&str>, default_days: i64) -> AppResult<(chrono::NaiveDate, chrono::NaiveDate)> {
+    let end = match end_date {
+        Some(value) => parse_date(value)
+            .ok_or_else(|| AppError::invalid_input(Msg::InvalidEndDate.text()))?,
+        None => Local::now().date_naive(),
+    };
+    let start = match start_date {
+        Some(value) => parse_date(value)
+            .ok_or_else(|| AppError::invalid_input(Msg::InvalidStartDate.text()))?,
+        None => end - Duration::days(default_days - 1),
+    };
+
+    if start > end {
+        return Err(AppError::invalid_input(Msg::StartAfterEnd.text()));
+    }
+    if (end - start).num_days() >= MAX_TREND_DAYS {
+        return Err(AppError::invalid_input(fill(Msg::RangeTooLong.text(), &[&MAX_TREND_DAYS])));
+    }
+
+    Ok((start, end))
+}
+
+#[tauri::command]
+pub async fn get_task_trend(
+    start_date: Option<String>,
+    end_date: Option<String>,
+    storage: StorageState<'_>,
+) -> AppResult<ApiResponse<Vec<TaskTrendPoint>>> {
+    let storage = storage.lock()?;
+
+    let result = trend_range(start_date.as_deref(), end_date.as_deref())
+        .and_then(|(start, end)| {
+            let tasks = storage.load_tasks()?;
+            Ok(storage.get_task_trend(&tasks, start, end))
+        });
+
+    match result {
+        Ok(points) => Ok(ApiResponse::success(points)),
+        Err(e) => Ok(ApiResponse::error(Msg::TrendFailed, e)),
     }
 }
 
+/// 完成热力图、连续完成天数等分析，默认统计截至今天的一年
 #[tauri::command]
-pub async fn get_settings(storage: StorageState<'_>) -> Result<ApiResponse<Settings>, String> {
-    let storage = storage.lock().map_err(|e| e.to_string())?;
-    
+pub async fn get_productivity_stats(
+    start_date: Option<String>,
+    end_date: Option<String>,
+    storage: StorageState<'_>,
+) -> AppResult<ApiResponse<ProductivityStats>> {
+    let storage = storage.lock()?;
+
+    let result = date_range(start_date.as_deref(), end_date.as_deref(), MAX_TREND_DAYS)
+        .and_then(|(start, end)| {
+            let tasks = storage.load_tasks()?;
+            Ok(storage.get_productivity_stats(&tasks, start, end))
+        });
+
+    match result {
+        Ok(stats) => Ok(ApiResponse::success(stats)),
+        Err(e) => Ok(ApiResponse::error(Msg::ProductivityStatsFailed, e)),
+    }
+}
+
+#[tauri::command]
+pub async fn get_settings(storage: StorageState<'_>) -> AppResult<ApiResponse<Settings>> {
+    let storage = storage.lock()?;
+
     match storage.load_settings() {
         Ok(settings) => Ok(ApiResponse::success(settings)),
-        Err(e) => Ok(ApiResponse::error(format!("加载设置失败: {}", e))),
+        Err(e) => Ok(ApiResponse::error(Msg::LoadSettingsFailed, e)),
     }
 }
 
 #[tauri::command]
 pub async fn update_settings(
-    settings: Settings,
+    mut settings: Settings,
+    app: tauri::AppHandle,
     storage: StorageState<'_>,
-) -> Result<ApiResponse<Settings>, String> {
-    let storage = storage.lock().map_err(|e| e.to_string())?;
-    
-    match storage.save_settings(&settings) {
-        Ok(_) => Ok(ApiResponse::success(settings)),
-        Err(e) => Ok(ApiResponse::error(format!("保存设置失败: {}", e))),
+) -> AppResult<ApiResponse<Settings>> {
+    let storage = storage.lock()?;
+
+    // 前端未回传的未知字段沿用磁盘上的值
+    if let Ok(previous) = storage.load_settings() {
+        settings.merge_unknown_from(&previous);
+    }
+    settings.normalize();
+
+    if let Err(e) = storage.save_settings(&settings) {
+        return Ok(ApiResponse::error(Msg::SaveSettingsFailed, e));
+    }
+
+    // 语言、置顶状态可能变化，更新托盘
+    i18n::set_locale(settings.locale);
+    tray::refresh_tray(&app);
+
+    // 设置已保存；接口启动失败（如端口被占用）、快捷键被占用或无法写入开机启动项时返回错误供前端提示
+    let api_result = api_server::apply_settings(&app, &storage, &settings);
+    let shortcut_result = quick_add::apply_settings(&app, &settings);
+    let autostart_result = autostart::apply_settings(&app, &settings);
+    if let Err(e) = api_result {
+        return Ok(ApiResponse::error(Msg::StartApiFailed, e));
+    }
+    if let Err(e) = shortcut_result {
+        return Ok(ApiResponse::error(Msg::RegisterShortcutFailed, e));
+    }
+    if let Err(e) = autostart_result {
+        return Ok(ApiResponse::error(Msg::AutostartFailed, e));
+    }
+
+    Ok(ApiResponse::success(settings))
+}
+
+#[tauri::command]
+pub async fn get_api_status(app: tauri::AppHandle, storage: StorageState<'_>) -> AppResult<ApiResponse<ApiStatus>> {
+    let storage = storage.lock()?;
+
+    let result = storage.load_settings()
+        .and_then(|settings| api_server::status(&app, &storage, &settings));
+
+    match result {
+        Ok(status) => Ok(ApiResponse::success(status)),
+        Err(e) => Ok(ApiResponse::error(Msg::LoadSettingsFailed, e)),
+    }
+}
+
+#[tauri::command]
+pub async fn reset_api_token(app: tauri::AppHandle, storage: StorageState<'_>) -> AppResult<ApiResponse<String>> {
+    let storage = storage.lock()?;
+
+    match api_server::reset_token(&app, &storage) {
+        Ok(token) => Ok(ApiResponse::success(token)),
+        Err(e) => Ok(ApiResponse::error(Msg::ResetApiTokenFailed, e)),
+    }
+}
+
+#[tauri::command]
+pub async fn get_shortcut_status(app: tauri::AppHandle, storage: StorageState<'_>) -> AppResult<ApiResponse<ShortcutStatus>> {
+    let storage = storage.lock()?;
+
+    let result = storage.load_settings()
+        .and_then(|settings| quick_add::status(&app, &settings));
+
+    match result {
+        Ok(status) => Ok(ApiResponse::success(status)),
+        Err(e) => Ok(ApiResponse::error(Msg::LoadSettingsFailed, e)),
+    }
+}
+
+/// 快速添加窗口提交：保存后通知主窗口刷新并收起快速添加窗口
+#[tauri::command]
+pub async fn submit_quick_add(
+    request: CreateTaskRequest,
+    app: tauri::AppHandle,
+    storage: StorageState<'_>,
+) -> AppResult<ApiResponse<Task>> {
+    let result = create_task_from_request(&*storage.lock()?, request);
+
+    match result {
+        Ok(task) => {
+            notify_tasks_changed(&app);
+            quick_add::hide_quick_add(&app);
+            Ok(ApiResponse::success(task))
+        }
+        Err(e) => Ok(ApiResponse::error(Msg::CreateTaskFailed, e)),
+    }
+}
+
+#[tauri::command]
+pub async fn hide_quick_add(app: tauri::AppHandle) -> AppResult<ApiResponse<bool>> {
+    quick_add::hide_quick_add(&app);
+    Ok(ApiResponse::success(true))
+}
+
+fn pomodoro_response(result: AppResult<PomodoroState>) -> AppResult<ApiResponse<PomodoroState>> {
+    match result {
+        Ok(state) => Ok(ApiResponse::success(state)),
+        Err(e) => Ok(ApiResponse::error(Msg::PomodoroFailed, e)),
+    }
+}
+
+#[tauri::command]
+pub async fn get_pomodoro_state(app: tauri::AppHandle) -> AppResult<ApiResponse<PomodoroState>> {
+    pomodoro_response(pomodoro::current(&app))
+}
+
+/// 开始或继续番茄钟，传入待办 id 时专注时长计入该待办
+#[tauri::command]
+pub async fn start_pomodoro(task_id: Option<String>, app: tauri::AppHandle) -> AppResult<ApiResponse<PomodoroState>> {
+    pomodoro_response(pomodoro::start(&app, task_id))
+}
+
+#[tauri::command]
+pub async fn set_pomodoro_task(task_id: Option<String>, app: tauri::AppHandle) -> AppResult<ApiResponse<PomodoroState>> {
+    pomodoro_response(pomodoro::set_task(&app, task_id))
+}
+
+#[tauri::command]
+pub async fn pause_pomodoro(app: tauri::AppHandle) -> AppResult<ApiResponse<PomodoroState>> {
+    pomodoro_response(pomodoro::pause(&app))
+}
+
+#[tauri::command]
+pub async fn reset_pomodoro(app: tauri::AppHandle) -> AppResult<ApiResponse<PomodoroState>> {
+    pomodoro_response(pomodoro::reset(&app))
+}
+
+/// 立即结束当前阶段并进入下一阶段
+#[tauri::command]
+pub async fn skip_pomodoro(app: tauri::AppHandle) -> AppResult<ApiResponse<PomodoroState>> {
+    pomodoro_response(pomodoro::skip(&app))
+}
+
+/// 已完成的番茄钟专注记录，可只看某个待办的
+#[tauri::command]
+pub async fn get_focus_sessions(
+    task_id: Option<String>,
+    storage: StorageState<'_>,
+) -> AppResult<ApiResponse<Vec<FocusSession>>> {
+    let storage = storage.lock()?;
+
+    match pomodoro::sessions(&storage, task_id.as_deref()) {
+        Ok(sessions) => Ok(ApiResponse::success(sessions)),
+        Err(e) => Ok(ApiResponse::error(Msg::PomodoroFailed, e)),
+    }
+}
+
+#[tauri::command]
+pub async fn update_pomodoro_settings(
+    settings: PomodoroSettings,
+    app: tauri::AppHandle,
+) -> AppResult<ApiResponse<PomodoroState>> {
+    pomodoro_response(pomodoro::update_settings(&app, settings))
+}
+
+fn time_entry_response<T>(result: AppResult<T>) -> AppResult<ApiResponse<T>> {
+    match result {
+        Ok(data) => Ok(ApiResponse::success(data)),
+        Err(e) => Ok(ApiResponse::error(Msg::TimeEntryFailed, e)),
+    }
+}
+
+/// 在待办上开始计时，同一时间只有一个计时，其他待办上的计时会被停止
+#[tauri::command]
+pub async fn start_time_entry(task_id: String, storage: StorageState<'_>) -> AppResult<ApiResponse<TimeEntry>> {
+    let storage = storage.lock()?;
+    time_entry_response(time_tracking::start(&storage, &task_id))
+}
+
+#[tauri::command]
+pub async fn stop_time_entry(storage: StorageState<'_>) -> AppResult<ApiResponse<Option<TimeEntry>>> {
+    let storage = storage.lock()?;
+    time_entry_response(time_tracking::stop(&storage))
+}
+
+/// 正在进行的计时，应用重启后前端据此恢复显示
+#[tauri::command]
+pub async fn get_running_time_entry(storage: StorageState<'_>) -> AppResult<ApiResponse<Option<TimeEntry>>> {
+    let storage = storage.lock()?;
+    time_entry_response(time_tracking::running(&storage))
+}
+
+#[tauri::command]
+pub async fn get_time_entries(
+    task_id: Option<String>,
+    storage: StorageState<'_>,
+) -> AppResult<ApiResponse<Vec<TimeEntry>>> {
+    let storage = storage.lock()?;
+    time_entry_response(time_tracking::entries(&storage, task_id.as_deref()))
+}
+
+#[tauri::command]
+pub async fn add_time_entry(
+    request: AddTimeEntryRequest,
+    storage: StorageState<'_>,
+) -> AppResult<ApiResponse<TimeEntry>> {
+    let storage = storage.lock()?;
+    time_entry_response(time_tracking::add(&storage, request))
+}
+
+#[tauri::command]
+pub async fn update_time_entry(
+    id: String,
+    updates: UpdateTimeEntryRequest,
+    storage: StorageState<'_>,
+) -> AppResult<ApiResponse<TimeEntry>> {
+    let storage = storage.lock()?;
+    time_entry_response(time_tracking::update(&storage, &id, updates))
+}
+
+#[tauri::command]
+pub async fn delete_time_entry(id: String, storage: StorageState<'_>) -> AppResult<ApiResponse<bool>> {
+    let storage = storage.lock()?;
+    time_entry_response(time_tracking::delete(&storage, &id).map(|_| true))
+}
+
+#[tauri::command]
+pub async fn get_usage_stats(app: tauri::AppHandle, storage: StorageState<'_>) -> AppResult<ApiResponse<UsageStats>> {
+    let storage = storage.lock()?;
+
+    match usage::stats(&app, &storage) {
+        Ok(stats) => Ok(ApiResponse::success(stats)),
+        Err(e) => Ok(ApiResponse::error(Msg::UsageStatsFailed, e)),
     }
 }
 
+/// 按天列出使用时长，日期区间规则与趋势统计相同
 #[tauri::command]
-pub async fn export_data(storage: StorageState<'_>) -> Result<ApiResponse<String>, String> {
-    let storage = storage.lock().map_err(|e| e.to_string())?;
-    
+pub async fn get_usage_details(
+    start_date: Option<String>,
+    end_date: Option<String>,
+    app: tauri::AppHandle,
+    storage: StorageState<'_>,
+) -> AppResult<ApiResponse<Vec<UsageDetail>>> {
+    let storage = storage.lock()?;
+
+    let result = trend_range(start_date.as_deref(), end_date.as_deref())
+        .and_then(|(start, end)| usage::details(&app, &storage, start, end));
+
+    match result {
+        Ok(details) => Ok(ApiResponse::success(details)),
+        Err(e) => Ok(ApiResponse::error(Msg::UsageStatsFailed, e)),
+    }
+}
+
+/// 导入旧版本保存在浏览器缓存中的每日使用分钟数
+#[tauri::command]
+pub async fn import_legacy_usage(
+    daily: std::collections::BTreeMap<String, u32>,
+    storage: StorageState<'_>,
+) -> AppResult<ApiResponse<usize>> {
+    let storage = storage.lock()?;
+
+    match usage::import_legacy(&storage, &daily) {
+        Ok(count) => Ok(ApiResponse::success(count)),
+        Err(e) => Ok(ApiResponse::error(Msg::ImportUsageFailed, e)),
+    }
+}
+
+/// 自定义区间与趋势统计规则相同，否则取本周或本月
+fn report_range(request: &ExportReportRequest) -> AppResult<(chrono::NaiveDate, chrono::NaiveDate)> {
+    if request.start_date.is_some() || request.end_date.is_some() {
+        trend_range(request.start_date.as_deref(), request.end_date.as_deref())
+    } else {
+        Ok(report::period_range(request.kind, Local::now().date_naive()))
+    }
+}
+
+fn choose_save_path(app: &tauri::AppHandle, title: Msg, file_name: &str, extension: &str) -> Option<std::path::PathBuf> {
+    app.dialog()
+        .file()
+        .set_title(title.text())
+        .set_file_name(file_name)
+        .add_filter(extension.to_uppercase(), &[extension])
+        .blocking_save_file()
+        .and_then(|path| path.into_path().ok())
+}
+
+/// 生成周报或月报并写入文件，返回保存的路径；在保存对话框中取消时返回 null
+#[tauri::command]
+pub async fn export_report(
+    request: ExportReportRequest,
+    app: tauri::AppHandle,
+    storage: StorageState<'_>,
+) -> AppResult<ApiResponse<Option<String>>> {
+    let (start, end) = match report_range(&request) {
+        Ok(range) => range,
+        Err(e) => return Ok(ApiResponse::error(Msg::ExportReportFailed, e)),
+    };
+    let template = request.template.as_deref();
+    let format = report::effective_format(request.format, template);
+
+    // 对话框打开期间不持有存储锁
+    let path = match &request.path {
+        Some(path) => Some(std::path::PathBuf::from(path)),
+        None => choose_save_path(&app, Msg::ReportSaveTitle, &report::default_file_name(request.kind, format, start, end), format.extension()),
+    };
+    let Some(path) = path else {
+        return Ok(ApiResponse::success(None));
+    };
+
+    let storage = storage.lock()?;
+    let result = ReportSource::load(&storage).and_then(|source| {
+        let data = report::build_report(&source, request.kind, start, end, &Local::now());
+        report::export(&storage, &data, format, template, &path)
+    });
+
+    match result {
+        Ok(()) => Ok(ApiResponse::success(Some(path.to_string_lossy().to_string()))),
+        Err(e) => Ok(ApiResponse::error(Msg::ExportReportFailed, e)),
+    }
+}
+
+/// 按导出时的规则渲染报告内容，用于在编写模板时预览效果
+#[tauri::command]
+pub async fn preview_report(
+    request: ExportReportRequest,
+    storage: StorageState<'_>,
+) -> AppResult<ApiResponse<String>> {
+    let storage = storage.lock()?;
+
+    let result = report_range(&request).and_then(|(start, end)| {
+        let source = ReportSource::load(&storage)?;
+        let data = report::build_report(&source, request.kind, start, end, &Local::now());
+        report::render(&storage, &data, request.format, request.template.as_deref())
+    });
+
+    match result {
+        Ok(content) => Ok(ApiResponse::success(content)),
+        Err(e) => Ok(ApiResponse::error(Msg::PreviewReportFailed, e)),
+    }
+}
+
+/// 数据目录 report_templates 下的自定义报告模板
+#[tauri::command]
+pub async fn list_report_templates(storage: StorageState<'_>) -> AppResult<ApiResponse<Vec<ReportTemplate>>> {
+    let storage = storage.lock()?;
+
+    match report::list_templates(&storage) {
+        Ok(templates) => Ok(ApiResponse::success(templates)),
+        Err(e) => Ok(ApiResponse::error(Msg::LoadReportTemplatesFailed, e)),
+    }
+}
+
+/// 导出待办为 CSV，返回保存的路径；在保存对话框中取消时返回 null
+#[tauri::command]
+pub async fn export_tasks_csv(
+    request: ExportTasksCsvRequest,
+    app: tauri::AppHandle,
+    storage: StorageState<'_>,
+) -> AppResult<ApiResponse<Option<String>>> {
+    let columns = request.columns
+        .filter(|columns| !columns.is_empty())
+        .unwrap_or_else(|| TaskField::ALL.to_vec());
+
+    // 对话框打开期间不持有存储锁
+    let path = match &request.path {
+        Some(path) => Some(std::path::PathBuf::from(path)),
+        None => choose_save_path(&app, Msg::CsvSaveTitle, &task_csv::default_file_name(Local::now().date_naive()), "csv"),
+    };
+    let Some(path) = path else {
+        return Ok(ApiResponse::success(None));
+    };
+
+    let storage = storage.lock()?;
+    let result = storage.load_tasks()
+        .and_then(|tasks| task_csv::export(&tasks, &columns, &Local, &path));
+
+    match result {
+        Ok(()) => Ok(ApiResponse::success(Some(path.to_string_lossy().into_owned()))),
+        Err(e) => Ok(ApiResponse::error(Msg::ExportCsvFailed, e)),
+    }
+}
+
+/// 读取 CSV 的表头与前几行，并按表头推测每列对应的字段
+#[tauri::command]
+pub async fn inspect_tasks_csv(content: String) -> AppResult<ApiResponse<CsvInspection>> {
+    match task_csv::inspect(&content) {
+        Ok(inspection) => Ok(ApiResponse::success(inspection)),
+        Err(e) => Ok(ApiResponse::error(Msg::ImportCsvFailed, e)),
+    }
+}
+
+/// 按列映射导入 CSV，追加到现有待办之后；无法识别的行跳过并在结果中列出
+#[tauri::command]
+pub async fn import_tasks_csv(
+    request: ImportTasksCsvRequest,
+    app: tauri::AppHandle,
+    storage: StorageState<'_>,
+) -> AppResult<ApiResponse<TaskImportResult>> {
+    let storage = storage.lock()?;
+
+    let result = task_csv::parse(&request.content, &request.mapping, &Local).and_then(|result| {
+        if !request.dry_run && !result.tasks.is_empty() {
+            storage.add_tasks(&result.tasks)?;
+            tray::refresh_tray(&app);
+        }
+        Ok(result)
+    });
+
+    match result {
+        Ok(result) => Ok(ApiResponse::success(result)),
+        Err(e) => Ok(ApiResponse::error(Msg::ImportCsvFailed, e)),
+    }
+}
+
+/// 生成 Markdown 或 todo.txt 格式的待办清单，用于预览和复制到剪贴板
+#[tauri::command]
+pub async fn render_task_list(
+    request: TaskListRequest,
+    storage: StorageState<'_>,
+) -> AppResult<ApiResponse<String>> {
+    let storage = storage.lock()?;
+    let group_by = request.group_by.unwrap_or_default();
+    let include_completed = request.include_completed.unwrap_or(true);
+
     match storage.load_tasks() {
-        Ok(tasks) => {
-            match serde_json::to_string_pretty(&tasks) {
-                Ok(json_data) => Ok(ApiResponse::success(json_data)),
-                Err(e) => Ok(ApiResponse::error(format!("导出数据失败: {}", e))),
-            }
+        Ok(tasks) => Ok(ApiResponse::success(task_list::render(&tasks, request.format, group_by, include_completed, &Local::now()))),
+        Err(e) => Ok(ApiResponse::error(Msg::ExportTaskListFailed, e)),
+    }
+}
+
+/// 导出待办清单到文件，返回保存的路径；在保存对话框中取消时返回 null
+#[tauri::command]
+pub async fn export_task_list(
+    request: TaskListRequest,
+    app: tauri::AppHandle,
+    storage: StorageState<'_>,
+) -> AppResult<ApiResponse<Option<String>>> {
+    let group_by = request.group_by.unwrap_or_default();
+    let include_completed = request.include_completed.unwrap_or(true);
+
+    // 对话框打开期间不持有存储锁
+    let path = match &request.path {
+        Some(path) => Some(std::path::PathBuf::from(path)),
+        None => {
+            let file_name = task_list::default_file_name(request.format, Local::now().date_naive());
+            choose_save_path(&app, Msg::TaskListSaveTitle, &file_name, request.format.extension())
+        }
+    };
+    let Some(path) = path else {
+        return Ok(ApiResponse::success(None));
+    };
+
+    let storage = storage.lock()?;
+    let result = storage.load_tasks()
+        .and_then(|tasks| task_list::export(&tasks, request.format, group_by, include_completed, &Local::now(), &path));
+
+    match result {
+        Ok(()) => Ok(ApiResponse::success(Some(path.to_string_lossy().into_owned()))),
+        Err(e) => Ok(ApiResponse::error(Msg::ExportTaskListFailed, e)),
+    }
+}
+
+/// 从其他待办应用的导出文件导入，追加到现有待办之后；dry_run 时只返回解析结果供预览
+#[tauri::command]
+pub async fn import_from_app(
+    request: ImportFromAppRequest,
+    app: tauri::AppHandle,
+    storage: StorageState<'_>,
+) -> AppResult<ApiResponse<TaskImportResult>> {
+    let storage = storage.lock()?;
+
+    let result = importers::parse(request.source, &request.content, request.skip_completed, &Local).and_then(|result| {
+        if !request.dry_run && !result.tasks.is_empty() {
+            storage.add_tasks(&result.tasks)?;
+            tray::refresh_tray(&app);
         }
-        Err(e) => Ok(ApiResponse::error(format!("加载待办失败: {}", e))),
+        Ok(result)
+    });
+
+    match result {
+        Ok(result) => Ok(ApiResponse::success(result)),
+        Err(e) => Ok(ApiResponse::error(Msg::ImportFromAppFailed, e)),
+    }
+}
+
+#[tauri::command]
+pub async fn export_data(storage: StorageState<'_>) -> AppResult<ApiResponse<String>> {
+    let storage = storage.lock()?;
+
+    let result = storage.load_tasks()
+        .and_then(|tasks| Ok(serde_json::to_string_pretty(&tasks)?));
+
+    match result {
+        Ok(json_data) => Ok(ApiResponse::success(json_data)),
+        Err(e) => Ok(ApiResponse::error(Msg::ExportFailed, e)),
     }
 }
 
 #[tauri::command]
 pub async fn import_data(
-    data: String,
+    json_data: String,
+    app: tauri::AppHandle,
     storage: StorageState<'_>,
-) -> Result<ApiResponse<bool>, String> {
-    let storage = storage.lock().map_err(|e| e.to_string())?;
-    
-    match serde_json::from_str::<Vec<Task>>(&data) {
-        Ok(tasks) => {
-            match storage.save_tasks(&tasks) {
-                Ok(_) => Ok(ApiResponse::success(true)),
-                Err(e) => Ok(ApiResponse::error(format!("导入数据失败: {}", e))),
-            }
+) -> AppResult<ApiResponse<bool>> {
+    let storage = storage.lock()?;
+
+    let (tasks, settings) = match serde_json::from_str::<ImportPayload>(&json_data) {
+        Ok(ImportPayload::Tasks(tasks)) => (tasks, None),
+        Ok(ImportPayload::Backup { tasks, settings }) => (tasks, settings),
+        Err(e) => return Ok(ApiResponse::error(Msg::ParseImportFailed, AppError::invalid_input(e.to_string()))),
+    };
+
+    if let Some(settings) = settings {
+        if let Err(e) = storage.save_settings(&settings) {
+            return Ok(ApiResponse::error(Msg::ImportSettingsFailed, e));
         }
-        Err(e) => Ok(ApiResponse::error(format!("解析数据失败: {}", e))),
+    }
+
+    match storage.save_tasks(&tasks) {
+        Ok(_) => {
+            tray::refresh_tray(&app);
+            Ok(ApiResponse::success(true))
+        }
+        Err(e) => Ok(ApiResponse::error(Msg::ImportFailed, e)),
     }
 }
 
 #[tauri::command]
-pub async fn clear_all_data(storage: StorageState<'_>) -> Result<ApiResponse<bool>, String> {
-    let storage = storage.lock().map_err(|e| e.to_string())?;
-    
+pub async fn clear_all_data(app: tauri::AppHandle, storage: StorageState<'_>) -> AppResult<ApiResponse<bool>> {
+    let storage = storage.lock()?;
+
     match storage.save_tasks(&[]) {
-        Ok(_) => Ok(ApiResponse::success(true)),
-        Err(e) => Ok(ApiResponse::error(format!("清空数据失败: {}", e))),
+        Ok(_) => {
+            tray::refresh_tray(&app);
+            Ok(ApiResponse::success(true))
+        }
+        Err(e) => Ok(ApiResponse::error(Msg::ClearFailed, e)),
     }
 }
 
@@ -157,65 +758,159 @@ pub fn exit_app(app: tauri::AppHandle) {
     app.exit(0);
 }
 
+/// 取走等待打开的路由（深链接等触发），没有时返回 null
 #[tauri::command]
-pub async fn get_data_dir_path(storage: StorageState<'_>) -> Result<ApiResponse<String>, String> {
-    let storage = storage.lock().map_err(|e| e.to_string())?;
+pub async fn take_pending_route(pending: State<'_, PendingRoute>) -> AppResult<ApiResponse<Option<String>>> {
+    let route = pending.0.lock()?.take();
+    Ok(ApiResponse::success(route))
+}
+
+#[tauri::command]
+pub async fn get_data_dir_path(storage: StorageState<'_>) -> AppResult<ApiResponse<String>> {
+    let storage = storage.lock()?;
     let path = storage.get_data_dir_path();
     Ok(ApiResponse::success(path))
 }
 
 #[tauri::command]
-pub async fn open_file_with_system(file_name: String, file_data: String, _file_type: String) -> Result<ApiResponse<bool>, String> {
+pub async fn get_sync_status(storage: StorageState<'_>) -> AppResult<ApiResponse<SyncStatus>> {
+    let storage = storage.lock()?;
+
+    match sync::status(&storage) {
+        Ok(status) => Ok(ApiResponse::success(status)),
+        Err(e) => Ok(ApiResponse::error(Msg::LoadSyncStatusFailed, e)),
+    }
+}
+
+/// 选择同步文件夹（如网盘中的文件夹），取消时返回 null
+#[tauri::command]
+pub async fn choose_sync_folder(app: tauri::AppHandle) -> AppResult<ApiResponse<Option<String>>> {
+    let folder = app.dialog()
+        .file()
+        .set_title(Msg::SyncFolderPickTitle.text())
+        .blocking_pick_folder()
+        .and_then(|path| path.into_path().ok());
+    Ok(ApiResponse::success(folder.map(|f| f.to_string_lossy().into_owned())))
+}
+
+/// 设置同步文件夹与本机名称；更换文件夹后立即与其中已有的数据合并
+#[tauri::command]
+pub async fn configure_sync(
+    request: ConfigureSyncRequest,
+    app: tauri::AppHandle,
+    storage: StorageState<'_>,
+) -> AppResult<ApiResponse<SyncStatus>> {
+    let storage = storage.lock()?;
+    let folder = request.folder.filter(|f| !f.trim().is_empty()).map(std::path::PathBuf::from);
+
+    let result = sync::configure(&storage, folder, request.device_name).and_then(|changed| {
+        if changed {
+            notify_tasks_changed(&app);
+        }
+        sync::status(&storage)
+    });
+
+    match result {
+        Ok(status) => Ok(ApiResponse::success(status)),
+        Err(e) => Ok(ApiResponse::error(Msg::ConfigureSyncFailed, e)),
+    }
+}
+
+#[tauri::command]
+pub async fn sync_now(app: tauri::AppHandle, storage: StorageState<'_>) -> AppResult<ApiResponse<SyncStatus>> {
+    let result = sync::sync_shared(&storage).and_then(|changed| {
+        if changed {
+            notify_tasks_changed(&app);
+        }
+        sync::status(&*storage.lock()?)
+    });
+
+    match result {
+        Ok(status) => Ok(ApiResponse::success(status)),
+        Err(e) => Ok(ApiResponse::error(Msg::SyncFailed, e)),
+    }
+}
+
+#[tauri::command]
+pub async fn get_sync_conflicts(storage: StorageState<'_>) -> AppResult<ApiResponse<Vec<SyncConflict>>> {
+    let storage = storage.lock()?;
+
+    match sync::conflicts(&storage) {
+        Ok(conflicts) => Ok(ApiResponse::success(conflicts)),
+        Err(e) => Ok(ApiResponse::error(Msg::LoadSyncStatusFailed, e)),
+    }
+}
+
+/// 处理同步冲突：use_other 为 true 时改用被覆盖的值，否则保留当前值
+#[tauri::command]
+pub async fn resolve_sync_conflict(
+    id: String,
+    use_other: bool,
+    app: tauri::AppHandle,
+    storage: StorageState<'_>,
+) -> AppResult<ApiResponse<bool>> {
+    let storage = storage.lock()?;
+
+    match sync::resolve_conflict(&storage, &id, use_other) {
+        Ok(changed) => {
+            if changed {
+                notify_tasks_changed(&app);
+            }
+            Ok(ApiResponse::success(true))
+        }
+        Err(e) => Ok(ApiResponse::error(Msg::ResolveConflictFailed, e)),
+    }
+}
+
+/// 使用系统默认程序打开文件
+fn open_with_system(path: &std::path::Path) -> std::io::Result<()> {
+    #[cfg(target_os = "windows")]
+    {
+        std::process::Command::new("cmd")
+            .args(["/C", "start", ""])
+            .arg(path)
+            .spawn()?;
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        std::process::Command::new("open")
+            .arg(path)
+            .spawn()?;
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        std::process::Command::new("xdg-open")
+            .arg(path)
+            .spawn()?;
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn open_file_with_system(file_name: String, file_data: String, _file_type: String) -> AppResult<ApiResponse<bool>> {
     use std::fs;
     use base64::{Engine as _, engine::general_purpose};
-    
+
     // 创建临时文件
     let temp_dir = std::env::temp_dir();
     let file_path = temp_dir.join(&file_name);
-    
+
     // 解码 base64 数据
-    match general_purpose::STANDARD.decode(&file_data) {
-        Ok(decoded_data) => {
-            // 写入临时文件
-            match fs::write(&file_path, decoded_data) {
-                Ok(_) => {
-                    // 使用系统默认应用打开文件
-                    #[cfg(target_os = "windows")]
-                    {
-                        match std::process::Command::new("cmd")
-                            .args(&["/C", "start", "", file_path.to_str().unwrap()])
-                            .spawn()
-                        {
-                            Ok(_) => Ok(ApiResponse::success(true)),
-                            Err(e) => Ok(ApiResponse::error(format!("打开文件失败: {}", e))),
-                        }
-                    }
-                    
-                    #[cfg(target_os = "macos")]
-                    {
-                        match std::process::Command::new("open")
-                            .arg(&file_path)
-                            .spawn()
-                        {
-                            Ok(_) => Ok(ApiResponse::success(true)),
-                            Err(e) => Ok(ApiResponse::error(format!("打开文件失败: {}", e))),
-                        }
-                    }
-                    
-                    #[cfg(target_os = "linux")]
-                    {
-                        match std::process::Command::new("xdg-open")
-                            .arg(&file_path)
-                            .spawn()
-                        {
-                            Ok(_) => Ok(ApiResponse::success(true)),
-                            Err(e) => Ok(ApiResponse::error(format!("打开文件失败: {}", e))),
-                        }
-                    }
-                }
-                Err(e) => Ok(ApiResponse::error(format!("写入临时文件失败: {}", e))),
-            }
-        }
-        Err(e) => Ok(ApiResponse::error(format!("解码文件数据失败: {}", e))),
+    let decoded_data = match general_purpose::STANDARD.decode(&file_data) {
+        Ok(data) => data,
+        Err(e) => return Ok(ApiResponse::error(Msg::DecodeFileFailed, AppError::invalid_input(e.to_string()))),
+    };
+
+    // 写入临时文件
+    if let Err(e) = fs::write(&file_path, decoded_data) {
+        return Ok(ApiResponse::error(Msg::WriteTempFileFailed, e.into()));
     }
-}
\ No newline at end of file
+
+    match open_with_system(&file_path) {
+        Ok(_) => Ok(ApiResponse::success(true)),
+        Err(e) => Ok(ApiResponse::error(Msg::OpenFileFailed, e.into())),
+    }
+}
@@ -0,0 +1,293 @@
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta, TimeZone};
+
+// 前端写入的日期时间格式（不带时区，按本地时间理解）
+const NAIVE_DATETIME_FORMATS: &[&str] = &[
+    "%Y-%m-%dT%H:%M:%S%.f",
+    "%Y-%m-%dT%H:%M",
+    "%Y-%m-%d %H:%M:%S",
+    "%Y-%m-%d %H:%M",
+];
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// 将本地时间转换为指定时区的时刻
+///
+/// 夏令时回拨造成的重复时间取较早的一个；夏令时跳变造成的不存在时间顺延到跳变之后
+pub fn resolve_local<Tz: TimeZone>(naive: NaiveDateTime, tz: &Tz) -> DateTime<Tz> {
+    if let Some(dt) = tz.from_local_datetime(&naive).earliest() {
+        return dt;
+    }
+
+    // 跳变区间一般不超过一小时，逐步向后试探
+    let mut probe = naive;
+    for _ in 0..4 {
+        probe += Duration::minutes(30);
+        if let Some(dt) = tz.from_local_datetime(&probe).earliest() {
+            return dt;
+        }
+    }
+
+    tz.from_utc_datetime(&naive)
+}
+
+/// 指定日期在本地时区的零点
+pub fn start_of_day<Tz: TimeZone>(date: NaiveDate, tz: &Tz) -> DateTime<Tz> {
+    resolve_local(date.and_hms_opt(0, 0, 0).unwrap(), tz)
+}
+
+/// 解析仅包含日期的字符串（YYYY-MM-DD）
+pub fn parse_date(value: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(value.trim(), DATE_FORMAT).ok()
+}
+
+/// 解析任意受支持格式的时间字符串，返回指定时区下的时刻
+///
+/// 纯日期按当天零点处理
+pub fn parse_datetime<Tz: TimeZone>(value: &str, tz: &Tz) -> Option<DateTime<Tz>> {
+    let value = value.trim();
+    if value.is_empty() {
+        return None;
+    }
+
+    if let Ok(dt) = DateTime::parse_from_rfc3339(value) {
+        return Some(dt.with_timezone(tz));
+    }
+
+    for format in NAIVE_DATETIME_FORMATS {
+        if let Ok(naive) = NaiveDateTime::parse_from_str(value, format) {
+            return Some(resolve_local(naive, tz));
+        }
+    }
+
+    parse_date(value).map(|date| start_of_day(date, tz))
+}
+
+/// 时间字符串所在的本地日期
+pub fn local_date<Tz: TimeZone>(value: &str, tz: &Tz) -> Option<NaiveDate> {
+    if let Some(date) = parse_date(value) {
+        return Some(date);
+    }
+    parse_datetime(value, tz).map(|dt| dt.date_naive())
+}
+
+/// 截止时间：纯日期的截止日视为当天结束（即次日零点）
+pub fn due_deadline<Tz: TimeZone>(value: &str, tz: &Tz) -> Option<DateTime<Tz>> {
+    if let Some(date) = parse_date(value) {
+        return date.succ_opt().map(|next| start_of_day(next, tz));
+    }
+    parse_datetime(value, tz)
+}
+
+// 前端 datetime-local 输入框使用的格式
+pub const DUE_FORMAT: &str = "%Y-%m-%dT%H:%M";
+
+/// 解析命令行、链接等处输入的截止时间，返回本地时间
+///
+/// 支持 today / tomorrow / +3d / 星期名（mon、monday，取下一个该日）以及 YYYY-MM-DD[ HH:MM]，
+/// 只给出日期时截止到当天 23:59
+pub fn parse_due_input(value: &str, today: NaiveDate) -> Option<NaiveDateTime> {
+    let value = value.trim();
+    let keyword = value.to_ascii_lowercase();
+    let end_of_day = |date: NaiveDate| date.and_hms_opt(23, 59, 0);
+    // 天数过大时超出日期范围，返回 None 而不是 panic
+    let days_later = |days: i64| TimeDelta::try_days(days).and_then(|delta| today.checked_add_signed(delta));
+
+    match keyword.as_str() {
+        "today" => return end_of_day(today),
+        "tomorrow" => return days_later(1).and_then(end_of_day),
+        _ => {}
+    }
+
+    if let Some(days) = keyword.strip_prefix('+').and_then(|v| v.strip_suffix('d')) {
+        let days: i64 = days.parse().ok()?;
+        return days_later(days).and_then(end_of_day);
+    }
+
+    if let Ok(weekday) = keyword.parse::<chrono::Weekday>() {
+        let ahead = (weekday.num_days_from_monday() as i64 - today.weekday().num_days_from_monday() as i64 + 7) % 7;
+        let ahead = if ahead == 0 { 7 } else { ahead };
+        return days_later(ahead).and_then(end_of_day);
+    }
+
+    for format in NAIVE_DATETIME_FORMATS {
+        if let Ok(naive) = NaiveDateTime::parse_from_str(value, format) {
+            return Some(naive);
+        }
+    }
+
+    parse_date(value).and_then(end_of_day)
+}
+
+// 表格等外部数据中常见的日期格式，月/日/年 与 日/月/年 有歧义时优先按月/日/年理解
+const LOOSE_DATE_FORMATS: &[&str] = &[
+    "%Y-%m-%d",
+    "%Y/%m/%d",
+    "%Y.%m.%d",
+    "%Y年%m月%d日",
+    "%m/%d/%Y",
+    "%d/%m/%Y",
+    "%d.%m.%Y",
+    "%b %d, %Y",
+    "%d %b %Y",
+];
+
+// 偏移量不带冒号的时间，如滴答清单导出的 2025-01-05T16:00:00+0000
+const OFFSET_DATETIME_FORMATS: &[&str] = &[
+    "%Y-%m-%dT%H:%M:%S%.f%z",
+    "%Y-%m-%d %H:%M:%S%.f%z",
+];
+
+const LOOSE_TIME_FORMATS: &[&str] = &[
+    "%H:%M:%S%.f",
+    "%H:%M",
+    "%I:%M:%S %p",
+    "%I:%M %p",
+    "%H时%M分",
+];
+
+// Excel 序列日期的起点，1 表示 1900-01-01（沿用其 1900 年为闰年的错误）
+const EXCEL_EPOCH: (i32, u32, u32) = (1899, 12, 30);
+
+/// Excel 以数字保存的日期，如 45658.5 为 2025-01-01 12:00
+fn parse_excel_serial(value: &str) -> Option<NaiveDateTime> {
+    let serial: f64 = value.parse().ok()?;
+    // 只接受 1954 到 2119 年之间的值，避免把普通数字当成日期
+    if !(20000.0..80000.0).contains(&serial) {
+        return None;
+    }
+    let (year, month, day) = EXCEL_EPOCH;
+    let epoch = NaiveDate::from_ymd_opt(year, month, day)?.and_hms_opt(0, 0, 0)?;
+    let seconds = (serial * 86400.0).round() as i64;
+    Some(epoch + Duration::seconds(seconds))
+}
+
+/// 解析外部数据中的日期时间，返回 `tz` 下的本地日期与时间（未给出时间时为 None）
+///
+/// 支持带时区的时间（RFC 3339 或 +0800 形式的偏移量）、Excel 序列日期、YYYYMMDD，以及 `LOOSE_DATE_FORMATS` 中的日期后接可选的时间
+pub fn parse_loose_datetime<Tz: TimeZone>(value: &str, tz: &Tz) -> Option<(NaiveDate, Option<NaiveTime>)> {
+    let value = value.trim();
+    if value.is_empty() {
+        return None;
+    }
+
+    let with_offset = DateTime::parse_from_rfc3339(value)
+        .ok()
+        .or_else(|| OFFSET_DATETIME_FORMATS.iter().find_map(|f| DateTime::parse_from_str(value, f).ok()));
+    if let Some(dt) = with_offset {
+        let local = dt.with_timezone(tz).naive_local();
+        return Some((local.date(), Some(local.time())));
+    }
+
+    if value.len() == 8 && value.bytes().all(|b| b.is_ascii_digit()) {
+        return NaiveDate::parse_from_str(value, "%Y%m%d").ok().map(|date| (date, None));
+    }
+
+    if let Some(naive) = parse_excel_serial(value) {
+        let time = naive.time();
+        return Some((naive.date(), (time != NaiveTime::MIN).then_some(time)));
+    }
+
+    for format in LOOSE_DATE_FORMATS {
+        let Ok((date, rest)) = NaiveDate::parse_and_remainder(value, format) else {
+            continue;
+        };
+        let rest = rest.trim_start_matches(['T', ' ']).trim();
+        if rest.is_empty() {
+            return Some((date, None));
+        }
+        if let Some(time) = LOOSE_TIME_FORMATS.iter().find_map(|f| NaiveTime::parse_from_str(rest, f).ok()) {
+            return Some((date, Some(time)));
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{FixedOffset, Utc};
+    use chrono_tz::America::{Havana, New_York};
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn utc(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn start_of_day_moves_past_dst_gap_at_midnight() {
+        // 哈瓦那 2024-03-10 零点直接跳到 01:00
+        let start = start_of_day(date(2024, 3, 10), &Havana);
+        assert_eq!(start.naive_local(), date(2024, 3, 10).and_hms_opt(1, 0, 0).unwrap());
+        assert_eq!(start.with_timezone(&Utc), utc("2024-03-10T05:00:00Z"));
+    }
+
+    #[test]
+    fn start_of_day_takes_earlier_repeated_midnight() {
+        // 哈瓦那 2024-11-03 01:00 回拨到 00:00，零点出现两次
+        let start = start_of_day(date(2024, 11, 3), &Havana);
+        assert_eq!(start.with_timezone(&Utc), utc("2024-11-03T04:00:00Z"));
+    }
+
+    #[test]
+    fn due_deadline_for_date_ends_at_next_local_midnight() {
+        // 纽约 2024-03-10 只有 23 小时，2024-11-03 有 25 小时
+        let short_day = due_deadline("2024-03-10", &New_York).unwrap();
+        assert_eq!(short_day.with_timezone(&Utc), utc("2024-03-11T04:00:00Z"));
+        assert_eq!((short_day - start_of_day(date(2024, 3, 10), &New_York)).num_hours(), 23);
+
+        let long_day = due_deadline("2024-11-03", &New_York).unwrap();
+        assert_eq!(long_day.with_timezone(&Utc), utc("2024-11-04T05:00:00Z"));
+        assert_eq!((long_day - start_of_day(date(2024, 11, 3), &New_York)).num_hours(), 25);
+
+        // 零点不存在时截止到次日 01:00
+        let deadline = due_deadline("2024-03-09", &Havana).unwrap();
+        assert_eq!(deadline.with_timezone(&Utc), utc("2024-03-10T05:00:00Z"));
+    }
+
+    #[test]
+    fn due_deadline_with_time_in_dst_gap_and_overlap() {
+        // 02:30 不存在，顺延到跳变之后的 03:00
+        let gap = due_deadline("2024-03-10T02:30", &New_York).unwrap();
+        assert_eq!(gap.with_timezone(&Utc), utc("2024-03-10T07:00:00Z"));
+
+        // 01:30 出现两次，取较早的（夏令时）
+        let overlap = due_deadline("2024-11-03T01:30", &New_York).unwrap();
+        assert_eq!(overlap.with_timezone(&Utc), utc("2024-11-03T05:30:00Z"));
+    }
+
+    #[test]
+    fn local_date_uses_offset_of_timezone() {
+        let east = FixedOffset::east_opt(8 * 3600).unwrap();
+        let west = FixedOffset::west_opt(5 * 3600).unwrap();
+        assert_eq!(local_date("2025-01-01T16:30:00Z", &east), Some(date(2025, 1, 2)));
+        assert_eq!(local_date("2025-01-01T15:59:00Z", &east), Some(date(2025, 1, 1)));
+        assert_eq!(local_date("2025-01-01T03:00:00Z", &west), Some(date(2024, 12, 31)));
+        // 纯日期与时区无关
+        assert_eq!(local_date("2025-01-01", &east), Some(date(2025, 1, 1)));
+    }
+
+    #[test]
+    fn parse_due_input_relative_days() {
+        // 2025-01-01 是星期三
+        let today = date(2025, 1, 1);
+        let end = |d: NaiveDate| d.and_hms_opt(23, 59, 0);
+        assert_eq!(parse_due_input("tomorrow", today), end(date(2025, 1, 2)));
+        assert_eq!(parse_due_input("+3d", today), end(date(2025, 1, 4)));
+        assert_eq!(parse_due_input("wed", today), end(date(2025, 1, 8)));
+        assert_eq!(parse_due_input("Friday", today), end(date(2025, 1, 3)));
+    }
+
+    #[test]
+    fn parse_due_input_rejects_overflowing_days() {
+        let today = date(2025, 1, 1);
+        assert_eq!(parse_due_input("+99999999d", today), None);
+        assert_eq!(parse_due_input(&format!("+{}d", i64::MAX), today), None);
+        assert_eq!(parse_due_input("+-99999999d", today), None);
+        assert_eq!(parse_due_input("tomorrow", NaiveDate::MAX), None);
+        assert_eq!(parse_due_input("mon", NaiveDate::MAX), None);
+    }
+}
@@ -0,0 +1,197 @@
+use std::sync::Mutex;
+use serde_json::Value;
+use tauri::{AppHandle, Emitter, Manager};
+use url::Url;
+use crate::commands::{create_task_from_request, notify_tasks_changed, parse_due_argument};
+use crate::error::{AppError, AppResult};
+use crate::i18n::{fill, Msg};
+use crate::models::{ApiResponse, CreateTaskRequest, Priority};
+use crate::storage::Storage;
+use crate::window::{navigate, show_main_window};
+
+// 注册到系统的 URL scheme，需与 tauri.conf.json 中 plugins.deep-link 保持一致
+pub const SCHEME: &str = "semidone";
+
+// 链接处理失败时通知前端提示用户
+pub const DEEP_LINK_ERROR_EVENT: &str = "deep-link-error";
+
+/// 支持的深链接
+#[derive(Debug)]
+pub enum DeepLink {
+    /// semidone://add?title=...&due=...&priority=high&description=...
+    AddTask(CreateTaskRequest),
+    /// semidone://task/<id>
+    OpenTask(String),
+}
+
+impl DeepLink {
+    pub fn parse(link: &str) -> AppResult<Self> {
+        let invalid = || AppError::invalid_input(fill(Msg::InvalidDeepLink.text(), &[&link]));
+        let url = Url::parse(link.trim()).map_err(|_| invalid())?;
+        if url.scheme() != SCHEME {
+            return Err(invalid());
+        }
+
+        // scheme 由 url 统一转为小写，host 需自行处理
+        match url.host_str().map(str::to_ascii_lowercase).as_deref() {
+            Some("add") => {
+                let mut request = CreateTaskRequest {
+                    title: String::new(),
+                    description: None,
+                    priority: None,
+                    due_date: None,
+                    attachments: None,
+                    estimated_minutes: None,
+                };
+                for (key, value) in url.query_pairs() {
+                    match key.as_ref() {
+                        "title" => request.title = value.trim().to_string(),
+                        "description" | "desc" => {
+                            request.description = Some(value.trim().to_string()).filter(|d| !d.is_empty());
+                        }
+                        "due" => request.due_date = Some(parse_due_argument(&value)?),
+                        "priority" => {
+                            let priority = Priority::parse(&value)
+                                .ok_or_else(|| AppError::invalid_input(Msg::InvalidPriority.text()))?;
+                            request.priority = Some(priority);
+                        }
+                        // 未知参数忽略，兼容以后新增的字段
+                        _ => {}
+                    }
+                }
+
+                if request.title.is_empty() {
+                    return Err(AppError::invalid_input(Msg::TitleRequired.text()));
+                }
+                Ok(DeepLink::AddTask(request))
+            }
+            Some("task") => {
+                // 只接受 task/<id>，允许末尾的斜杠
+                let segments: Vec<&str> = url.path_segments().map(Iterator::collect).unwrap_or_default();
+                match segments.as_slice() {
+                    [id] | [id, ""] if !id.is_empty() => Ok(DeepLink::OpenTask(id.to_string())),
+                    _ => Err(invalid()),
+                }
+            }
+            _ => Err(invalid()),
+        }
+    }
+}
+
+/// 启动参数中的深链接（Windows / Linux 由系统作为命令行参数传入）
+pub fn find_in_args(args: &[String]) -> Option<&str> {
+    args.iter()
+        .map(String::as_str)
+        .find(|arg| {
+            arg.split_once(':')
+                .is_some_and(|(scheme, _)| scheme.eq_ignore_ascii_case(SCHEME))
+        })
+}
+
+/// 处理深链接，返回与前端命令格式一致的结果，失败时同时通知前端
+pub fn handle_link(app: &AppHandle, link: &str) -> ApiResponse<Value> {
+    log::info!("🔗 处理深链接: {}", link);
+
+    match open_link(app, link) {
+        Ok(data) => ApiResponse::success(data),
+        Err(e) => {
+            let response = ApiResponse::error(Msg::DeepLinkFailed, e);
+            show_main_window(app);
+            if let Err(e) = app.emit(DEEP_LINK_ERROR_EVENT, &response.error) {
+                log::warn!("Failed to emit {}: {}", DEEP_LINK_ERROR_EVENT, e);
+            }
+            response
+        }
+    }
+}
+
+fn open_link(app: &AppHandle, link: &str) -> AppResult<Value> {
+    let state = app.state::<Mutex<Storage>>();
+
+    match DeepLink::parse(link)? {
+        DeepLink::AddTask(request) => {
+            let task = create_task_from_request(&*state.lock()?, request)?;
+            notify_tasks_changed(app);
+            show_main_window(app);
+            Ok(serde_json::to_value(task)?)
+        }
+        DeepLink::OpenTask(id) => {
+            if !state.lock()?.load_tasks()?.iter().any(|task| task.id == id) {
+                return Err(AppError::not_found(Msg::EntityTask, id));
+            }
+            navigate(app, format!("/task/{}", id));
+            Ok(serde_json::json!({ "id": id }))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn add(link: &str) -> CreateTaskRequest {
+        match DeepLink::parse(link).unwrap() {
+            DeepLink::AddTask(request) => request,
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    fn open(link: &str) -> String {
+        match DeepLink::parse(link).unwrap() {
+            DeepLink::OpenTask(id) => id,
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    fn rejected(link: &str) -> String {
+        match DeepLink::parse(link) {
+            Err(AppError::InvalidInput(message)) => message,
+            other => panic!("expected invalid input, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn scheme_and_host_are_case_insensitive() {
+        assert_eq!(add("SemiDone://ADD?title=Buy").title, "Buy");
+        assert_eq!(open("SEMIDONE://Task/abc"), "abc");
+        assert_eq!(rejected("other://add?title=Buy"), fill(Msg::InvalidDeepLink.text(), &[&"other://add?title=Buy"]));
+    }
+
+    #[test]
+    fn add_requires_a_title() {
+        assert_eq!(rejected("semidone://add"), Msg::TitleRequired.text());
+        assert_eq!(rejected("semidone://add?title=%20%20&priority=high"), Msg::TitleRequired.text());
+    }
+
+    #[test]
+    fn add_decodes_percent_encoded_values() {
+        let request = add("semidone://add?title=%E4%B9%B0%E8%8F%9C+now&desc=a%26b%3Dc&priority=HIGH&unknown=1");
+        assert_eq!(request.title, "买菜 now");
+        assert_eq!(request.description.as_deref(), Some("a&b=c"));
+        assert_eq!(request.priority, Some(Priority::High));
+        assert_eq!(add("semidone://add?title=x&description=").description, None);
+    }
+
+    #[test]
+    fn add_rejects_invalid_priority_and_due() {
+        assert_eq!(rejected("semidone://add?title=x&priority=urgent"), Msg::InvalidPriority.text());
+        assert_eq!(rejected("semidone://add?title=x&due=someday"), fill(Msg::InvalidDue.text(), &[&"someday"]));
+        assert_eq!(rejected("semidone://add?title=x&due=%2B99999999d"), fill(Msg::InvalidDue.text(), &[&"+99999999d"]));
+        assert!(add("semidone://add?title=x&due=2025-01-02").due_date.is_some_and(|due| due == "2025-01-02T23:59"));
+    }
+
+    #[test]
+    fn task_requires_exactly_one_id() {
+        assert_eq!(open("semidone://task/abc/"), "abc");
+        for link in ["semidone://task", "semidone://task/", "semidone://task//abc", "semidone://task/abc/def"] {
+            rejected(link);
+        }
+    }
+
+    #[test]
+    fn unknown_host_or_malformed_link_is_rejected() {
+        for link in ["semidone://delete?id=abc", "semidone:add?title=x", "semidone://", "not a url"] {
+            rejected(link);
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use std::fmt;
+use std::io;
+use std::sync::PoisonError;
+use serde::{Deserialize, Serialize, Serializer};
+use serde::ser::SerializeStruct;
+use ts_rs::TS;
+use crate::i18n::{fill, Msg};
+
+// Windows 下磁盘已满的系统错误码（ERROR_HANDLE_DISK_FULL / ERROR_DISK_FULL）
+#[cfg(windows)]
+const DISK_FULL_OS_CODES: &[i32] = &[39, 112];
+// Unix 下的 ENOSPC
+#[cfg(not(windows))]
+const DISK_FULL_OS_CODES: &[i32] = &[28];
+
+/// 前端可识别的错误码，随错误信息一起返回
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum ErrorCode {
+    NotFound,
+    InvalidInput,
+    CorruptData,
+    DiskFull,
+    PermissionDenied,
+    Io,
+    Internal,
+    Unauthorized,
+}
+
+/// 后端统一错误类型
+#[derive(Debug)]
+pub enum AppError {
+    /// 指定的对象不存在，如待办 id
+    NotFound { entity: Msg, id: String },
+    /// 参数不合法
+    InvalidInput(String),
+    /// 数据文件无法解析
+    CorruptData { file: String, source: serde_json::Error },
+    /// 文件读写失败
+    Io(io::Error),
+    /// 序列化失败等内部错误
+    Internal(String),
+    /// 本地接口请求缺少或携带了错误的访问令牌
+    Unauthorized,
+}
+
+pub type AppResult<T> = Result<T, AppError>;
+
+impl AppError {
+    pub fn not_found(entity: Msg, id: impl Into<String>) -> Self {
+        AppError::NotFound { entity, id: id.into() }
+    }
+
+    pub fn invalid_input(message: impl Into<String>) -> Self {
+        AppError::InvalidInput(message.into())
+    }
+
+    pub fn corrupt(file: impl Into<String>, source: serde_json::Error) -> Self {
+        AppError::CorruptData { file: file.into(), source }
+    }
+
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            AppError::NotFound { .. } => ErrorCode::NotFound,
+            AppError::InvalidInput(_) => ErrorCode::InvalidInput,
+            AppError::CorruptData { .. } => ErrorCode::CorruptData,
+            AppError::Io(e) if e.kind() == io::ErrorKind::PermissionDenied => ErrorCode::PermissionDenied,
+            AppError::Io(e) if e.raw_os_error().is_some_and(|c| DISK_FULL_OS_CODES.contains(&c)) => ErrorCode::DiskFull,
+            AppError::Io(_) => ErrorCode::Io,
+            AppError::Internal(_) => ErrorCode::Internal,
+            AppError::Unauthorized => ErrorCode::Unauthorized,
+        }
+    }
+}
+
+// 错误信息按当前语言输出
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            AppError::NotFound { entity, id } => fill(Msg::ErrNotFound.text(), &[&entity.text(), id]),
+            AppError::InvalidInput(message) => fill(Msg::ErrInvalidInput.text(), &[message]),
+            AppError::CorruptData { file, source } => fill(Msg::ErrCorruptData.text(), &[file, source]),
+            AppError::Io(e) => match self.code() {
+                ErrorCode::DiskFull => fill(Msg::ErrDiskFull.text(), &[e]),
+                ErrorCode::PermissionDenied => fill(Msg::ErrPermissionDenied.text(), &[e]),
+                _ => fill(Msg::ErrIo.text(), &[e]),
+            },
+            AppError::Internal(message) => fill(Msg::ErrInternal.text(), &[message]),
+            AppError::Unauthorized => Msg::ErrUnauthorized.text().to_string(),
+        };
+        f.write_str(&message)
+    }
+}
+
+impl std::error::Error for AppError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            AppError::CorruptData { source, .. } => Some(source),
+            AppError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for AppError {
+    fn from(e: io::Error) -> Self {
+        AppError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for AppError {
+    fn from(e: serde_json::Error) -> Self {
+        AppError::Internal(e.to_string())
+    }
+}
+
+impl<T> From<PoisonError<T>> for AppError {
+    fn from(e: PoisonError<T>) -> Self {
+        AppError::Internal(e.to_string())
+    }
+}
+
+// 命令直接返回 Err 时，前端收到 { code, message }
+impl Serialize for AppError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("AppError", 2)?;
+        state.serialize_field("code", &self.code())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.end()
+    }
+}
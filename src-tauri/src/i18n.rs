@@ -0,0 +1,593 @@
+use std::sync::atomic::{AtomicU8, Ordering};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// 界面语言
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub enum Locale {
+    #[default]
+    #[serde(rename = "zh-CN")]
+    ZhCn,
+    #[serde(rename = "en-US")]
+    EnUs,
+}
+
+// 当前语言，启动时从设置读取，设置变更时更新
+static CURRENT_LOCALE: AtomicU8 = AtomicU8::new(0);
+
+impl Locale {
+    fn to_u8(self) -> u8 {
+        match self {
+            Locale::ZhCn => 0,
+            Locale::EnUs => 1,
+        }
+    }
+
+    fn from_u8(value: u8) -> Self {
+        match value {
+            1 => Locale::EnUs,
+            _ => Locale::ZhCn,
+        }
+    }
+}
+
+pub fn current_locale() -> Locale {
+    Locale::from_u8(CURRENT_LOCALE.load(Ordering::Relaxed))
+}
+
+/// 切换当前语言，返回语言是否发生了变化
+pub fn set_locale(locale: Locale) -> bool {
+    CURRENT_LOCALE.swap(locale.to_u8(), Ordering::Relaxed) != locale.to_u8()
+}
+
+/// 后端所有面向用户的文案
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Msg {
+    // 托盘
+    TrayShow,
+    TrayQuit,
+    TrayTooltip,
+    TrayOverdue,
+    TrayDueToday,
+    TrayNoTasks,
+    TrayMoreTasks,
+    TrayNewTask,
+    TrayTogglePomodoro,
+    TrayPin,
+    TrayToggleCollapse,
+    TrayTooltipOverdue,
+    TrayTooltipDueToday,
+    TrayTooltipPomodoro,
+
+    // 番茄钟通知
+    PomodoroTitle,
+    PomodoroWorkDone,
+    PomodoroLongBreak,
+    PomodoroBreakDone,
+
+    // 操作失败前缀
+    LoadTasksFailed,
+    CreateTaskFailed,
+    UpdateTaskFailed,
+    DeleteTaskFailed,
+    StatsFailed,
+    TrendFailed,
+    ProductivityStatsFailed,
+    LoadSettingsFailed,
+    SaveSettingsFailed,
+    ExportFailed,
+    ParseImportFailed,
+    ImportSettingsFailed,
+    ImportFailed,
+    ClearFailed,
+    DecodeFileFailed,
+    WriteTempFileFailed,
+    OpenFileFailed,
+    ParseCommandFailed,
+    DeepLinkFailed,
+    ApiRequestFailed,
+    StartApiFailed,
+    ResetApiTokenFailed,
+    RegisterShortcutFailed,
+    AutostartFailed,
+    UsageStatsFailed,
+    ImportUsageFailed,
+    PomodoroFailed,
+    TimeEntryFailed,
+    ExportReportFailed,
+    PreviewReportFailed,
+    LoadReportTemplatesFailed,
+    ExportCsvFailed,
+    ImportCsvFailed,
+    ImportFromAppFailed,
+    ExportTaskListFailed,
+    LoadSyncStatusFailed,
+    ConfigureSyncFailed,
+    SyncFailed,
+    ResolveConflictFailed,
+
+    // 错误详情，{0} 为附加信息
+    ErrNotFound,
+    ErrInvalidInput,
+    ErrCorruptData,
+    ErrDiskFull,
+    ErrPermissionDenied,
+    ErrIo,
+    ErrInternal,
+    ErrUnauthorized,
+    HomeDirMissing,
+
+    // 对象名称
+    EntityTask,
+    EntityRoute,
+    EntityTimeEntry,
+    EntityReportTemplate,
+    EntitySyncConflict,
+
+    // 参数校验
+    TitleRequired,
+    InvalidStartDate,
+    InvalidEndDate,
+    StartAfterEnd,
+    RangeTooLong,
+    InvalidDue,
+    InvalidDeepLink,
+    BodyTooLarge,
+    InvalidPriority,
+    AmbiguousTaskId,
+    InvalidShortcut,
+    ShortcutUnavailable,
+    InvalidPomodoroSettings,
+    TimeEntryEndBeforeStart,
+    TimeEntryInFuture,
+    InvalidReportTemplate,
+    CsvEmpty,
+    CsvMappingMismatch,
+    CsvTitleNotMapped,
+    CsvDuplicateField,
+    ImportUnrecognizedFormat,
+    SyncNotEnabled,
+    SyncFolderMissing,
+
+    // 命令行输出
+    CliTaskAdded,
+    CliTaskCompleted,
+    CliTaskUpdated,
+    CliStats,
+    CliNoTasks,
+    CliSynced,
+
+    // 报告
+    ReportWeekly,
+    ReportMonthly,
+    ReportFileName,
+    ReportSaveTitle,
+    ReportColFocus,
+    ReportColTracked,
+    ReportColStatus,
+    ReportColTitle,
+    ReportColDescription,
+    ReportColPriority,
+    ReportColDue,
+    ReportColCompletedAt,
+    ReportColEstimate,
+    ReportStatusCompleted,
+    ReportStatusPending,
+    ReportStatusOverdue,
+    ReportPriorityHigh,
+    ReportPriorityMedium,
+    ReportPriorityLow,
+    ReportDue,
+    ReportCompletedAt,
+    ReportOverdueDays,
+    ReportEffort,
+    ReportEstimate,
+    ReportDurationMinutes,
+    ReportDurationHours,
+
+    // 导入时跳过某一行的原因
+    ImportMalformedRow,
+    ImportMissingTitle,
+    ImportInvalidDate,
+    ImportInvalidPriority,
+    ImportInvalidCompleted,
+    ImportInvalidNumber,
+    ImportListNote,
+    ImportOriginalDue,
+
+    // 待办 CSV
+    CsvFileName,
+    CsvSaveTitle,
+    CsvYes,
+    CsvNo,
+    FieldTitle,
+    FieldDescription,
+    FieldCompleted,
+    FieldPriority,
+    FieldDueDate,
+    FieldCreatedAt,
+    FieldCompletedAt,
+    FieldEstimatedMinutes,
+    FieldFocusMinutes,
+
+    // 待办清单（Markdown / todo.txt）
+    TaskListTitle,
+    TaskListFileName,
+    TaskListSaveTitle,
+    TaskListPriorityGroup,
+    TaskListOverdue,
+    TaskListToday,
+    TaskListTomorrow,
+    TaskListNoDue,
+
+    // 文件夹同步
+    SyncFolderPickTitle,
+}
+
+impl Msg {
+    /// 当前语言下的文案
+    pub fn text(self) -> &'static str {
+        self.text_in(current_locale())
+    }
+
+    pub fn text_in(self, locale: Locale) -> &'static str {
+        match locale {
+            Locale::ZhCn => zh_cn(self),
+            Locale::EnUs => en_us(self),
+        }
+    }
+}
+
+/// 用参数替换文案中的 {0}、{1} 占位符
+pub fn fill(template: &str, args: &[&dyn std::fmt::Display]) -> String {
+    let mut result = template.to_string();
+    for (i, arg) in args.iter().enumerate() {
+        result = result.replace(&format!("{{{}}}", i), &arg.to_string());
+    }
+    result
+}
+
+fn zh_cn(msg: Msg) -> &'static str {
+    match msg {
+        Msg::TrayShow => "显示窗口",
+        Msg::TrayQuit => "退出",
+        Msg::TrayTooltip => "事半·SemiDone",
+        Msg::TrayOverdue => "已过期 ({0})",
+        Msg::TrayDueToday => "今日到期 ({0})",
+        Msg::TrayNoTasks => "今天没有待办",
+        Msg::TrayMoreTasks => "还有 {0} 项…",
+        Msg::TrayNewTask => "新建待办…",
+        Msg::TrayTogglePomodoro => "开始/暂停番茄钟",
+        Msg::TrayPin => "置顶窗口",
+        Msg::TrayToggleCollapse => "折叠/展开窗口",
+        Msg::TrayTooltipOverdue => "{0} 项已过期",
+        Msg::TrayTooltipDueToday => "{0} 项今日到期",
+        Msg::TrayTooltipPomodoro => "番茄钟进行中",
+
+        Msg::PomodoroTitle => "番茄钟",
+        Msg::PomodoroWorkDone => "专注完成！休息 {0} 分钟",
+        Msg::PomodoroLongBreak => "已完成 {0} 个番茄钟！长休息 {1} 分钟",
+        Msg::PomodoroBreakDone => "休息结束！开始专注 {0} 分钟",
+
+        Msg::LoadTasksFailed => "加载待办失败",
+        Msg::CreateTaskFailed => "创建待办失败",
+        Msg::UpdateTaskFailed => "更新待办失败",
+        Msg::DeleteTaskFailed => "删除待办失败",
+        Msg::StatsFailed => "获取统计信息失败",
+        Msg::TrendFailed => "获取趋势统计失败",
+        Msg::ProductivityStatsFailed => "获取完成情况分析失败",
+        Msg::LoadSettingsFailed => "加载设置失败",
+        Msg::SaveSettingsFailed => "保存设置失败",
+        Msg::ExportFailed => "导出数据失败",
+        Msg::ParseImportFailed => "解析数据失败",
+        Msg::ImportSettingsFailed => "导入设置失败",
+        Msg::ImportFailed => "导入数据失败",
+        Msg::ClearFailed => "清空数据失败",
+        Msg::DecodeFileFailed => "解码文件数据失败",
+        Msg::WriteTempFileFailed => "写入临时文件失败",
+        Msg::OpenFileFailed => "打开文件失败",
+        Msg::ParseCommandFailed => "解析命令失败",
+        Msg::DeepLinkFailed => "打开链接失败",
+        Msg::ApiRequestFailed => "请求失败",
+        Msg::StartApiFailed => "启动本地接口失败",
+        Msg::ResetApiTokenFailed => "重置访问令牌失败",
+        Msg::RegisterShortcutFailed => "注册快捷键失败",
+        Msg::AutostartFailed => "设置开机启动失败",
+        Msg::UsageStatsFailed => "获取使用时长统计失败",
+        Msg::ImportUsageFailed => "导入使用记录失败",
+        Msg::PomodoroFailed => "番茄钟操作失败",
+        Msg::TimeEntryFailed => "计时操作失败",
+        Msg::ExportReportFailed => "导出报告失败",
+        Msg::PreviewReportFailed => "生成报告预览失败",
+        Msg::LoadReportTemplatesFailed => "读取报告模板失败",
+        Msg::ExportCsvFailed => "导出 CSV 失败",
+        Msg::ImportCsvFailed => "导入 CSV 失败",
+        Msg::ImportFromAppFailed => "导入失败",
+        Msg::ExportTaskListFailed => "导出待办清单失败",
+        Msg::LoadSyncStatusFailed => "读取同步状态失败",
+        Msg::ConfigureSyncFailed => "设置同步失败",
+        Msg::SyncFailed => "同步失败",
+        Msg::ResolveConflictFailed => "处理冲突失败",
+
+        Msg::ErrNotFound => "{0}不存在: {1}",
+        Msg::ErrInvalidInput => "参数无效: {0}",
+        Msg::ErrCorruptData => "数据文件已损坏 ({0}): {1}",
+        Msg::ErrDiskFull => "磁盘空间不足: {0}",
+        Msg::ErrPermissionDenied => "没有文件访问权限: {0}",
+        Msg::ErrIo => "文件读写失败: {0}",
+        Msg::ErrInternal => "内部错误: {0}",
+        Msg::ErrUnauthorized => "访问令牌缺失或无效",
+        Msg::HomeDirMissing => "无法获取用户主目录",
+
+        Msg::EntityTask => "待办",
+        Msg::EntityRoute => "接口",
+        Msg::EntityTimeEntry => "计时记录",
+        Msg::EntityReportTemplate => "报告模板",
+        Msg::EntitySyncConflict => "同步冲突",
+
+        Msg::TitleRequired => "标题不能为空",
+        Msg::InvalidStartDate => "开始日期格式无效，应为 YYYY-MM-DD",
+        Msg::InvalidEndDate => "结束日期格式无效，应为 YYYY-MM-DD",
+        Msg::StartAfterEnd => "开始日期不能晚于结束日期",
+        Msg::RangeTooLong => "统计区间不能超过 {0} 天",
+        Msg::InvalidDue => "无法识别的截止时间: {0}",
+        Msg::InvalidDeepLink => "无法识别的链接: {0}",
+        Msg::BodyTooLarge => "请求体不能超过 {0} 字节",
+        Msg::InvalidPriority => "优先级应为 high、medium 或 low",
+        Msg::AmbiguousTaskId => "id 前缀 {0} 匹配到多个待办，请输入更长的前缀",
+        Msg::InvalidShortcut => "无法识别的快捷键: {0}",
+        Msg::ShortcutUnavailable => "快捷键 {0} 无法注册，可能已被其他程序占用: {1}",
+        Msg::InvalidPomodoroSettings => "番茄钟时长应为 1 到 {0} 分钟，长休息间隔应为 1 到 {1} 次",
+        Msg::TimeEntryEndBeforeStart => "结束时间应晚于开始时间",
+        Msg::TimeEntryInFuture => "计时记录不能晚于当前时间",
+        Msg::InvalidReportTemplate => "报告模板有误: {0}",
+        Msg::CsvEmpty => "CSV 文件中没有数据",
+        Msg::CsvMappingMismatch => "列对应关系与表头的列数不一致",
+        Msg::CsvTitleNotMapped => "请指定标题所在的列",
+        Msg::CsvDuplicateField => "「{0}」对应了多列",
+        Msg::ImportUnrecognizedFormat => "无法识别文件内容，请确认选择的应用与文件一致",
+        Msg::SyncNotEnabled => "尚未设置同步文件夹",
+        Msg::SyncFolderMissing => "同步文件夹不存在或无法访问: {0}",
+
+        Msg::CliTaskAdded => "已添加待办 {0}: {1}",
+        Msg::CliTaskCompleted => "已完成待办 {0}: {1}",
+        Msg::CliTaskUpdated => "已更新待办 {0}: {1}",
+        Msg::CliStats => "共 {0} 项，已完成 {1}，未完成 {2}，已过期 {3}，今日到期 {4}",
+        Msg::CliNoTasks => "没有符合条件的待办",
+        Msg::CliSynced => "已与 {0} 台设备同步，{1} 个冲突待处理",
+
+        Msg::ReportWeekly => "周报",
+        Msg::ReportMonthly => "月报",
+        Msg::ReportFileName => "事半SemiDone_{0}_{1}_{2}",
+        Msg::ReportSaveTitle => "保存报告",
+        Msg::ReportColFocus => "专注（分钟）",
+        Msg::ReportColTracked => "计时（分钟）",
+        Msg::ReportColStatus => "状态",
+        Msg::ReportColTitle => "标题",
+        Msg::ReportColDescription => "描述",
+        Msg::ReportColPriority => "优先级",
+        Msg::ReportColDue => "截止时间",
+        Msg::ReportColCompletedAt => "完成时间",
+        Msg::ReportColEstimate => "预计（分钟）",
+        Msg::ReportStatusCompleted => "已完成",
+        Msg::ReportStatusPending => "未完成",
+        Msg::ReportStatusOverdue => "已逾期",
+        Msg::ReportPriorityHigh => "高",
+        Msg::ReportPriorityMedium => "中",
+        Msg::ReportPriorityLow => "低",
+        Msg::ReportDue => "截止 {0}",
+        Msg::ReportCompletedAt => "完成于 {0}",
+        Msg::ReportOverdueDays => "逾期 {0} 天",
+        Msg::ReportEffort => "专注 {0} 分钟 · 计时 {1} 分钟",
+        Msg::ReportEstimate => "预计 {0} 分钟",
+        Msg::ReportDurationMinutes => "{0} 分钟",
+        Msg::ReportDurationHours => "{0} 小时 {1} 分钟",
+
+        Msg::ImportMalformedRow => "格式有误: {0}",
+        Msg::ImportMissingTitle => "标题为空",
+        Msg::ImportInvalidDate => "无法识别的日期: {0}",
+        Msg::ImportInvalidPriority => "无法识别的优先级: {0}",
+        Msg::ImportInvalidCompleted => "无法识别的完成状态: {0}",
+        Msg::ImportInvalidNumber => "无法识别的数字: {0}",
+        Msg::ImportListNote => "清单: {0}",
+        Msg::ImportOriginalDue => "原截止时间: {0}",
+
+        Msg::CsvFileName => "事半SemiDone_待办_{0}",
+        Msg::CsvSaveTitle => "导出待办",
+        Msg::CsvYes => "是",
+        Msg::CsvNo => "否",
+        Msg::FieldTitle => "标题",
+        Msg::FieldDescription => "描述",
+        Msg::FieldCompleted => "已完成",
+        Msg::FieldPriority => "优先级",
+        Msg::FieldDueDate => "截止时间",
+        Msg::FieldCreatedAt => "创建时间",
+        Msg::FieldCompletedAt => "完成时间",
+        Msg::FieldEstimatedMinutes => "预计耗时（分钟）",
+        Msg::FieldFocusMinutes => "专注时长（分钟）",
+
+        Msg::TaskListTitle => "待办清单",
+        Msg::TaskListFileName => "事半SemiDone_待办清单_{0}",
+        Msg::TaskListSaveTitle => "导出待办清单",
+        Msg::TaskListPriorityGroup => "{0}优先级",
+        Msg::TaskListOverdue => "已过期",
+        Msg::TaskListToday => "{0} · 今天",
+        Msg::TaskListTomorrow => "{0} · 明天",
+        Msg::TaskListNoDue => "无截止时间",
+
+        Msg::SyncFolderPickTitle => "选择同步文件夹",
+    }
+}
+
+fn en_us(msg: Msg) -> &'static str {
+    match msg {
+        Msg::TrayShow => "Show Window",
+        Msg::TrayQuit => "Quit",
+        Msg::TrayTooltip => "SemiDone",
+        Msg::TrayOverdue => "Overdue ({0})",
+        Msg::TrayDueToday => "Due today ({0})",
+        Msg::TrayNoTasks => "Nothing due today",
+        Msg::TrayMoreTasks => "{0} more…",
+        Msg::TrayNewTask => "New task…",
+        Msg::TrayTogglePomodoro => "Start/pause pomodoro",
+        Msg::TrayPin => "Pin window",
+        Msg::TrayToggleCollapse => "Collapse/expand window",
+        Msg::TrayTooltipOverdue => "{0} overdue",
+        Msg::TrayTooltipDueToday => "{0} due today",
+        Msg::TrayTooltipPomodoro => "Pomodoro running",
+
+        Msg::PomodoroTitle => "Pomodoro",
+        Msg::PomodoroWorkDone => "Focus session done! Take a {0} minute break",
+        Msg::PomodoroLongBreak => "{0} pomodoros done! Take a {1} minute long break",
+        Msg::PomodoroBreakDone => "Break is over! Focus for {0} minutes",
+
+        Msg::LoadTasksFailed => "Failed to load tasks",
+        Msg::CreateTaskFailed => "Failed to create task",
+        Msg::UpdateTaskFailed => "Failed to update task",
+        Msg::DeleteTaskFailed => "Failed to delete task",
+        Msg::StatsFailed => "Failed to load statistics",
+        Msg::TrendFailed => "Failed to load trend statistics",
+        Msg::ProductivityStatsFailed => "Failed to load productivity statistics",
+        Msg::LoadSettingsFailed => "Failed to load settings",
+        Msg::SaveSettingsFailed => "Failed to save settings",
+        Msg::ExportFailed => "Failed to export data",
+        Msg::ParseImportFailed => "Failed to parse data",
+        Msg::ImportSettingsFailed => "Failed to import settings",
+        Msg::ImportFailed => "Failed to import data",
+        Msg::ClearFailed => "Failed to clear data",
+        Msg::DecodeFileFailed => "Failed to decode file data",
+        Msg::WriteTempFileFailed => "Failed to write temporary file",
+        Msg::OpenFileFailed => "Failed to open file",
+        Msg::ParseCommandFailed => "Failed to parse command",
+        Msg::DeepLinkFailed => "Failed to open link",
+        Msg::ApiRequestFailed => "Request failed",
+        Msg::StartApiFailed => "Failed to start local API",
+        Msg::ResetApiTokenFailed => "Failed to reset access token",
+        Msg::RegisterShortcutFailed => "Failed to register shortcut",
+        Msg::AutostartFailed => "Failed to update launch at login",
+        Msg::UsageStatsFailed => "Failed to load usage statistics",
+        Msg::ImportUsageFailed => "Failed to import usage records",
+        Msg::PomodoroFailed => "Pomodoro operation failed",
+        Msg::TimeEntryFailed => "Time tracking operation failed",
+        Msg::ExportReportFailed => "Failed to export report",
+        Msg::PreviewReportFailed => "Failed to preview report",
+        Msg::LoadReportTemplatesFailed => "Failed to load report templates",
+        Msg::ExportCsvFailed => "Failed to export CSV",
+        Msg::ImportCsvFailed => "Failed to import CSV",
+        Msg::ImportFromAppFailed => "Import failed",
+        Msg::ExportTaskListFailed => "Failed to export task list",
+        Msg::LoadSyncStatusFailed => "Failed to load sync status",
+        Msg::ConfigureSyncFailed => "Failed to configure sync",
+        Msg::SyncFailed => "Sync failed",
+        Msg::ResolveConflictFailed => "Failed to resolve conflict",
+
+        Msg::ErrNotFound => "{0} not found: {1}",
+        Msg::ErrInvalidInput => "Invalid input: {0}",
+        Msg::ErrCorruptData => "Data file is corrupted ({0}): {1}",
+        Msg::ErrDiskFull => "Disk is full: {0}",
+        Msg::ErrPermissionDenied => "Permission denied: {0}",
+        Msg::ErrIo => "File I/O error: {0}",
+        Msg::ErrInternal => "Internal error: {0}",
+        Msg::ErrUnauthorized => "Missing or invalid access token",
+        Msg::HomeDirMissing => "Cannot determine the home directory",
+
+        Msg::EntityTask => "Task",
+        Msg::EntityRoute => "Endpoint",
+        Msg::EntityTimeEntry => "Time entry",
+        Msg::EntityReportTemplate => "Report template",
+        Msg::EntitySyncConflict => "Sync conflict",
+
+        Msg::TitleRequired => "Title must not be empty",
+        Msg::InvalidStartDate => "Invalid start date, expected YYYY-MM-DD",
+        Msg::InvalidEndDate => "Invalid end date, expected YYYY-MM-DD",
+        Msg::StartAfterEnd => "Start date must not be after end date",
+        Msg::RangeTooLong => "Range must not exceed {0} days",
+        Msg::InvalidDue => "Unrecognized due date: {0}",
+        Msg::InvalidDeepLink => "Unrecognized link: {0}",
+        Msg::BodyTooLarge => "Request body must not exceed {0} bytes",
+        Msg::InvalidPriority => "Priority must be high, medium or low",
+        Msg::AmbiguousTaskId => "Id prefix {0} matches more than one task, use a longer prefix",
+        Msg::InvalidShortcut => "Unrecognized shortcut: {0}",
+        Msg::ShortcutUnavailable => "Shortcut {0} could not be registered, it may be in use by another application: {1}",
+        Msg::InvalidPomodoroSettings => "Pomodoro durations must be 1 to {0} minutes and the long break interval 1 to {1} cycles",
+        Msg::TimeEntryEndBeforeStart => "End time must be after start time",
+        Msg::TimeEntryInFuture => "Time entries cannot be in the future",
+        Msg::InvalidReportTemplate => "Invalid report template: {0}",
+        Msg::CsvEmpty => "The CSV file contains no data",
+        Msg::CsvMappingMismatch => "The column mapping does not match the header",
+        Msg::CsvTitleNotMapped => "Choose the column that contains the title",
+        Msg::CsvDuplicateField => "\"{0}\" is mapped to more than one column",
+        Msg::ImportUnrecognizedFormat => "The file was not recognized, check that the selected app matches the file",
+        Msg::SyncNotEnabled => "No sync folder has been set",
+        Msg::SyncFolderMissing => "Sync folder is missing or inaccessible: {0}",
+
+        Msg::CliTaskAdded => "Added task {0}: {1}",
+        Msg::CliTaskCompleted => "Completed task {0}: {1}",
+        Msg::CliTaskUpdated => "Updated task {0}: {1}",
+        Msg::CliStats => "{0} total, {1} completed, {2} pending, {3} overdue, {4} due today",
+        Msg::CliNoTasks => "No matching tasks",
+        Msg::CliSynced => "Synced with {0} device(s), {1} conflict(s) to review",
+
+        Msg::ReportWeekly => "Weekly Report",
+        Msg::ReportMonthly => "Monthly Report",
+        Msg::ReportFileName => "SemiDone_{0}_{1}_{2}",
+        Msg::ReportSaveTitle => "Save report",
+        Msg::ReportColFocus => "Focus (min)",
+        Msg::ReportColTracked => "Tracked (min)",
+        Msg::ReportColStatus => "Status",
+        Msg::ReportColTitle => "Title",
+        Msg::ReportColDescription => "Description",
+        Msg::ReportColPriority => "Priority",
+        Msg::ReportColDue => "Due",
+        Msg::ReportColCompletedAt => "Completed at",
+        Msg::ReportColEstimate => "Estimate (min)",
+        Msg::ReportStatusCompleted => "Completed",
+        Msg::ReportStatusPending => "Pending",
+        Msg::ReportStatusOverdue => "Overdue",
+        Msg::ReportPriorityHigh => "High",
+        Msg::ReportPriorityMedium => "Medium",
+        Msg::ReportPriorityLow => "Low",
+        Msg::ReportDue => "due {0}",
+        Msg::ReportCompletedAt => "completed {0}",
+        Msg::ReportOverdueDays => "{0} days overdue",
+        Msg::ReportEffort => "focus {0} min · tracked {1} min",
+        Msg::ReportEstimate => "estimate {0} min",
+        Msg::ReportDurationMinutes => "{0} min",
+        Msg::ReportDurationHours => "{0} h {1} min",
+
+        Msg::ImportMalformedRow => "Malformed row: {0}",
+        Msg::ImportMissingTitle => "Title is empty",
+        Msg::ImportInvalidDate => "Unrecognized date: {0}",
+        Msg::ImportInvalidPriority => "Unrecognized priority: {0}",
+        Msg::ImportInvalidCompleted => "Unrecognized completion state: {0}",
+        Msg::ImportInvalidNumber => "Unrecognized number: {0}",
+        Msg::ImportListNote => "List: {0}",
+        Msg::ImportOriginalDue => "Original due date: {0}",
+
+        Msg::CsvFileName => "SemiDone_Tasks_{0}",
+        Msg::CsvSaveTitle => "Export tasks",
+        Msg::CsvYes => "Yes",
+        Msg::CsvNo => "No",
+        Msg::FieldTitle => "Title",
+        Msg::FieldDescription => "Description",
+        Msg::FieldCompleted => "Done",
+        Msg::FieldPriority => "Priority",
+        Msg::FieldDueDate => "Due",
+        Msg::FieldCreatedAt => "Created",
+        Msg::FieldCompletedAt => "Completed at",
+        Msg::FieldEstimatedMinutes => "Estimate (min)",
+        Msg::FieldFocusMinutes => "Focus (min)",
+
+        Msg::TaskListTitle => "Tasks",
+        Msg::TaskListFileName => "SemiDone_TaskList_{0}",
+        Msg::TaskListSaveTitle => "Export task list",
+        Msg::TaskListPriorityGroup => "{0} priority",
+        Msg::TaskListOverdue => "Overdue",
+        Msg::TaskListToday => "{0} · today",
+        Msg::TaskListTomorrow => "{0} · tomorrow",
+        Msg::TaskListNoDue => "No due date",
+
+        Msg::SyncFolderPickTitle => "Choose sync folder",
+    }
+}
@@ -0,0 +1,680 @@
+use chrono::{TimeZone, Utc};
+use csv::{ReaderBuilder, StringRecord};
+use serde::Deserialize;
+use serde_json::Value;
+use crate::datetime::{parse_date, parse_loose_datetime};
+use crate::error::{AppError, AppResult};
+use crate::i18n::{fill, Msg};
+use crate::models::{ImportRowError, ImportSource, Priority, Task, TaskImportResult};
+use crate::task_csv::{due_from_parts, timestamp_from_parts};
+
+const UTF8_BOM: &str = "\u{feff}";
+
+/// 各格式解析出的待办，统一在 `into_task` 中转换
+///
+/// 应用没有清单与子任务，清单名记在描述末尾，子任务作为普通待办导入
+#[derive(Default)]
+struct Draft {
+    title: String,
+    notes: Vec<String>,
+    list: Option<String>,
+    priority: Option<Priority>,
+    due: Option<String>,
+    // 全天任务只保留截止日期
+    all_day: bool,
+    completed: bool,
+    completed_at: Option<String>,
+    created_at: Option<String>,
+}
+
+impl Draft {
+    fn into_task<Tz: TimeZone>(self, tz: &Tz) -> Result<Task, String> {
+        let title = self.title.trim();
+        if title.is_empty() {
+            return Err(Msg::ImportMissingTitle.text().to_string());
+        }
+
+        let mut notes = self.notes;
+        let mut task = Task::new(title.to_string(), None, self.priority, None, None);
+
+        if let Some(due) = self.due.as_deref().map(str::trim).filter(|d| !d.is_empty()) {
+            match parse_loose_datetime(due, tz) {
+                Some((date, time)) => task.due_date = Some(due_from_parts(date, time.filter(|_| !self.all_day))),
+                // 重复规则、自然语言等无法识别的截止时间保留在描述中
+                None => notes.push(fill(Msg::ImportOriginalDue.text(), &[&due])),
+            }
+        }
+
+        if let Some((date, time)) = self.created_at.as_deref().and_then(|c| parse_loose_datetime(c, tz)) {
+            task.created_at = timestamp_from_parts(date, time, tz);
+            task.updated_at = task.created_at.clone();
+        }
+
+        if self.completed {
+            task.completed = true;
+            task.completed_at = Some(self.completed_at.as_deref()
+                .and_then(|c| parse_loose_datetime(c, tz))
+                .map_or_else(|| Utc::now().to_rfc3339(), |(date, time)| timestamp_from_parts(date, time, tz)));
+        }
+
+        if let Some(list) = self.list.as_deref().map(str::trim).filter(|l| !l.is_empty()) {
+            notes.push(fill(Msg::ImportListNote.text(), &[&list]));
+        }
+        let description: Vec<&str> = notes.iter().map(|n| n.trim()).filter(|n| !n.is_empty()).collect();
+        task.description = Some(description.join("\n")).filter(|d| !d.is_empty());
+
+        Ok(task)
+    }
+}
+
+type Rows = Vec<(usize, Result<Draft, String>)>;
+
+fn unrecognized() -> AppError {
+    AppError::invalid_input(Msg::ImportUnrecognizedFormat.text())
+}
+
+/// 解析其他应用的导出文件，无法识别的条目记录原因后跳过
+pub fn parse<Tz: TimeZone>(source: ImportSource, content: &str, skip_completed: bool, tz: &Tz) -> AppResult<TaskImportResult> {
+    let content = content.strip_prefix(UTF8_BOM).unwrap_or(content);
+    let rows = match source {
+        ImportSource::MicrosoftTodo => parse_microsoft_todo(content)?,
+        ImportSource::TickTick => parse_ticktick(content)?,
+        ImportSource::Todoist => parse_todoist(content)?,
+        ImportSource::TodoTxt => parse_todo_txt(content),
+    };
+
+    let mut tasks = Vec::new();
+    let mut errors = Vec::new();
+    for (row, draft) in rows {
+        match draft.and_then(|d| d.into_task(tz)) {
+            Ok(task) if skip_completed && task.completed => {}
+            Ok(task) => tasks.push(task),
+            Err(message) => errors.push(ImportRowError { row, message }),
+        }
+    }
+    Ok(TaskImportResult { tasks, errors })
+}
+
+// ---------- CSV ----------
+
+/// 按候选列名（不区分大小写）查找的表头
+struct Columns(Vec<String>);
+
+impl Columns {
+    fn find(&self, names: &[&str]) -> Option<usize> {
+        self.0.iter().position(|h| names.iter().any(|n| h.eq_ignore_ascii_case(n)))
+    }
+}
+
+fn cell(record: &StringRecord, column: Option<usize>) -> &str {
+    column.and_then(|c| record.get(c)).unwrap_or_default().trim()
+}
+
+fn csv_reader(content: &str) -> csv::Reader<&[u8]> {
+    ReaderBuilder::new().flexible(true).from_reader(content.as_bytes())
+}
+
+fn csv_headers(reader: &mut csv::Reader<&[u8]>) -> AppResult<Columns> {
+    let headers = reader.headers().map_err(|_| unrecognized())?;
+    Ok(Columns(headers.iter().map(|h| h.trim().to_string()).collect()))
+}
+
+fn malformed(e: &csv::Error, line_offset: usize, fallback: usize) -> (usize, Result<Draft, String>) {
+    let row = e.position().map_or(fallback, |p| p.line() as usize + line_offset);
+    (row, Err(fill(Msg::ImportMalformedRow.text(), &[e])))
+}
+
+/// 滴答清单 / TickTick 设置中导出的 CSV 备份
+///
+/// 文件开头有几行说明，表头是同时包含 Title 与 List Name 的那一行；
+/// 优先级 0/1/3/5 分别为无/低/中/高，状态 1、2 为已完成与已归档
+fn parse_ticktick(content: &str) -> AppResult<Rows> {
+    let mut offset = 0;
+    let mut header_line = None;
+    for (index, line) in content.split_inclusive('\n').enumerate() {
+        let lower = line.to_lowercase();
+        if (lower.contains("\"title\"") || lower.contains("\"标题\"")) && (lower.contains("list name") || lower.contains("清单")) {
+            header_line = Some(index);
+            break;
+        }
+        offset += line.len();
+    }
+    let header_line = header_line.ok_or_else(unrecognized)?;
+
+    let mut reader = csv_reader(&content[offset..]);
+    let columns = csv_headers(&mut reader)?;
+    let title = columns.find(&["Title", "标题"]);
+    let list = columns.find(&["List Name", "清单名称"]);
+    let content_column = columns.find(&["Content", "内容"]);
+    let kind = columns.find(&["Kind", "类型"]);
+    let priority = columns.find(&["Priority", "优先级"]);
+    let status = columns.find(&["Status", "状态"]);
+    let due = columns.find(&["Due Date", "截止日期"]);
+    let all_day = columns.find(&["Is All Day", "是否全天"]);
+    let created = columns.find(&["Created Time", "创建时间"]);
+    let completed = columns.find(&["Completed Time", "完成时间"]);
+
+    let mut rows = Vec::new();
+    for (index, record) in reader.records().enumerate() {
+        let fallback = header_line + index + 2;
+        let record = match record {
+            Ok(record) => record,
+            Err(e) => {
+                rows.push(malformed(&e, header_line, fallback));
+                continue;
+            }
+        };
+        // 笔记不是待办
+        if cell(&record, kind).eq_ignore_ascii_case("note") {
+            continue;
+        }
+        let row = record.position().map_or(fallback, |p| p.line() as usize + header_line);
+        let draft = Draft {
+            title: cell(&record, title).to_string(),
+            notes: vec![cell(&record, content_column).to_string()],
+            list: Some(cell(&record, list).to_string()),
+            priority: match cell(&record, priority) {
+                "1" => Some(Priority::Low),
+                "3" => Some(Priority::Medium),
+                "5" => Some(Priority::High),
+                _ => None,
+            },
+            due: Some(cell(&record, due).to_string()),
+            all_day: cell(&record, all_day).eq_ignore_ascii_case("true"),
+            completed: matches!(cell(&record, status), "1" | "2"),
+            completed_at: Some(cell(&record, completed).to_string()),
+            created_at: Some(cell(&record, created).to_string()),
+        };
+        rows.push((row, Ok(draft)));
+    }
+    Ok(rows)
+}
+
+/// Todoist 项目导出的 CSV 模板
+///
+/// TYPE 为 section 的行作为之后待办的清单，note 行附加到上一个待办的描述；
+/// 优先级 1 为最高（p1），4 为未设置；模板中只有未完成的待办
+fn parse_todoist(content: &str) -> AppResult<Rows> {
+    let mut reader = csv_reader(content);
+    let columns = csv_headers(&mut reader)?;
+    let kind = columns.find(&["TYPE"]);
+    let title = columns.find(&["CONTENT"]);
+    if kind.is_none() || title.is_none() {
+        return Err(unrecognized());
+    }
+    let description = columns.find(&["DESCRIPTION"]);
+    let priority = columns.find(&["PRIORITY"]);
+    let date = columns.find(&["DATE"]);
+    let deadline = columns.find(&["DEADLINE"]);
+
+    let mut rows: Rows = Vec::new();
+    let mut section = None;
+    for (index, record) in reader.records().enumerate() {
+        let record = match record {
+            Ok(record) => record,
+            Err(e) => {
+                rows.push(malformed(&e, 0, index + 2));
+                continue;
+            }
+        };
+        let row = record.position().map_or(index + 2, |p| p.line() as usize);
+        match cell(&record, kind).to_lowercase().as_str() {
+            "section" => section = Some(cell(&record, title).to_string()),
+            "task" => {
+                let due = [cell(&record, deadline), cell(&record, date)].into_iter().find(|d| !d.is_empty());
+                let draft = Draft {
+                    title: cell(&record, title).to_string(),
+                    notes: vec![cell(&record, description).to_string()],
+                    list: section.clone(),
+                    priority: match cell(&record, priority) {
+                        "1" => Some(Priority::High),
+                        "2" => Some(Priority::Medium),
+                        "3" => Some(Priority::Low),
+                        _ => None,
+                    },
+                    due: due.map(str::to_string),
+                    ..Draft::default()
+                };
+                rows.push((row, Ok(draft)));
+            }
+            "note" | "comment" => {
+                if let Some((_, Ok(previous))) = rows.last_mut() {
+                    previous.notes.push(cell(&record, title).to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(rows)
+}
+
+// ---------- todo.txt ----------
+
+/// todo.txt 格式：x 完成日期 创建日期 (A) 标题 +项目 @情境 due:YYYY-MM-DD
+///
+/// (A) 为高优先级，(B) 为中，其余为低；完成的待办优先级写在 pri:A 中；第一个 +项目 作为清单
+fn parse_todo_txt(content: &str) -> Rows {
+    let mut rows = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let mut draft = Draft::default();
+        let mut tokens: Vec<&str> = line.split_whitespace().collect();
+        let take_date = |tokens: &mut Vec<&str>| {
+            let date = tokens.first().and_then(|t| parse_date(t)).map(|_| tokens[0].to_string());
+            if date.is_some() {
+                tokens.remove(0);
+            }
+            date
+        };
+
+        if tokens.first() == Some(&"x") {
+            tokens.remove(0);
+            draft.completed = true;
+            draft.completed_at = take_date(&mut tokens);
+        }
+        if let Some(letter) = tokens.first().and_then(|t| priority_letter(t.strip_prefix('(')?.strip_suffix(')')?)) {
+            draft.priority = Some(letter);
+            tokens.remove(0);
+        }
+        draft.created_at = take_date(&mut tokens);
+
+        let mut title = Vec::new();
+        let mut projects = Vec::new();
+        for token in tokens {
+            if let Some(due) = token.strip_prefix("due:") {
+                draft.due = Some(due.to_string());
+            } else if let Some(letter) = token.strip_prefix("pri:").and_then(priority_letter) {
+                draft.priority = Some(letter);
+            } else if let Some(project) = token.strip_prefix('+').filter(|p| !p.is_empty()) {
+                projects.push(project);
+            } else {
+                title.push(token);
+            }
+        }
+        draft.title = title.join(" ");
+        draft.list = projects.first().map(|p| p.to_string());
+
+        rows.push((index + 1, Ok(draft)));
+    }
+    rows
+}
+
+fn priority_letter(value: &str) -> Option<Priority> {
+    let mut chars = value.chars();
+    let letter = chars.next().filter(|c| c.is_ascii_uppercase() && chars.next().is_none())?;
+    Some(match letter {
+        'A' => Priority::High,
+        'B' => Priority::Medium,
+        _ => Priority::Low,
+    })
+}
+
+// ---------- Microsoft To Do ----------
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphDateTime {
+    date_time: String,
+    #[serde(default)]
+    time_zone: String,
+}
+
+impl GraphDateTime {
+    /// 时区为 UTC 时补上 Z，其余按本地时间理解
+    fn value(&self) -> String {
+        if self.time_zone.eq_ignore_ascii_case("utc") && !self.date_time.ends_with('Z') {
+            format!("{}Z", self.date_time)
+        } else {
+            self.date_time.clone()
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphBody {
+    #[serde(default)]
+    content: String,
+    #[serde(default)]
+    content_type: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphChecklistItem {
+    #[serde(default)]
+    display_name: String,
+    #[serde(default)]
+    is_checked: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphTask {
+    title: String,
+    body: Option<GraphBody>,
+    #[serde(default)]
+    importance: String,
+    #[serde(default)]
+    status: String,
+    due_date_time: Option<GraphDateTime>,
+    completed_date_time: Option<GraphDateTime>,
+    created_date_time: Option<String>,
+    #[serde(default)]
+    checklist_items: Vec<GraphChecklistItem>,
+}
+
+/// 去掉 HTML 格式的备注中的标签
+fn strip_html(html: &str) -> String {
+    let html = html.replace("<br>", "\n").replace("<br/>", "\n").replace("</p>", "\n").replace("</div>", "\n");
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.replace("&nbsp;", " ").replace("&lt;", "<").replace("&gt;", ">").replace("&quot;", "\"").replace("&amp;", "&")
+}
+
+impl GraphTask {
+    fn into_draft(self, list: &str) -> Draft {
+        let mut notes = Vec::new();
+        if let Some(body) = self.body {
+            notes.push(if body.content_type.eq_ignore_ascii_case("html") { strip_html(&body.content) } else { body.content });
+        }
+        // 步骤写成 Markdown 清单
+        for item in self.checklist_items {
+            notes.push(format!("- [{}] {}", if item.is_checked { "x" } else { " " }, item.display_name));
+        }
+
+        Draft {
+            title: self.title,
+            notes,
+            list: Some(list.to_string()),
+            priority: match self.importance.to_lowercase().as_str() {
+                "high" => Some(Priority::High),
+                "low" => Some(Priority::Low),
+                _ => None,
+            },
+            // To Do 的截止时间只有日期
+            due: self.due_date_time.map(|d| d.date_time.chars().take(10).collect()),
+            all_day: true,
+            completed: self.status.eq_ignore_ascii_case("completed"),
+            completed_at: self.completed_date_time.map(|d| d.value()),
+            created_at: self.created_date_time,
+        }
+    }
+}
+
+/// Microsoft Graph 格式的 JSON，支持以下几种结构：
+/// 清单数组 `[{ displayName, tasks: [...] }]`、`{ lists: [...] }`、单个清单的 `{ value: [...] }` 以及待办数组
+fn parse_microsoft_todo(content: &str) -> AppResult<Rows> {
+    let value: Value = serde_json::from_str(content).map_err(|_| unrecognized())?;
+
+    let has_tasks = |items: &[Value]| items.iter().any(|item| item.get("tasks").is_some());
+    let lists: Vec<(String, Vec<Value>)> = match value {
+        Value::Array(items) if has_tasks(&items) => graph_lists(items),
+        Value::Array(items) => vec![(String::new(), items)],
+        Value::Object(mut map) => match (map.remove("lists"), map.remove("value")) {
+            (Some(Value::Array(lists)), _) => graph_lists(lists),
+            (_, Some(Value::Array(tasks))) => vec![(String::new(), tasks)],
+            _ => return Err(unrecognized()),
+        },
+        _ => return Err(unrecognized()),
+    };
+
+    let mut rows = Vec::new();
+    for (list, tasks) in lists {
+        for task in tasks {
+            let row = rows.len() + 1;
+            let draft = serde_json::from_value::<GraphTask>(task)
+                .map(|task| task.into_draft(&list))
+                .map_err(|e| fill(Msg::ImportMalformedRow.text(), &[&e]));
+            rows.push((row, draft));
+        }
+    }
+    Ok(rows)
+}
+
+fn graph_lists(lists: Vec<Value>) -> Vec<(String, Vec<Value>)> {
+    lists.into_iter()
+        .map(|mut list| {
+            let name = list.get("displayName").and_then(Value::as_str).unwrap_or_default().to_string();
+            let tasks = match list.get_mut("tasks").map(Value::take) {
+                Some(Value::Array(tasks)) => tasks,
+                _ => Vec::new(),
+            };
+            (name, tasks)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{DateTime, FixedOffset, NaiveDate};
+    use crate::datetime::local_date;
+    use crate::task_list::todo_txt_line;
+
+    fn tz() -> FixedOffset {
+        FixedOffset::east_opt(8 * 3600).unwrap()
+    }
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn instant(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value).unwrap().with_timezone(&Utc)
+    }
+
+    fn list_note(list: &str) -> String {
+        fill(Msg::ImportListNote.text(), &[&list])
+    }
+
+    fn import(source: ImportSource, content: &str) -> TaskImportResult {
+        parse(source, content, false, &tz()).unwrap()
+    }
+
+    fn error_rows(result: &TaskImportResult) -> Vec<usize> {
+        result.errors.iter().map(|e| e.row).collect()
+    }
+
+    #[test]
+    fn ticktick_backup() {
+        // 开头的说明中有跨行的引号，行号仍按文件中的行计算
+        let content = "\u{feff}\"Date: 2025-01-05+0000\"\n\
+            \"Version: 7.1\"\n\
+            \"Status: \n\
+            0 Normal\n\
+            1 Completed\n\
+            2 Archived\"\n\
+            \"List Name\",\"Title\",\"Kind\",\"Content\",\"Due Date\",\"Is All Day\",\"Priority\",\"Status\",\"Created Time\",\"Completed Time\"\n\
+            \"Inbox\",\"Write report\",\"TEXT\",\"draft first\",\"2025-01-10T09:00:00+0000\",\"false\",\"5\",\"0\",\"2025-01-01T08:00:00+0000\",\"\"\n\
+            \"Work\",\"Old task\",\"TEXT\",\"\",\"\",\"\",\"1\",\"2\",\"2024-12-01T08:00:00+0000\",\"2024-12-02T10:00:00+0000\"\n\
+            \"Work\",\"Meeting memo\",\"NOTE\",\"text\",\"\",\"\",\"0\",\"0\",\"\",\"\"\n\
+            \"Work\",\"\",\"TEXT\",\"\",\"\",\"\",\"0\",\"0\",\"\",\"\"\n\
+            \"Home\",\"Buy milk\",\"CHECKLIST\",\"\",\"2025-01-11T16:00:00+0000\",\"true\",\"3\",\"1\",\"\",\"\"\n";
+        let result = import(ImportSource::TickTick, content);
+        assert_eq!(error_rows(&result), [11]);
+
+        let [report, old, milk] = &result.tasks[..] else {
+            panic!("unexpected tasks {:?}", result.tasks);
+        };
+        assert_eq!(report.title, "Write report");
+        assert_eq!(report.priority, Priority::High);
+        assert!(!report.completed);
+        assert_eq!(report.due_date.as_deref(), Some("2025-01-10T17:00"));
+        assert_eq!(report.description, Some(format!("draft first\n{}", list_note("Inbox"))));
+        assert_eq!(instant(&report.created_at), instant("2025-01-01T08:00:00Z"));
+
+        // 状态 2 为已归档，同样视为完成
+        assert_eq!(old.priority, Priority::Low);
+        assert!(old.completed);
+        assert_eq!(old.completed_at.as_deref().map(instant), Some(instant("2024-12-02T10:00:00Z")));
+
+        // 全天任务只保留本地的截止日期
+        assert_eq!(milk.priority, Priority::Medium);
+        assert!(milk.completed);
+        assert_eq!(milk.due_date.as_deref(), Some("2025-01-12"));
+
+        assert!(parse(ImportSource::TickTick, "Title,Due\nA,2025-01-01\n", false, &tz()).is_err());
+    }
+
+    #[test]
+    fn todoist_template() {
+        let content = "TYPE,CONTENT,DESCRIPTION,PRIORITY,INDENT,DATE,DATE_LANG,DEADLINE\n\
+            task,Plan trip,Book flights,1,1,2025-02-01,en,\n\
+            note,Check visa,,,,,,\n\
+            section,Errands,,,,,,\n\
+            task,Buy stamps,,4,1,every monday,en,2025-02-03\n\
+            task,Call bank,,2,1,every day,en,\n\
+            ,,,,,,,\n\
+            task,,,3,1,,,\n";
+        let result = import(ImportSource::Todoist, content);
+        assert_eq!(error_rows(&result), [8]);
+
+        let [trip, stamps, bank] = &result.tasks[..] else {
+            panic!("unexpected tasks {:?}", result.tasks);
+        };
+        assert_eq!(trip.priority, Priority::High);
+        assert_eq!(trip.due_date.as_deref(), Some("2025-02-01"));
+        assert_eq!(trip.description.as_deref(), Some("Book flights\nCheck visa"));
+
+        // 有 DEADLINE 时优先使用，section 之后的待办记下清单名
+        assert_eq!(stamps.priority, Priority::Medium);
+        assert_eq!(stamps.due_date.as_deref(), Some("2025-02-03"));
+        assert_eq!(stamps.description, Some(list_note("Errands")));
+
+        // 无法识别的重复规则保留在描述中
+        assert_eq!(bank.due_date, None);
+        assert_eq!(
+            bank.description,
+            Some(format!("{}\n{}", fill(Msg::ImportOriginalDue.text(), &[&"every day"]), list_note("Errands")))
+        );
+
+        assert!(parse(ImportSource::Todoist, "Title,Due\nA,2025-01-01\n", false, &tz()).is_err());
+    }
+
+    #[test]
+    fn todo_txt_lines() {
+        let content = "(A) 2025-01-01 Call mom +Family +Home @phone due:2025-01-05\n\
+            x 2025-01-03 2025-01-02 Pay rent pri:B\n\
+            x Done without dates\n\
+            \n\
+            (C) Low thing\n\
+            (a) lowercase is part of the title +\n\
+            x 2025-01-03\n";
+        let result = import(ImportSource::TodoTxt, content);
+        assert_eq!(error_rows(&result), [7]);
+
+        let [mom, rent, done, low, plain] = &result.tasks[..] else {
+            panic!("unexpected tasks {:?}", result.tasks);
+        };
+        assert_eq!(mom.title, "Call mom @phone");
+        assert_eq!(mom.priority, Priority::High);
+        assert_eq!(mom.due_date.as_deref(), Some("2025-01-05"));
+        assert_eq!(mom.description, Some(list_note("Family")));
+        assert_eq!(local_date(&mom.created_at, &tz()), Some(date(2025, 1, 1)));
+
+        assert!(rent.completed);
+        assert_eq!(rent.priority, Priority::Medium);
+        assert_eq!(rent.completed_at.as_deref().and_then(|c| local_date(c, &tz())), Some(date(2025, 1, 3)));
+        assert_eq!(local_date(&rent.created_at, &tz()), Some(date(2025, 1, 2)));
+
+        assert!(done.completed && done.completed_at.is_some());
+        assert_eq!(done.title, "Done without dates");
+        assert_eq!(low.priority, Priority::Low);
+        assert_eq!(plain.title, "(a) lowercase is part of the title +");
+
+        let skipped = parse(ImportSource::TodoTxt, content, true, &tz()).unwrap();
+        assert_eq!(skipped.tasks.len(), 3);
+    }
+
+    #[test]
+    fn todo_txt_round_trip() {
+        let mut high = Task::new("Call mom".to_string(), None, Some(Priority::High), Some("2025-01-05".to_string()), None);
+        high.created_at = "2025-01-01T02:00:00+00:00".to_string();
+        let mut done = Task::new("Pay rent".to_string(), None, Some(Priority::Medium), None, None);
+        done.created_at = "2025-01-02T02:00:00+00:00".to_string();
+        done.completed = true;
+        done.completed_at = Some("2025-01-03T02:00:00+00:00".to_string());
+        let low = Task::new("Read".to_string(), None, Some(Priority::Low), Some("2025-01-07T20:30".to_string()), None);
+
+        let originals = [high, done, low];
+        let content: String = originals.iter().map(|t| todo_txt_line(t, &tz()) + "\n").collect();
+        let result = import(ImportSource::TodoTxt, &content);
+        assert!(result.errors.is_empty());
+        assert_eq!(result.tasks.len(), originals.len());
+
+        let day = |value: &str| local_date(value, &tz());
+        for (imported, original) in result.tasks.iter().zip(&originals) {
+            assert_eq!(imported.title, original.title);
+            assert_eq!(imported.priority, original.priority);
+            assert_eq!(imported.completed, original.completed);
+            // todo.txt 只保留日期
+            assert_eq!(imported.due_date.as_deref().and_then(day), original.due_date.as_deref().and_then(day));
+            assert_eq!(day(&imported.created_at), day(&original.created_at));
+            assert_eq!(imported.completed_at.as_deref().and_then(day), original.completed_at.as_deref().and_then(day));
+        }
+    }
+
+    #[test]
+    fn microsoft_todo_shapes() {
+        let report_json = r#"{
+            "title": "Report",
+            "body": { "content": "<p>Line&nbsp;one</p><div>two &amp; three</div>", "contentType": "html" },
+            "importance": "high",
+            "status": "completed",
+            "dueDateTime": { "dateTime": "2025-03-01T00:00:00.0000000", "timeZone": "UTC" },
+            "completedDateTime": { "dateTime": "2025-02-28T10:00:00.0000000", "timeZone": "UTC" },
+            "createdDateTime": "2025-02-01T08:00:00Z",
+            "checklistItems": [{ "displayName": "Outline", "isChecked": true }, { "displayName": "Draft" }]
+        }"#;
+        let plain_json = r#"{ "title": "Plain", "importance": "low", "status": "notStarted" }"#;
+        let broken_json = r#"{ "title": 5 }"#;
+
+        let lists = import(ImportSource::MicrosoftTodo, &format!(
+            r#"{{ "lists": [{{ "displayName": "Work", "tasks": [{}, {}] }}, {{ "displayName": "Empty" }}, {{ "displayName": "Home", "tasks": [{}] }}] }}"#,
+            report_json, broken_json, plain_json,
+        ));
+        assert_eq!(error_rows(&lists), [2]);
+        let [report, plain] = &lists.tasks[..] else {
+            panic!("unexpected tasks {:?}", lists.tasks);
+        };
+        assert_eq!(report.title, "Report");
+        assert_eq!(report.priority, Priority::High);
+        assert_eq!(report.due_date.as_deref(), Some("2025-03-01"));
+        assert!(report.completed);
+        assert_eq!(report.completed_at.as_deref().map(instant), Some(instant("2025-02-28T10:00:00Z")));
+        assert_eq!(instant(&report.created_at), instant("2025-02-01T08:00:00Z"));
+        assert_eq!(
+            report.description,
+            Some(format!("Line one\ntwo & three\n- [x] Outline\n- [ ] Draft\n{}", list_note("Work")))
+        );
+        assert_eq!(plain.priority, Priority::Low);
+        assert!(!plain.completed);
+        assert_eq!(plain.description, Some(list_note("Home")));
+
+        // 清单数组、单个清单的 { value } 与待办数组
+        let array = import(ImportSource::MicrosoftTodo, &format!(r#"[{{ "displayName": "Home", "tasks": [{}] }}]"#, plain_json));
+        assert_eq!(array.tasks[0].description, Some(list_note("Home")));
+        let value = import(ImportSource::MicrosoftTodo, &format!(r#"{{ "value": [{}] }}"#, plain_json));
+        assert_eq!((value.tasks[0].title.as_str(), value.tasks[0].description.as_deref()), ("Plain", None));
+        assert_eq!(value.tasks[0].priority, Priority::Low);
+        let tasks = import(ImportSource::MicrosoftTodo, &format!("[{}]", plain_json));
+        assert_eq!(tasks.tasks.len(), 1);
+
+        for content in ["{}", "\"text\"", "not json"] {
+            assert!(parse(ImportSource::MicrosoftTodo, content, false, &tz()).is_err());
+        }
+    }
+}
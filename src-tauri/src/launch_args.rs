@@ -0,0 +1,30 @@
+use tauri::AppHandle;
+use crate::single_instance::ForwardedArgs;
+use crate::window::show_main_window;
+use crate::cli;
+use crate::deep_link;
+use crate::autostart;
+
+/// 处理再次启动时转发来的参数，返回给第二个进程的回复
+///
+/// 命令行命令与深链接在主实例中执行，开机启动项保持现状；其他参数（包括不带参数再次启动）没有对应的操作，只唤醒主窗口
+pub fn handle_forwarded_args(app: &AppHandle, forwarded: ForwardedArgs) -> serde_json::Value {
+    log::info!("🔔 收到新实例的启动参数: {:?}", forwarded.args);
+
+    // 命令行命令在主实例中执行，不唤醒窗口
+    if cli::is_cli_invocation(&forwarded.args) {
+        return cli::handle_forwarded(app, &forwarded.args);
+    }
+
+    if let Some(link) = deep_link::find_in_args(&forwarded.args) {
+        return serde_json::to_value(deep_link::handle_link(app, link)).unwrap_or_default();
+    }
+
+    // 开机启动项在应用已运行时再次启动，保持现状
+    if autostart::is_minimized_launch(&forwarded.args) {
+        return serde_json::json!({ "success": true });
+    }
+
+    show_main_window(app);
+    serde_json::json!({ "success": true })
+}
@@ -2,33 +2,116 @@ mod models;
 mod storage;
 mod commands;
 mod single_instance;
+mod datetime;
+mod stats;
+mod error;
+mod i18n;
+mod tray;
+mod window;
+mod launch_args;
+mod cli;
+mod deep_link;
+mod api_server;
+mod mcp;
+mod quick_add;
+mod autostart;
+mod usage;
+mod pomodoro;
+mod time_tracking;
+mod report;
+mod task_csv;
+mod importers;
+mod task_list;
+mod sync;
 
 use std::sync::Mutex;
 use storage::Storage;
-use single_instance::ensure_single_instance;
-use tauri::{Manager, menu::{Menu, MenuItem}, tray::{TrayIconBuilder, TrayIconEvent}, WindowEvent};
+use single_instance::{ensure_single_instance, ForwardedArgs, InstanceRole};
+use tauri::{Manager, RunEvent, WindowEvent};
+use tauri_plugin_deep_link::DeepLinkExt;
+
+// 单实例锁、套接字等使用的应用名
+pub(crate) const APP_NAME: &str = "SemiDone";
+
+/// 单实例锁使用的名称；通过环境变量指定了数据目录时按目录区分，多份数据可以同时运行
+pub(crate) fn instance_name() -> String {
+  use std::hash::{DefaultHasher, Hash, Hasher};
+
+  match std::env::var_os(storage::DATA_DIR_ENV).filter(|dir| !dir.is_empty()) {
+    Some(dir) => {
+      let mut hasher = DefaultHasher::new();
+      dir.hash(&mut hasher);
+      format!("{}-{:016x}", APP_NAME, hasher.finish())
+    }
+    None => APP_NAME.to_string(),
+  }
+}
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
-  // 检查单实例
-  if let Err(e) = ensure_single_instance("SemiDone") {
-    eprintln!("Application is already running: {}", e);
-    std::process::exit(1);
+  // 命令行模式：不启动界面，执行完即退出
+  let args: Vec<String> = std::env::args().skip(1).collect();
+  if cli::is_cli_invocation(&args) {
+    std::process::exit(cli::run(&args));
+  }
+
+  // MCP 模式：通过 stdio 为 AI 助手提供待办工具，由客户端启动并长期运行
+  if args.first().map(String::as_str) == Some("--mcp") {
+    std::process::exit(mcp::run());
   }
+
+  // 开机启动时只在托盘中运行
+  let start_minimized = autostart::is_minimized_launch(&args);
+
+  // 检查单实例：已有实例运行时把启动参数转发过去后退出
+  let instance_server = match ensure_single_instance(&instance_name(), &ForwardedArgs::from_env()) {
+    Ok(InstanceRole::Primary(server)) => server,
+    Ok(InstanceRole::Secondary(reply)) => {
+      println!("Application is already running, arguments forwarded: {}", reply);
+      std::process::exit(0);
+    }
+    Err(e) => {
+      eprintln!("Application is already running: {}", e);
+      std::process::exit(1);
+    }
+  };
   
   // 初始化存储
   let storage = Storage::new().expect("Failed to initialize storage");
   
+  // 按设置中的语言初始化后端文案
+  if let Ok(settings) = storage.load_settings() {
+    i18n::set_locale(settings.locale);
+  }
+  
+  // 恢复上次退出时的番茄钟状态
+  let pomodoro = pomodoro::PomodoroEngine::load(&storage);
+  
   // 创建系统托盘菜单将在setup中处理
   
   tauri::Builder::default()
     .manage(Mutex::new(storage))
-    .on_window_event(|_window, event| match event {
-      WindowEvent::CloseRequested { api, .. } => {
-        _window.hide().unwrap();
-        api.prevent_close();
+    .manage(window::PendingRoute::default())
+    .manage(api_server::ApiServerState::default())
+    .manage(quick_add::QuickAddState::default())
+    .manage(tray::TrayState::default())
+    .manage(usage::UsageState::default())
+    .manage(pomodoro)
+    .on_window_event(|_window, event| {
+      match event {
+        WindowEvent::CloseRequested { api, .. } => {
+          _window.hide().unwrap();
+          api.prevent_close();
+        }
+        _ => {}
+      }
+      
+      // 主窗口显示、隐藏、最小化时开始或结束使用时长记录
+      if _window.label() == window::MAIN_WINDOW
+        && matches!(event, WindowEvent::CloseRequested { .. } | WindowEvent::Focused(_) | WindowEvent::Resized(_))
+      {
+        usage::sync(_window.app_handle());
       }
-      _ => {}
     })
     .invoke_handler(tauri::generate_handler![
       commands::get_tasks,
@@ -36,160 +119,125 @@ pub fn run() {
       commands::update_task,
       commands::delete_task,
       commands::get_task_stats,
+      commands::get_task_trend,
+      commands::get_productivity_stats,
       commands::get_settings,
       commands::update_settings,
+      commands::export_tasks_csv,
+      commands::inspect_tasks_csv,
+      commands::import_tasks_csv,
+      commands::import_from_app,
+      commands::render_task_list,
+      commands::export_task_list,
       commands::export_data,
       commands::import_data,
       commands::clear_all_data,
       commands::exit_app,
       commands::get_data_dir_path,
+      commands::get_sync_status,
+      commands::choose_sync_folder,
+      commands::configure_sync,
+      commands::sync_now,
+      commands::get_sync_conflicts,
+      commands::resolve_sync_conflict,
+      commands::take_pending_route,
+      commands::get_api_status,
+      commands::reset_api_token,
+      commands::get_shortcut_status,
+      commands::submit_quick_add,
+      commands::hide_quick_add,
+      commands::get_pomodoro_state,
+      commands::start_pomodoro,
+      commands::set_pomodoro_task,
+      commands::get_focus_sessions,
+      commands::start_time_entry,
+      commands::stop_time_entry,
+      commands::get_running_time_entry,
+      commands::get_time_entries,
+      commands::add_time_entry,
+      commands::update_time_entry,
+      commands::delete_time_entry,
+      commands::export_report,
+      commands::preview_report,
+      commands::list_report_templates,
+      commands::pause_pomodoro,
+      commands::reset_pomodoro,
+      commands::skip_pomodoro,
+      commands::update_pomodoro_settings,
+      commands::get_usage_stats,
+      commands::get_usage_details,
+      commands::import_legacy_usage,
       commands::open_file_with_system
     ])
     .plugin(tauri_plugin_fs::init())
-    .setup(|app| {
-      // 创建托盘右键菜单
-      let show_item = MenuItem::with_id(app, "show", "显示窗口", true, None::<&str>)?;
-      let quit_item = MenuItem::with_id(app, "quit", "退出", true, None::<&str>)?;
-      let menu = Menu::with_items(app, &[&show_item, &quit_item])?;
+    .plugin(tauri_plugin_deep_link::init())
+    .plugin(tauri_plugin_global_shortcut::Builder::new().build())
+    .plugin(autostart::plugin())
+    .plugin(tauri_plugin_notification::init())
+    .plugin(tauri_plugin_dialog::init())
+    .setup(move |app| {
+      // 接收后续启动转发来的参数
+      let handle = app.handle().clone();
+      instance_server.listen(move |forwarded| launch_args::handle_forwarded_args(&handle, forwarded));
       
       // 创建系统托盘（左键单击唤醒，右键显示菜单）
       // 重要：必须保存托盘对象到应用状态，否则会被释放导致功能失效
-      let tray = TrayIconBuilder::with_id("main")
-        .tooltip("事半·SemiDone")
-        .icon(app.default_window_icon().unwrap().clone())
-        .menu(&menu)
-        .on_menu_event(|app, event| {
-          println!("🔔 托盘菜单事件: {}", event.id.as_ref());
-          match event.id.as_ref() {
-            "show" => {
-              println!("  └─ 显示窗口");
-              if let Some(window) = app.get_webview_window("main") {
-                let _ = window.show();
-                let _ = window.unminimize();
-                let _ = window.set_always_on_top(true);
-                let _ = window.set_focus();
-                let _ = window.set_always_on_top(false);
-                println!("  └─ ✅ 窗口已显示并聚焦");
-              }
-            }
-            "quit" => {
-              println!("  └─ 退出应用");
-              app.exit(0);
-            }
-            _ => {}
-          }
-        })
-        .on_tray_icon_event(|tray, event| {
-          match event {
-            TrayIconEvent::Click { button, button_state, .. } => {
-              println!("🔔 托盘点击事件 - 按钮: {:?}, 状态: {:?}", button, button_state);
-              
-              // 只响应左键释放事件
-              if button == tauri::tray::MouseButton::Left && button_state == tauri::tray::MouseButtonState::Up {
-                println!("  └─ ✅ 左键释放，立即处理（无阻塞）");
-                
-                if let Some(window) = tray.app_handle().get_webview_window("main") {
-                  println!("  ├─ 找到窗口: main");
-                  
-                  // 检查窗口是否可见
-                  match window.is_visible() {
-                    Ok(is_visible) => {
-                      println!("  ├─ 窗口可见性: {}", is_visible);
-                      
-                      if is_visible {
-                        // 窗口已显示：聚焦并置顶到前台
-                        println!("  ├─ 📍 窗口已显示，强制聚焦到前台");
-                        
-                        // 先取消最小化
-                        if let Err(e) = window.unminimize() {
-                          println!("  │  ├─ ⚠️ 取消最小化失败: {:?}", e);
-                        } else {
-                          println!("  │  ├─ ✅ 取消最小化成功");
-                        }
-                        
-                        // 临时置顶（Windows需要这样才能强制前台）
-                        if let Err(e) = window.set_always_on_top(true) {
-                          println!("  │  ├─ ⚠️ 临时置顶失败: {:?}", e);
-                        } else {
-                          println!("  │  ├─ ✅ 临时置顶成功");
-                        }
-                        
-                        // 聚焦
-                        if let Err(e) = window.set_focus() {
-                          println!("  │  ├─ ⚠️ 聚焦失败: {:?}", e);
-                        } else {
-                          println!("  │  ├─ ✅ 聚焦成功");
-                        }
-                        
-                        // 立即取消置顶
-                        if let Err(e) = window.set_always_on_top(false) {
-                          println!("  │  └─ ⚠️ 取消置顶失败: {:?}", e);
-                        } else {
-                          println!("  │  └─ ✅ 取消置顶成功");
-                        }
-                      } else {
-                        // 窗口隐藏：显示并聚焦
-                        println!("  ├─ 👁️ 窗口隐藏，重新显示并聚焦");
-                        
-                        // 先显示
-                        if let Err(e) = window.show() {
-                          println!("  │  ├─ ⚠️ 显示窗口失败: {:?}", e);
-                        } else {
-                          println!("  │  ├─ ✅ 显示窗口成功");
-                        }
-                        
-                        // 取消最小化
-                        if let Err(e) = window.unminimize() {
-                          println!("  │  ├─ ⚠️ 取消最小化失败: {:?}", e);
-                        } else {
-                          println!("  │  ├─ ✅ 取消最小化成功");
-                        }
-                        
-                        // 临时置顶以强制前台
-                        if let Err(e) = window.set_always_on_top(true) {
-                          println!("  │  ├─ ⚠️ 临时置顶失败: {:?}", e);
-                        } else {
-                          println!("  │  ├─ ✅ 临时置顶成功");
-                        }
-                        
-                        // 聚焦
-                        if let Err(e) = window.set_focus() {
-                          println!("  │  ├─ ⚠️ 聚焦失败: {:?}", e);
-                        } else {
-                          println!("  │  ├─ ✅ 聚焦成功");
-                        }
-                        
-                        // 取消置顶
-                        if let Err(e) = window.set_always_on_top(false) {
-                          println!("  │  └─ ⚠️ 取消置顶失败: {:?}", e);
-                        } else {
-                          println!("  │  └─ ✅ 取消置顶成功");
-                        }
-                      }
-                      
-                      println!("✅ [处理完成] 托盘单击处理完成（无阻塞）\n");
-                    }
-                    Err(e) => {
-                      println!("  └─ ❌ 检查窗口可见性失败: {:?}", e);
-                    }
-                  }
-                } else {
-                  println!("  └─ ❌ 未找到窗口: main");
-                }
-              }
-              // 不输出忽略消息，减少日志噪音
-            }
-            _ => {
-              // 忽略其他事件（Move, Enter, Leave等），不输出任何日志
-            }
-          }
-        })
-        .build(app)?;
+      let tray = tray::create_tray(app.handle())?;
       
       // 关键修复：将托盘对象保存到应用状态中，防止被释放
       app.manage(tray);
       println!("✅ 托盘对象已保存到应用状态，生命周期已绑定到应用\n");
       
+      // 待办到期、跨过零点时更新托盘提示和角标
+      tray::start_refresh_timer(app.handle().clone());
+      
+      // 主窗口默认不显示（tauri.conf.json 中 visible: false），以 --minimized 启动时保持隐藏
+      if !start_minimized {
+        window::show_main_window(app.handle());
+      }
+      
+      // 记录主窗口的使用时长
+      usage::start_tracking(app.handle().clone());
+      
+      // 番茄钟在后台计时，窗口隐藏时照常切换阶段并发送通知
+      pomodoro::start_timer(app.handle().clone());
+      
+      // 启动时与同步文件夹合并，之后其他设备有修改时自动合并
+      sync::start_watcher(app.handle().clone());
+      
+      // 深链接：Windows / Linux 通过启动参数传入（运行中则经单实例转发），macOS 通过系统事件
+      #[cfg(any(target_os = "linux", all(debug_assertions, windows)))]
+      if let Err(e) = app.deep_link().register_all() {
+        log::warn!("Failed to register URL scheme: {}", e);
+      }
+      let handle = app.handle().clone();
+      app.deep_link().on_open_url(move |event| {
+        for url in event.urls() {
+          deep_link::handle_link(&handle, url.as_str());
+        }
+      });
+      if let Some(link) = deep_link::find_in_args(&args) {
+        deep_link::handle_link(app.handle(), link);
+      }
+      
+      // 按设置启动本地 HTTP 接口（默认关闭）、注册快速添加快捷键并同步开机启动项
+      {
+        let state = app.state::<Mutex<Storage>>();
+        let storage = state.lock().expect("storage lock poisoned");
+        if let Ok(settings) = storage.load_settings() {
+          if let Err(e) = api_server::apply_settings(app.handle(), &storage, &settings) {
+            log::warn!("Failed to start local API: {}", e);
+          }
+          // 快捷键被占用时记录原因，设置页会显示
+          if let Err(e) = quick_add::apply_settings(app.handle(), &settings) {
+            log::warn!("Failed to register quick add shortcut: {}", e);
+          }
+          if let Err(e) = autostart::apply_settings(app.handle(), &settings) {
+            log::warn!("Failed to update launch at login: {}", e);
+          }
+        }
+      }
+      
       if cfg!(debug_assertions) {
         app.handle().plugin(
           tauri_plugin_log::Builder::default()
@@ -199,6 +247,12 @@ pub fn run() {
       }
       Ok(())
     })
-    .run(tauri::generate_context!())
-    .expect("error while running tauri application");
+    .build(tauri::generate_context!())
+    .expect("error while running tauri application")
+    .run(|app, event| {
+      // 退出前保存进行中的使用时段
+      if let RunEvent::Exit = event {
+        usage::end_session(app);
+      }
+    });
 }
@@ -0,0 +1,399 @@
+use std::io::{self, BufRead, Write};
+use serde_json::{json, Value};
+use crate::cli;
+
+// 支持的 MCP 协议版本，客户端请求的版本不在列表中时返回最新版本
+const PROTOCOL_VERSIONS: &[&str] = &["2025-06-18", "2025-03-26", "2024-11-05"];
+
+// JSON-RPC 错误码
+const PARSE_ERROR: i64 = -32700;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const INTERNAL_ERROR: i64 = -32603;
+
+const TODAY_URI: &str = "semidone://tasks/today";
+const OVERDUE_URI: &str = "semidone://tasks/overdue";
+
+type RpcResult = Result<Value, (i64, String)>;
+
+/// `--mcp` 模式入口：通过 stdin/stdout 以换行分隔的 JSON-RPC 提供 MCP 服务，返回进程退出码
+///
+/// 每次调用都经由命令行的执行路径：界面在运行时转发给它执行，否则短暂加锁后直接读写数据文件，
+/// 因此本进程可以长时间运行而不会阻止界面启动或覆盖界面的写入。stdout 只能输出协议消息，日志写 stderr
+pub fn run() -> i32 {
+    cli::init_locale();
+    eprintln!("SemiDone MCP server listening on stdio");
+
+    let stdin = io::stdin();
+    let mut stdout = io::stdout();
+    for line in stdin.lock().lines() {
+        let Ok(line) = line else { break };
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        if let Some(reply) = handle_line(&line) {
+            if writeln!(stdout, "{}", reply).and_then(|_| stdout.flush()).is_err() {
+                break;
+            }
+        }
+    }
+    0
+}
+
+fn handle_line(line: &str) -> Option<Value> {
+    match serde_json::from_str::<Value>(line) {
+        Ok(message) => handle_message(&message),
+        Err(e) => Some(error_response(Value::Null, PARSE_ERROR, e.to_string())),
+    }
+}
+
+fn handle_message(message: &Value) -> Option<Value> {
+    // 没有 id 的是通知（如 notifications/initialized），不需要回复
+    let id = message.get("id")?.clone();
+    let method = message["method"].as_str().unwrap_or_default();
+    let params = &message["params"];
+
+    let result = match method {
+        "initialize" => Ok(initialize(params)),
+        "ping" => Ok(json!({})),
+        "tools/list" => Ok(json!({ "tools": tool_definitions() })),
+        "tools/call" => call_tool(params),
+        "resources/list" => Ok(json!({ "resources": resource_definitions() })),
+        "resources/read" => read_resource(params),
+        _ => Err((METHOD_NOT_FOUND, format!("Method not found: {}", method))),
+    };
+
+    Some(match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err((code, message)) => error_response(id, code, message),
+    })
+}
+
+fn error_response(id: Value, code: i64, message: String) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } })
+}
+
+fn initialize(params: &Value) -> Value {
+    let requested = params["protocolVersion"].as_str().unwrap_or_default();
+    let version = PROTOCOL_VERSIONS.iter()
+        .find(|v| **v == requested)
+        .unwrap_or(&PROTOCOL_VERSIONS[0]);
+
+    json!({
+        "protocolVersion": version,
+        "capabilities": { "tools": {}, "resources": {} },
+        "serverInfo": { "name": "semidone", "version": env!("CARGO_PKG_VERSION") },
+        "instructions": "Manage the user's SemiDone to-do list. Task ids may be shortened to a unique prefix. \
+            Due dates accept today, tomorrow, +3d, weekday names (mon..sun), YYYY-MM-DD or \"YYYY-MM-DD HH:MM\" in local time.",
+    })
+}
+
+fn tool_definitions() -> Value {
+    let priority = json!({ "type": "string", "enum": ["high", "medium", "low"] });
+    let due = json!({ "type": "string", "description": "today, tomorrow, +3d, mon..sun, YYYY-MM-DD or \"YYYY-MM-DD HH:MM\"" });
+
+    json!([
+        {
+            "name": "list_tasks",
+            "description": "List tasks, pending ones by default.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "status": { "type": "string", "enum": ["pending", "completed", "all"] },
+                    "dueToday": { "type": "boolean", "description": "Only tasks due today" },
+                    "overdue": { "type": "boolean", "description": "Only overdue tasks" },
+                },
+            },
+        },
+        {
+            "name": "search_tasks",
+            "description": "Find tasks whose title or description contains the query (case-insensitive).",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "query": { "type": "string" },
+                    "includeCompleted": { "type": "boolean" },
+                },
+                "required": ["query"],
+            },
+        },
+        {
+            "name": "create_task",
+            "description": "Create a task.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "title": { "type": "string" },
+                    "description": { "type": "string" },
+                    "due": due,
+                    "priority": priority,
+                },
+                "required": ["title"],
+            },
+        },
+        {
+            "name": "update_task",
+            "description": "Change a task's title, description, due date or priority.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "id": { "type": "string" },
+                    "title": { "type": "string" },
+                    "description": { "type": "string" },
+                    "due": due,
+                    "priority": priority,
+                },
+                "required": ["id"],
+            },
+        },
+        {
+            "name": "complete_task",
+            "description": "Mark a task as completed.",
+            "inputSchema": {
+                "type": "object",
+                "properties": { "id": { "type": "string" } },
+                "required": ["id"],
+            },
+        },
+        {
+            "name": "get_stats",
+            "description": "Task counts: total, completed, pending, overdue, due today and by priority.",
+            "inputSchema": { "type": "object", "properties": {} },
+        },
+    ])
+}
+
+fn resource_definitions() -> Value {
+    json!([
+        {
+            "uri": TODAY_URI,
+            "name": "today",
+            "description": "Pending tasks due today",
+            "mimeType": "application/json",
+        },
+        {
+            "uri": OVERDUE_URI,
+            "name": "overdue",
+            "description": "Pending tasks past their due date",
+            "mimeType": "application/json",
+        },
+    ])
+}
+
+/// 把工具调用转换为等价的命令行参数；取值统一使用 --flag=value 形式，避免以 - 开头的内容被当作选项
+fn tool_args(name: &str, arguments: &Value) -> Result<Vec<String>, String> {
+    let text = |key: &str| arguments[key].as_str().map(str::to_string);
+    let flag = |key: &str| arguments[key].as_bool().unwrap_or(false);
+    let required = |key: &str| text(key).ok_or_else(|| format!("Missing required argument: {}", key));
+
+    let mut args = Vec::new();
+    let option = |args: &mut Vec<String>, long: &str, key: &str| {
+        if let Some(value) = text(key) {
+            args.push(format!("--{}={}", long, value));
+        }
+    };
+
+    match name {
+        "list_tasks" => {
+            args.push("list".to_string());
+            match arguments["status"].as_str() {
+                Some("all") => args.push("--all".to_string()),
+                Some("completed") => args.push("--completed".to_string()),
+                _ => {}
+            }
+            if flag("dueToday") {
+                args.push("--today".to_string());
+            }
+            if flag("overdue") {
+                args.push("--overdue".to_string());
+            }
+        }
+        "search_tasks" => {
+            args.push("list".to_string());
+            args.push(format!("--query={}", required("query")?));
+            if flag("includeCompleted") {
+                args.push("--all".to_string());
+            }
+        }
+        "create_task" => {
+            args.push("add".to_string());
+            option(&mut args, "description", "description");
+            option(&mut args, "due", "due");
+            option(&mut args, "priority", "priority");
+            args.push("--".to_string());
+            args.push(required("title")?);
+        }
+        "update_task" => {
+            args.push("update".to_string());
+            option(&mut args, "title", "title");
+            option(&mut args, "description", "description");
+            option(&mut args, "due", "due");
+            option(&mut args, "priority", "priority");
+            args.push("--".to_string());
+            args.push(required("id")?);
+        }
+        "complete_task" => {
+            args.extend(["done".to_string(), "--".to_string(), required("id")?]);
+        }
+        "get_stats" => args.push("stats".to_string()),
+        _ => return Err(format!("Unknown tool: {}", name)),
+    }
+    Ok(args)
+}
+
+fn call_tool(params: &Value) -> RpcResult {
+    let name = params["name"].as_str().unwrap_or_default();
+    let args = tool_args(name, &params["arguments"]).map_err(|e| (INVALID_PARAMS, e))?;
+
+    // 业务错误（如待办不存在）作为工具结果返回，让模型可以自行修正
+    let (text, is_error) = match cli::dispatch(&args) {
+        Ok(reply) if reply["success"] == Value::Bool(true) => {
+            (serde_json::to_string_pretty(&reply["data"]).unwrap_or_default(), false)
+        }
+        Ok(reply) => (reply["error"].as_str().unwrap_or("Unknown error").to_string(), true),
+        Err(e) => (e, true),
+    };
+
+    Ok(json!({
+        "content": [{ "type": "text", "text": text }],
+        "isError": is_error,
+    }))
+}
+
+fn read_resource(params: &Value) -> RpcResult {
+    let uri = params["uri"].as_str().unwrap_or_default();
+    let args = match uri {
+        TODAY_URI => vec!["list".to_string(), "--today".to_string()],
+        OVERDUE_URI => vec!["list".to_string(), "--overdue".to_string()],
+        _ => return Err((INVALID_PARAMS, format!("Unknown resource: {}", uri))),
+    };
+
+    let reply = cli::dispatch(&args).map_err(|e| (INTERNAL_ERROR, e))?;
+    if reply["success"] != Value::Bool(true) {
+        return Err((INTERNAL_ERROR, reply["error"].as_str().unwrap_or("Unknown error").to_string()));
+    }
+
+    Ok(json!({
+        "contents": [{
+            "uri": uri,
+            "mimeType": "application/json",
+            "text": serde_json::to_string_pretty(&reply["data"]).unwrap_or_default(),
+        }],
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::Cli;
+
+    fn request(method: &str, params: Value) -> Value {
+        handle_message(&json!({ "jsonrpc": "2.0", "id": 7, "method": method, "params": params })).unwrap()
+    }
+
+    fn error_code(reply: &Value) -> i64 {
+        assert_eq!(reply["id"], 7);
+        reply["error"]["code"].as_i64().unwrap()
+    }
+
+    fn args(name: &str, arguments: Value) -> Vec<String> {
+        let args = tool_args(name, &arguments).unwrap();
+        // 生成的参数必须能被命令行解析
+        Cli::parse_args(&args).unwrap();
+        args
+    }
+
+    #[test]
+    fn initialize_negotiates_protocol_version() {
+        let reply = request("initialize", json!({ "protocolVersion": "2025-03-26" }));
+        assert_eq!(reply["result"]["protocolVersion"], "2025-03-26");
+        assert_eq!(reply["result"]["serverInfo"]["name"], "semidone");
+
+        let reply = request("initialize", json!({ "protocolVersion": "1999-01-01" }));
+        assert_eq!(reply["result"]["protocolVersion"], PROTOCOL_VERSIONS[0]);
+        let reply = request("initialize", Value::Null);
+        assert_eq!(reply["result"]["protocolVersion"], PROTOCOL_VERSIONS[0]);
+    }
+
+    #[test]
+    fn lists_tools_and_resources() {
+        let tools = request("tools/list", Value::Null);
+        let names: Vec<&str> = tools["result"]["tools"].as_array().unwrap().iter().map(|t| t["name"].as_str().unwrap()).collect();
+        assert_eq!(names, ["list_tasks", "search_tasks", "create_task", "update_task", "complete_task", "get_stats"]);
+
+        let resources = request("resources/list", Value::Null);
+        assert_eq!(resources["result"]["resources"][0]["uri"], TODAY_URI);
+        assert_eq!(request("ping", Value::Null)["result"], json!({}));
+    }
+
+    #[test]
+    fn notifications_and_malformed_messages() {
+        assert_eq!(handle_line(r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#), None);
+
+        let reply = handle_line("{not json").unwrap();
+        assert_eq!(reply["id"], Value::Null);
+        assert_eq!(reply["error"]["code"], PARSE_ERROR);
+
+        assert_eq!(error_code(&request("tasks/delete", Value::Null)), METHOD_NOT_FOUND);
+        assert_eq!(error_code(&handle_message(&json!({ "id": 7 })).unwrap()), METHOD_NOT_FOUND);
+    }
+
+    #[test]
+    fn rejects_invalid_tool_calls_before_running_them() {
+        for params in [
+            Value::Null,
+            json!({ "name": "drop_tasks", "arguments": {} }),
+            json!({ "name": "create_task" }),
+            json!({ "name": "create_task", "arguments": { "title": 5 } }),
+            json!({ "name": "search_tasks", "arguments": {} }),
+            json!({ "name": "update_task", "arguments": { "title": "x" } }),
+            json!({ "name": "complete_task", "arguments": { "id": null } }),
+        ] {
+            let reply = request("tools/call", params.clone());
+            assert_eq!(error_code(&reply), INVALID_PARAMS, "{}", params);
+        }
+        assert_eq!(
+            request("tools/call", json!({ "name": "create_task", "arguments": {} }))["error"]["message"],
+            "Missing required argument: title"
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_resource() {
+        let reply = request("resources/read", json!({ "uri": "semidone://tasks/all" }));
+        assert_eq!(error_code(&reply), INVALID_PARAMS);
+        assert_eq!(reply["error"]["message"], "Unknown resource: semidone://tasks/all");
+        assert_eq!(error_code(&request("resources/read", Value::Null)), INVALID_PARAMS);
+    }
+
+    #[test]
+    fn translates_tool_calls_to_cli_args() {
+        assert_eq!(args("list_tasks", json!({})), ["list"]);
+        assert_eq!(args("list_tasks", json!({ "status": "all", "dueToday": true, "overdue": true })), ["list", "--all", "--today", "--overdue"]);
+        assert_eq!(args("list_tasks", json!({ "status": "completed", "dueToday": "yes" })), ["list", "--completed"]);
+        assert_eq!(args("search_tasks", json!({ "query": "milk", "includeCompleted": true })), ["list", "--query=milk", "--all"]);
+        assert_eq!(args("complete_task", json!({ "id": "1a2b" })), ["done", "--", "1a2b"]);
+        assert_eq!(args("get_stats", Value::Null), ["stats"]);
+        assert_eq!(
+            args("create_task", json!({ "title": "Buy milk", "due": "tomorrow", "priority": "high", "description": "2 liters" })),
+            ["add", "--description=2 liters", "--due=tomorrow", "--priority=high", "--", "Buy milk"]
+        );
+    }
+
+    #[test]
+    fn values_starting_with_dash_stay_values() {
+        let create = args("create_task", json!({ "title": "-5 push-ups", "description": "--help" }));
+        assert_eq!(create, ["add", "--description=--help", "--", "-5 push-ups"]);
+        let parsed = format!("{:?}", Cli::parse_args(&create).unwrap());
+        assert!(parsed.contains(r#"title: "-5 push-ups""#) && parsed.contains(r#"description: Some("--help")"#), "{}", parsed);
+
+        let update = args("update_task", json!({ "id": "--version", "title": "-x", "due": "-1d" }));
+        assert_eq!(update, ["update", "--title=-x", "--due=-1d", "--", "--version"]);
+        let parsed = format!("{:?}", Cli::parse_args(&update).unwrap());
+        assert!(parsed.contains(r#"id: "--version""#) && parsed.contains(r#"title: Some("-x")"#), "{}", parsed);
+
+        let search = args("search_tasks", json!({ "query": "-h" }));
+        assert!(format!("{:?}", Cli::parse_args(&search).unwrap()).contains(r#"query: Some("-h")"#));
+    }
+}
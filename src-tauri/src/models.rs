@@ -1,80 +1,150 @@
 use serde::{Deserialize, Serialize};
-use chrono::Utc;
+use chrono::{DateTime, Utc};
+use ts_rs::TS;
+use crate::error::{AppError, ErrorCode};
+use crate::i18n::{Locale, Msg};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+// 所有模型均以 camelCase 与前端交互，并通过 ts-rs 生成 src/types/bindings 下的 TypeScript 类型
+// ts-rs 启用 serde 兼容，直接沿用 #[serde(...)] 的重命名规则，#[ts(...)] 只描述类型上的差异
+// 旧版本以 snake_case 写入的数据文件通过 alias 继续兼容读取
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
 pub struct Attachment {
     pub id: String,
     pub name: String,
+    #[ts(type = "number")]
     pub size: u64,
     #[serde(rename = "type")]
     pub file_type: String,
     pub data: String,
+    #[serde(alias = "created_at")]
     pub created_at: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "lowercase")]
+#[ts(export)]
 pub enum Priority {
+    #[serde(alias = "High")]
     High,
+    #[serde(alias = "Medium")]
     Medium,
+    #[serde(alias = "Low")]
     Low,
 }
 
 #[allow(dead_code)]
 impl Priority {
-    pub fn to_string(&self) -> String {
+    pub fn as_str(&self) -> &'static str {
         match self {
-            Priority::High => "high".to_string(),
-            Priority::Medium => "medium".to_string(),
-            Priority::Low => "low".to_string(),
+            Priority::High => "high",
+            Priority::Medium => "medium",
+            Priority::Low => "low",
         }
     }
 
     pub fn from_string(s: &str) -> Self {
-        match s {
-            "high" => Priority::High,
-            "medium" => Priority::Medium,
-            "low" => Priority::Low,
-            _ => Priority::Medium,
+        Self::parse(s).unwrap_or(Priority::Medium)
+    }
+
+    /// 严格解析（不区分大小写），无法识别时返回 None，用于命令行等外部输入
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "high" => Some(Priority::High),
+            "medium" => Some(Priority::Medium),
+            "low" => Some(Priority::Low),
+            _ => None,
         }
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "lowercase")]
+#[ts(export)]
 pub enum Theme {
+    #[default]
+    #[serde(alias = "Light")]
     Light,
+    #[serde(alias = "Dark")]
+    Dark,
+    #[serde(alias = "Pink")]
     Pink,
 }
 
 #[allow(dead_code)]
 impl Theme {
-    pub fn to_string(&self) -> String {
+    pub fn as_str(&self) -> &'static str {
         match self {
-            Theme::Light => "light".to_string(),
-            Theme::Pink => "pink".to_string(),
+            Theme::Light => "light",
+            Theme::Dark => "dark",
+            Theme::Pink => "pink",
         }
     }
 
     pub fn from_string(s: &str) -> Self {
-        match s {
+        match s.to_ascii_lowercase().as_str() {
             "light" => Theme::Light,
+            "dark" => Theme::Dark,
             "pink" => Theme::Pink,
             _ => Theme::Light,
         }
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+// 折叠模式：展开 / 条状 / 悬浮球
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "lowercase")]
+#[ts(export)]
+pub enum CollapseMode {
+    #[default]
+    Expanded,
+    Bar,
+    Floating,
+}
+
+// 窗口吸附的屏幕边缘
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "lowercase")]
+#[ts(export)]
+pub enum EdgePosition {
+    Left,
+    #[default]
+    Right,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
 pub struct Task {
     pub id: String,
     pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
     pub description: Option<String>,
     pub completed: bool,
     pub priority: Priority,
+    #[serde(default, alias = "due_date", skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
     pub due_date: Option<String>,
+    #[serde(alias = "created_at")]
     pub created_at: String,
+    #[serde(alias = "updated_at")]
     pub updated_at: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, alias = "completed_at", skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub completed_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
     pub attachments: Option<Vec<Attachment>>,
+    // 预计耗时（分钟），与实际专注时长对比
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub estimated_minutes: Option<u32>,
+    // 在此待办上完成的番茄钟专注时长累计（分钟）
+    #[serde(default)]
+    pub focus_minutes: u32,
 }
 
 impl Task {
@@ -89,79 +159,735 @@ impl Task {
             due_date,
             created_at: now.clone(),
             updated_at: now,
+            completed_at: None,
             attachments,
+            estimated_minutes: None,
+            focus_minutes: 0,
         }
     }
 
     pub fn update(&mut self) {
         self.updated_at = Utc::now().to_rfc3339();
     }
+
+    /// 切换完成状态，同时记录或清除完成时间
+    pub fn set_completed(&mut self, completed: bool) {
+        if completed && !self.completed {
+            self.completed_at = Some(Utc::now().to_rfc3339());
+        } else if !completed {
+            self.completed_at = None;
+        }
+        self.completed = completed;
+    }
+
+    /// 完成时间；旧数据没有记录完成时间时以最后更新时间代替
+    pub fn completion_time(&self) -> Option<&str> {
+        if !self.completed {
+            return None;
+        }
+        Some(self.completed_at.as_deref().unwrap_or(&self.updated_at))
+    }
+
+    /// 标题或描述包含关键字（不区分大小写）
+    pub fn matches_query(&self, query: &str) -> bool {
+        let query = query.trim().to_lowercase();
+        self.title.to_lowercase().contains(&query)
+            || self.description.as_deref().is_some_and(|d| d.to_lowercase().contains(&query))
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+// settings.json 的结构版本，变更字段时递增并在 Storage 中补充迁移逻辑
+pub const SETTINGS_VERSION: u32 = 2;
+
+// 本地 HTTP 接口的默认端口
+pub const DEFAULT_API_PORT: u16 = 27485;
+
+// 快速添加窗口的默认全局快捷键
+pub const DEFAULT_QUICK_ADD_SHORTCUT: &str = "CommandOrControl+Shift+Space";
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase", default)]
+#[ts(export)]
 pub struct Settings {
-    pub theme: String,
+    #[ts(skip)]
+    pub version: u32,
+    pub theme: Theme,
     pub notifications: bool,
-    #[serde(rename = "autoSave")]
     pub auto_save: bool,
-    #[serde(rename = "isPinned")]
     pub is_pinned: bool,
-    #[serde(rename = "isCollapsed")]
     pub is_collapsed: bool,
+    pub collapse_mode: CollapseMode,
+    pub use_capsule_mode: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
     pub username: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
     pub avatar: Option<String>,
+    pub transparent_enabled: bool,
+    // 透明度级别 0-100（100 为完全不透明）
+    pub transparent_level: u8,
+    pub is_edge_snapped: bool,
+    pub edge_position: EdgePosition,
+    // 后端文案（托盘菜单、错误信息、通知）使用的语言
+    pub locale: Locale,
+    // 本地 HTTP 接口（仅监听 127.0.0.1），默认关闭
+    pub api_enabled: bool,
+    pub api_port: u16,
+    // 唤出快速添加窗口的全局快捷键，为空表示不注册
+    pub quick_add_shortcut: String,
+    // 开机启动（以 --minimized 启动，只显示托盘图标）
+    pub launch_at_login: bool,
+    // 当前版本不认识的字段原样保留，避免降级或新版前端写入的设置丢失
+    #[serde(flatten)]
+    #[ts(skip)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl Settings {
+    /// 校正超出范围的取值
+    pub fn normalize(&mut self) {
+        self.transparent_level = self.transparent_level.min(100);
+    }
+
+    /// 将旧设置中本版本不认识、而新设置没有提供的字段合并进来
+    pub fn merge_unknown_from(&mut self, previous: &Settings) {
+        for (key, value) in &previous.extra {
+            self.extra.entry(key.clone()).or_insert_with(|| value.clone());
+        }
+    }
 }
 
 impl Default for Settings {
     fn default() -> Self {
         Self {
-            theme: "light".to_string(),
+            version: SETTINGS_VERSION,
+            theme: Theme::Light,
             notifications: true,
             auto_save: true,
             is_pinned: false,
             is_collapsed: false,
+            collapse_mode: CollapseMode::Expanded,
+            use_capsule_mode: false,
             username: None,
             avatar: None,
+            transparent_enabled: false,
+            transparent_level: 100,
+            is_edge_snapped: false,
+            edge_position: EdgePosition::Right,
+            locale: Locale::ZhCn,
+            api_enabled: false,
+            api_port: DEFAULT_API_PORT,
+            quick_add_shortcut: DEFAULT_QUICK_ADD_SHORTCUT.to_string(),
+            launch_at_login: false,
+            extra: serde_json::Map::new(),
         }
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export, optional_fields)]
 pub struct CreateTaskRequest {
     pub title: String,
     pub description: Option<String>,
-    pub priority: Option<String>,
+    pub priority: Option<Priority>,
+    #[serde(alias = "due_date")]
     pub due_date: Option<String>,
     pub attachments: Option<Vec<Attachment>>,
+    pub estimated_minutes: Option<u32>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export, optional_fields)]
 pub struct UpdateTaskRequest {
     pub title: Option<String>,
     pub description: Option<String>,
     pub completed: Option<bool>,
-    pub priority: Option<String>,
+    pub priority: Option<Priority>,
+    #[serde(alias = "due_date")]
     pub due_date: Option<String>,
     pub attachments: Option<Vec<Attachment>>,
+    // 为 0 时清除预计耗时
+    pub estimated_minutes: Option<u32>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
 pub struct TaskStats {
     pub total: usize,
     pub completed: usize,
     pub pending: usize,
+    pub overdue: usize,
+    pub today: usize,
     pub high_priority: usize,
     pub medium_priority: usize,
     pub low_priority: usize,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct TaskTrendPoint {
+    pub date: String,
+    pub created: usize,
+    pub completed: usize,
+}
+
+/// 热力图中的一天，level 为 0-4 的颜色深浅，按区间内完成最多的一天划分
+#[derive(Debug, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct HeatmapDay {
+    pub date: String,
+    pub completed: usize,
+    pub level: u8,
+}
+
+/// 某一优先级待办从创建到完成的平均用时
+#[derive(Debug, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct PriorityCompletionTime {
+    pub priority: Priority,
+    pub completed: usize,
+    // 没有完成的待办时为 None
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub average_hours: Option<f64>,
+}
+
+/// 一段时间内的完成情况分析，均按完成时间的本地日期、星期与小时统计
+#[derive(Debug, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct ProductivityStats {
+    pub start_date: String,
+    pub end_date: String,
+    pub total_completed: usize,
+    pub heatmap: Vec<HeatmapDay>,
+    // 截至区间最后一天连续有完成待办的天数；最后一天是今天且尚未完成时从昨天算起
+    pub current_streak: u32,
+    pub longest_streak: u32,
+    pub average_completion: Vec<PriorityCompletionTime>,
+    // 按星期（0 为周一）与小时（0-23）统计的完成数量
+    pub by_weekday: Vec<usize>,
+    pub by_hour: Vec<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub most_productive_weekday: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub most_productive_hour: Option<u8>,
+}
+
+/// 主窗口处于使用中的一段时间，保存在数据目录的 usage.json
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageSession {
+    pub id: String,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+/// 使用时长汇总，单位均为分钟
+#[derive(Debug, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct UsageStats {
+    pub today: u32,
+    pub this_week: u32,
+    pub this_month: u32,
+    // 最近 30 天中有使用记录的日子的平均值
+    pub average_daily: u32,
+    pub total_sessions: usize,
+    pub longest_session: u32,
+}
+
+/// 某一天的使用情况，跨零点的使用时段分别计入两天
+#[derive(Debug, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct UsageDetail {
+    pub date: String,
+    pub total_minutes: u32,
+    pub sessions: usize,
+    // 当天首次、最后使用的时间（HH:MM）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub first_use: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub last_use: Option<String>,
+}
+
+/// 番茄钟阶段
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub enum PomodoroMode {
+    #[default]
+    Work,
+    Break,
+    LongBreak,
+}
+
+/// 番茄钟各阶段时长（分钟）与长休息间隔
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct PomodoroSettings {
+    pub work_duration: u32,
+    pub break_duration: u32,
+    pub long_break_duration: u32,
+    pub cycles_before_long_break: u32,
+}
+
+impl Default for PomodoroSettings {
+    fn default() -> Self {
+        Self {
+            work_duration: 25,
+            break_duration: 5,
+            long_break_duration: 15,
+            cycles_before_long_break: 4,
+        }
+    }
+}
+
+/// 番茄钟状态，保存在数据目录的 pomodoro.json
+///
+/// 运行中以 `endsAt` 为准计算剩余时间，窗口隐藏、应用重启都不会造成偏差；暂停时以 `timeLeft` 为准
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct PomodoroState {
+    pub is_active: bool,
+    pub current_mode: PomodoroMode,
+    // 剩余秒数
+    pub time_left: u32,
+    // 已完成的专注次数
+    pub cycle: u32,
+    #[serde(flatten)]
+    pub settings: PomodoroSettings,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[ts(optional, type = "string")]
+    pub ends_at: Option<DateTime<Utc>>,
+    // 当前专注的待办
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub task_id: Option<String>,
+    // 当前专注阶段第一次开始的时间，以及期间暂停的次数
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional, type = "string")]
+    pub started_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub interruptions: u32,
+    // 当前专注阶段在最近一次暂停前累计计时的秒数，不含暂停的时间
+    #[serde(default)]
+    pub focused_seconds: u32,
+}
+
+impl Default for PomodoroState {
+    fn default() -> Self {
+        let settings = PomodoroSettings::default();
+        Self {
+            is_active: false,
+            current_mode: PomodoroMode::Work,
+            time_left: settings.work_duration * 60,
+            cycle: 0,
+            settings,
+            ends_at: None,
+            task_id: None,
+            started_at: None,
+            interruptions: 0,
+            focused_seconds: 0,
+        }
+    }
+}
+
+/// 一次完整的番茄钟专注，保存在数据目录的 focus_sessions.json
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct FocusSession {
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub task_id: Option<String>,
+    #[ts(type = "string")]
+    pub start: DateTime<Utc>,
+    #[ts(type = "string")]
+    pub end: DateTime<Utc>,
+    // 计入待办的专注分钟数，即实际计时的时间（不含暂停，四舍五入到分钟）
+    pub minutes: u32,
+    pub interruptions: u32,
+}
+
+/// 在待办上手动计时的一段时间，保存在数据目录的 time_entries.json
+///
+/// 结束时间为空表示正在计时，同一时间最多只有一条
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct TimeEntry {
+    pub id: String,
+    pub task_id: String,
+    #[ts(type = "string")]
+    pub start: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional, type = "string")]
+    pub end: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub note: Option<String>,
+}
+
+/// 事后补录一段计时
+#[derive(Debug, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct AddTimeEntryRequest {
+    pub task_id: String,
+    #[ts(type = "string")]
+    pub start: DateTime<Utc>,
+    #[ts(type = "string")]
+    pub end: DateTime<Utc>,
+    #[ts(optional)]
+    pub note: Option<String>,
+}
+
+/// 修改计时记录，未传的字段保持不变；给正在计时的记录设置结束时间即停止计时
+#[derive(Debug, Default, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export, optional_fields)]
+pub struct UpdateTimeEntryRequest {
+    pub task_id: Option<String>,
+    #[ts(optional, type = "string")]
+    pub start: Option<DateTime<Utc>>,
+    #[ts(optional, type = "string")]
+    pub end: Option<DateTime<Utc>>,
+    // 为空字符串时清除备注
+    pub note: Option<String>,
+}
+
+/// 报告周期，未指定日期时取本周（周一起）或本月
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "lowercase")]
+#[ts(export)]
+pub enum ReportKind {
+    Week,
+    Month,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "lowercase")]
+#[ts(export)]
+pub enum ReportFormat {
+    Markdown,
+    Html,
+    Csv,
+}
+
+impl ReportFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            ReportFormat::Markdown => "md",
+            ReportFormat::Html => "html",
+            ReportFormat::Csv => "csv",
+        }
+    }
+}
+
+/// 导出或预览周报、月报；导出时未传保存路径则弹出保存对话框
+#[derive(Debug, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export, optional_fields)]
+pub struct ExportReportRequest {
+    pub kind: ReportKind,
+    pub format: ReportFormat,
+    // 自定义区间（YYYY-MM-DD），需同时提供
+    pub start_date: Option<String>,
+    pub end_date: Option<String>,
+    // 数据目录 report_templates 下的模板文件名，报告格式由模板扩展名决定
+    pub template: Option<String>,
+    // 预览时忽略
+    pub path: Option<String>,
+}
+
+/// 用户放在数据目录 report_templates 下的报告模板
+#[derive(Debug, Clone, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct ReportTemplate {
+    pub name: String,
+    pub format: ReportFormat,
+}
+
+/// 可导入导出的待办字段，对应 CSV 中的一列
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub enum TaskField {
+    Title,
+    Description,
+    Completed,
+    Priority,
+    DueDate,
+    CreatedAt,
+    CompletedAt,
+    EstimatedMinutes,
+    FocusMinutes,
+}
+
+impl TaskField {
+    pub const ALL: [TaskField; 9] = [
+        TaskField::Title,
+        TaskField::Description,
+        TaskField::Completed,
+        TaskField::Priority,
+        TaskField::DueDate,
+        TaskField::CreatedAt,
+        TaskField::CompletedAt,
+        TaskField::EstimatedMinutes,
+        TaskField::FocusMinutes,
+    ];
+}
+
+/// 导出待办为 CSV；未传列时导出全部字段，未传保存路径则弹出保存对话框
+#[derive(Debug, Default, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export, optional_fields)]
+pub struct ExportTasksCsvRequest {
+    pub columns: Option<Vec<TaskField>>,
+    pub path: Option<String>,
+}
+
+/// CSV 的表头与前几行，以及按表头推测的各列对应字段（无法对应时为 null）
+#[derive(Debug, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct CsvInspection {
+    pub headers: Vec<String>,
+    pub sample_rows: Vec<Vec<String>>,
+    pub total_rows: usize,
+    pub suggested_mapping: Vec<Option<TaskField>>,
+}
+
+/// 按列映射导入 CSV，mapping 与表头逐列对应，null 表示忽略该列
+#[derive(Debug, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct ImportTasksCsvRequest {
+    pub content: String,
+    pub mapping: Vec<Option<TaskField>>,
+    // 只解析并返回结果，不保存
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// 导入时无法识别的一行，row 为文件中的行号（CSV 表头为第 1 行）；JSON 中为第几个待办
+#[derive(Debug, Clone, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct ImportRowError {
+    pub row: usize,
+    pub message: String,
+}
+
+/// 导入结果：成功解析的待办与跳过的行
+#[derive(Debug, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct TaskImportResult {
+    pub tasks: Vec<Task>,
+    pub errors: Vec<ImportRowError>,
+}
+
+/// 可导入的其他待办应用导出格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub enum ImportSource {
+    // Microsoft Graph 格式的 JSON（To Do 导出工具均使用此格式）
+    MicrosoftTodo,
+    // 滴答清单 / TickTick 的 CSV 备份
+    TickTick,
+    // Todoist 项目导出的 CSV 模板
+    Todoist,
+    TodoTxt,
+}
+
+/// 从其他应用的导出文件导入待办；dry_run 时只返回解析结果供预览
+#[derive(Debug, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct ImportFromAppRequest {
+    pub source: ImportSource,
+    pub content: String,
+    #[serde(default)]
+    pub skip_completed: bool,
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// 待办清单的文本格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub enum TaskListFormat {
+    // GitHub 风格的 Markdown 任务列表
+    Markdown,
+    TodoTxt,
+}
+
+impl TaskListFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            TaskListFormat::Markdown => "md",
+            TaskListFormat::TodoTxt => "txt",
+        }
+    }
+}
+
+/// 待办清单的分组方式
+///
+/// SemiDone 的待办没有所属清单，也没有子任务，因此只能按优先级或截止日期分组，
+/// 无法按清单分组或把子任务缩进在父任务下
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub enum TaskListGroup {
+    #[default]
+    None,
+    Priority,
+    DueDate,
+}
+
+/// 生成 Markdown 或 todo.txt 格式的待办清单；导出时未传保存路径则弹出保存对话框
+#[derive(Debug, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export, optional_fields)]
+pub struct TaskListRequest {
+    pub format: TaskListFormat,
+    pub group_by: Option<TaskListGroup>,
+    // 默认包含已完成的待办
+    pub include_completed: Option<bool>,
+    pub path: Option<String>,
+}
+
+/// 导入数据：兼容桌面端导出的待办数组和浏览器端导出的 { tasks, settings } 对象
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum ImportPayload {
+    Tasks(Vec<Task>),
+    Backup {
+        tasks: Vec<Task>,
+        #[serde(default)]
+        settings: Option<Settings>,
+    },
+}
+
+/// 本地 HTTP 接口的运行状态
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct ApiStatus {
+    pub running: bool,
+    pub url: String,
+    pub token: String,
+    // 最近一次启动失败的原因，如端口被占用
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub error: Option<String>,
+}
+
+/// 全局快捷键的注册状态
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct ShortcutStatus {
+    pub shortcut: String,
+    pub registered: bool,
+    // 注册失败的原因，如已被其他程序占用
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub error: Option<String>,
+}
+
+/// 参与文件夹同步的其他设备
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct SyncDevice {
+    pub id: String,
+    pub name: String,
+    // 已合并的变更条数
+    pub changes: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub last_change_at: Option<String>,
+}
+
+/// 两台设备在互不知情时修改了同一待办的同一字段，按最后写入的保留，另一个值记录在这里
+///
+/// field 为前端使用的字段名，`_deleted` 表示一台设备删除了待办而另一台仍在修改；
+/// 两个值均为 JSON 文本
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct SyncConflict {
+    pub id: String,
+    pub task_id: String,
+    pub task_title: String,
+    pub field: String,
+    pub kept_value: String,
+    pub kept_device: String,
+    pub kept_at: String,
+    pub other_value: String,
+    pub other_device: String,
+    pub other_at: String,
+}
+
+/// 文件夹同步的状态，未启用时 folder 为空
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct SyncStatus {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub folder: Option<String>,
+    pub device_id: String,
+    pub device_name: String,
+    pub devices: Vec<SyncDevice>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub last_sync_at: Option<String>,
+    pub conflicts: usize,
+}
+
+/// 设置同步文件夹与本机名称；folder 为空时关闭同步，device_name 为空时保持不变
+#[derive(Debug, Default, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export, optional_fields)]
+pub struct ConfigureSyncRequest {
+    pub folder: Option<String>,
+    pub device_name: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, TS)]
+#[ts(export)]
 pub struct ApiResponse<T> {
     pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[ts(optional = nullable)]
     pub data: Option<T>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
     pub error: Option<String>,
+    // 失败时的错误码，供前端区分错误类型
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub code: Option<ErrorCode>,
 }
 
 impl<T> ApiResponse<T> {
@@ -170,14 +896,17 @@ impl<T> ApiResponse<T> {
             success: true,
             data: Some(data),
             error: None,
+            code: None,
         }
     }
 
-    pub fn error(message: String) -> Self {
+    /// 失败响应，错误信息以操作描述开头，如“加载待办失败: …”
+    pub fn error(action: Msg, err: AppError) -> Self {
         Self {
             success: false,
             data: None,
-            error: Some(message),
+            error: Some(format!("{}: {}", action.text(), err)),
+            code: Some(err.code()),
         }
     }
-}
\ No newline at end of file
+}
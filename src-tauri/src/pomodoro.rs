@@ -0,0 +1,411 @@
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration as StdDuration;
+use chrono::{DateTime, Duration, Utc};
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_notification::NotificationExt;
+use uuid::Uuid;
+use crate::commands::notify_tasks_changed;
+use crate::error::{AppError, AppResult};
+use crate::i18n::{fill, Msg};
+use crate::models::{FocusSession, PomodoroMode, PomodoroSettings, PomodoroState};
+use crate::storage::Storage;
+use crate::tray;
+
+// 开始、暂停、切换阶段等状态变化时通知前端，附带最新状态
+pub const POMODORO_CHANGED_EVENT: &str = "pomodoro-changed";
+
+// 单个阶段的最长分钟数与长休息间隔上限
+const MAX_DURATION_MINUTES: u32 = 180;
+const MAX_CYCLES_BEFORE_LONG_BREAK: u32 = 12;
+
+// 检查阶段是否结束的间隔
+const TICK_MILLIS: u64 = 500;
+
+/// 托管在应用状态中的番茄钟，计时以系统时间为准，由后台线程切换阶段
+pub struct PomodoroEngine {
+    state: Mutex<PomodoroState>,
+}
+
+impl PomodoroEngine {
+    /// 恢复上次保存的状态，读取失败时从默认设置开始
+    ///
+    /// 手动修改过的 pomodoro.json 中超出范围的时长与长休息间隔按上下限修正，避免计时线程出错
+    pub fn load(storage: &Storage) -> Self {
+        let mut state = storage.load_pomodoro().unwrap_or_else(|e| {
+            log::warn!("Failed to load pomodoro state: {}", e);
+            PomodoroState::default()
+        });
+        clamp_settings(&mut state.settings);
+        Self { state: Mutex::new(state) }
+    }
+}
+
+fn clamp_settings(settings: &mut PomodoroSettings) {
+    for minutes in [&mut settings.work_duration, &mut settings.break_duration, &mut settings.long_break_duration] {
+        *minutes = (*minutes).clamp(1, MAX_DURATION_MINUTES);
+    }
+    settings.cycles_before_long_break = settings.cycles_before_long_break.clamp(1, MAX_CYCLES_BEFORE_LONG_BREAK);
+}
+
+fn phase_seconds(settings: &PomodoroSettings, mode: PomodoroMode) -> u32 {
+    let minutes = match mode {
+        PomodoroMode::Work => settings.work_duration,
+        PomodoroMode::Break => settings.break_duration,
+        PomodoroMode::LongBreak => settings.long_break_duration,
+    };
+    minutes * 60
+}
+
+// 不足一秒按一秒计，避免还在运行时就显示 00:00
+fn seconds_until(ends_at: DateTime<Utc>, now: DateTime<Utc>) -> u32 {
+    let millis = (ends_at - now).num_milliseconds().max(0);
+    ((millis + 999) / 1000) as u32
+}
+
+/// 运行中按结束时间换算剩余秒数
+fn snapshot(state: &PomodoroState) -> PomodoroState {
+    let mut snapshot = state.clone();
+    if let Some(ends_at) = state.ends_at {
+        snapshot.time_left = seconds_until(ends_at, Utc::now());
+    }
+    snapshot
+}
+
+/// 停止计时，把这段计时的时间计入当前阶段；计时到 `ends_at` 为止，之后的时间不计
+fn stop_running(state: &mut PomodoroState, now: DateTime<Utc>) {
+    if let Some(ends_at) = state.ends_at.take() {
+        let time_left = seconds_until(ends_at, now.min(ends_at));
+        state.focused_seconds += state.time_left.saturating_sub(time_left);
+        state.time_left = time_left;
+    }
+    state.is_active = false;
+}
+
+/// 开始或继续计时，专注阶段第一次开始时记下开始时间
+fn start_running(state: &mut PomodoroState, now: DateTime<Utc>) {
+    if !state.is_active {
+        state.is_active = true;
+        state.ends_at = Some(now + Duration::seconds(i64::from(state.time_left)));
+        if state.current_mode == PomodoroMode::Work && state.started_at.is_none() {
+            state.started_at = Some(now);
+        }
+    }
+}
+
+/// 暂停计时，专注阶段中的暂停记为一次中断
+fn pause_running(state: &mut PomodoroState, now: DateTime<Utc>) {
+    if state.is_active && state.current_mode == PomodoroMode::Work {
+        state.interruptions += 1;
+    }
+    stop_running(state, now);
+}
+
+/// 进入下一阶段，返回通知文案；运行中的番茄钟从 `now` 起开始新阶段的计时
+///
+/// 专注阶段到时结束（而不是被跳过）时同时返回这次专注的记录，结束时间为阶段的结束时间，
+/// 时长只计实际计时的时间，暂停期间以及应用关闭后超出阶段结束时间的部分都不计入
+fn advance(state: &mut PomodoroState, finished: bool, now: DateTime<Utc>) -> (String, Option<FocusSession>) {
+    let settings = state.settings;
+    let running = state.is_active;
+    let end = state.ends_at.map_or(now, |ends_at| now.min(ends_at));
+    stop_running(state, now);
+    state.is_active = running;
+
+    let mut session = None;
+    let (mode, message) = match state.current_mode {
+        PomodoroMode::Work => {
+            if finished {
+                session = state.started_at.map(|start| FocusSession {
+                    id: Uuid::new_v4().to_string(),
+                    task_id: state.task_id.clone(),
+                    start,
+                    end,
+                    minutes: (state.focused_seconds + 30) / 60,
+                    interruptions: state.interruptions,
+                });
+            }
+            state.cycle += 1;
+            if state.cycle % settings.cycles_before_long_break == 0 {
+                (PomodoroMode::LongBreak, fill(Msg::PomodoroLongBreak.text(), &[&state.cycle, &settings.long_break_duration]))
+            } else {
+                (PomodoroMode::Break, fill(Msg::PomodoroWorkDone.text(), &[&settings.break_duration]))
+            }
+        }
+        PomodoroMode::Break | PomodoroMode::LongBreak => {
+            (PomodoroMode::Work, fill(Msg::PomodoroBreakDone.text(), &[&settings.work_duration]))
+        }
+    };
+
+    state.current_mode = mode;
+    state.time_left = phase_seconds(&settings, mode);
+    state.started_at = None;
+    state.interruptions = 0;
+    state.focused_seconds = 0;
+    if state.is_active {
+        state.ends_at = Some(now + Duration::seconds(i64::from(state.time_left)));
+        if mode == PomodoroMode::Work {
+            state.started_at = Some(now);
+        }
+    }
+    (message, session)
+}
+
+/// 修改状态后保存到磁盘，再通知前端并同步托盘
+fn update<F>(app: &AppHandle, change: F) -> AppResult<PomodoroState>
+where
+    F: FnOnce(&mut PomodoroState) -> AppResult<()>,
+{
+    let engine = app.state::<PomodoroEngine>();
+    let updated = {
+        let mut state = engine.state.lock()?;
+        change(&mut state)?;
+        let storage = app.state::<Mutex<Storage>>();
+        storage.lock()?.save_pomodoro(&state)?;
+        snapshot(&state)
+    };
+
+    if let Err(e) = app.emit(POMODORO_CHANGED_EVENT, &updated) {
+        log::warn!("Failed to emit {}: {}", POMODORO_CHANGED_EVENT, e);
+    }
+    tray::set_pomodoro_running(app, updated.is_active);
+    Ok(updated)
+}
+
+pub fn current(app: &AppHandle) -> AppResult<PomodoroState> {
+    let engine = app.state::<PomodoroEngine>();
+    let state = engine.state.lock()?;
+    Ok(snapshot(&state))
+}
+
+fn ensure_task_exists(app: &AppHandle, task_id: &str) -> AppResult<()> {
+    let storage = app.state::<Mutex<Storage>>();
+    let tasks = storage.lock()?.load_tasks()?;
+    if tasks.iter().any(|t| t.id == task_id) {
+        Ok(())
+    } else {
+        Err(AppError::not_found(Msg::EntityTask, task_id))
+    }
+}
+
+/// 开始或继续计时；传入待办 id 时之后完成的专注计入该待办
+pub fn start(app: &AppHandle, task_id: Option<String>) -> AppResult<PomodoroState> {
+    if let Some(id) = &task_id {
+        ensure_task_exists(app, id)?;
+    }
+
+    update(app, |state| {
+        if task_id.is_some() {
+            state.task_id = task_id;
+        }
+        start_running(state, Utc::now());
+        Ok(())
+    })
+}
+
+pub fn pause(app: &AppHandle) -> AppResult<PomodoroState> {
+    update(app, |state| {
+        pause_running(state, Utc::now());
+        Ok(())
+    })
+}
+
+/// 更换或清除专注的待办，不影响计时
+pub fn set_task(app: &AppHandle, task_id: Option<String>) -> AppResult<PomodoroState> {
+    if let Some(id) = &task_id {
+        ensure_task_exists(app, id)?;
+    }
+    update(app, |state| {
+        state.task_id = task_id;
+        Ok(())
+    })
+}
+
+/// 托盘菜单使用：运行中则暂停，否则开始
+pub fn toggle(app: &AppHandle) -> AppResult<PomodoroState> {
+    if current(app)?.is_active {
+        pause(app)
+    } else {
+        start(app, None)
+    }
+}
+
+/// 停止计时并回到第一个专注阶段，保留时长设置；进行中的专注不会被记录
+pub fn reset(app: &AppHandle) -> AppResult<PomodoroState> {
+    update(app, |state| {
+        let settings = state.settings;
+        *state = PomodoroState {
+            time_left: phase_seconds(&settings, PomodoroMode::Work),
+            settings,
+            ..PomodoroState::default()
+        };
+        Ok(())
+    })
+}
+
+/// 立即结束当前阶段，运行状态保持不变；跳过的专注不会被记录
+pub fn skip(app: &AppHandle) -> AppResult<PomodoroState> {
+    update(app, |state| {
+        advance(state, false, Utc::now());
+        Ok(())
+    })
+}
+
+/// 修改时长设置；未在运行时按新时长重置当前阶段的剩余时间
+pub fn update_settings(app: &AppHandle, settings: PomodoroSettings) -> AppResult<PomodoroState> {
+    let durations = [settings.work_duration, settings.break_duration, settings.long_break_duration];
+    if durations.iter().any(|d| !(1..=MAX_DURATION_MINUTES).contains(d))
+        || !(1..=MAX_CYCLES_BEFORE_LONG_BREAK).contains(&settings.cycles_before_long_break)
+    {
+        return Err(AppError::invalid_input(fill(
+            Msg::InvalidPomodoroSettings.text(),
+            &[&MAX_DURATION_MINUTES, &MAX_CYCLES_BEFORE_LONG_BREAK],
+        )));
+    }
+
+    update(app, |state| {
+        state.settings = settings;
+        if !state.is_active {
+            // 当前阶段按新时长重新开始
+            state.time_left = phase_seconds(&settings, state.current_mode);
+            state.started_at = None;
+            state.interruptions = 0;
+            state.focused_seconds = 0;
+        }
+        Ok(())
+    })
+}
+
+fn notify(app: &AppHandle, body: &str) {
+    let result = app.notification()
+        .builder()
+        .title(Msg::PomodoroTitle.text())
+        .body(body)
+        .show();
+    if let Err(e) = result {
+        log::warn!("Failed to show pomodoro notification: {}", e);
+    }
+}
+
+/// 启动后台计时线程，阶段结束时自动进入下一阶段并发送系统通知
+///
+/// 上次退出时仍在运行的番茄钟继续计时，已经到时的立即进入下一阶段
+pub fn start_timer(app: AppHandle) {
+    if let Ok(state) = current(&app) {
+        tray::set_pomodoro_running(&app, state.is_active);
+    }
+
+    thread::spawn(move || loop {
+        thread::sleep(StdDuration::from_millis(TICK_MILLIS));
+
+        let mut advanced = None;
+        if let Err(e) = update_if_due(&app, &mut advanced) {
+            log::warn!("Failed to advance pomodoro: {}", e);
+        }
+        if let Some((message, session)) = advanced {
+            log::info!("🍅 {}", message);
+            notify(&app, &message);
+            if let Some(session) = session {
+                record_session(&app, &session);
+            }
+        }
+    });
+}
+
+fn update_if_due(app: &AppHandle, advanced: &mut Option<(String, Option<FocusSession>)>) -> AppResult<()> {
+    let due = current(app)?.ends_at.is_some_and(|ends_at| ends_at <= Utc::now());
+    if due {
+        update(app, |state| {
+            // 加锁后再确认一次，期间可能已被暂停或跳过
+            let now = Utc::now();
+            if state.ends_at.is_some_and(|ends_at| ends_at <= now) {
+                *advanced = Some(advance(state, true, now));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(())
+}
+
+fn record_session(app: &AppHandle, session: &FocusSession) {
+    let storage = app.state::<Mutex<Storage>>();
+    let result = storage.lock()
+        .map_err(AppError::from)
+        .and_then(|storage| storage.record_focus_session(session));
+    match result {
+        // 待办的专注时长已变化
+        Ok(()) if session.task_id.is_some() => notify_tasks_changed(app),
+        Ok(()) => {}
+        Err(e) => log::warn!("Failed to save focus session: {}", e),
+    }
+}
+
+/// 已完成的专注记录，可按待办筛选，最新的排在最前
+pub fn sessions(storage: &Storage, task_id: Option<&str>) -> AppResult<Vec<FocusSession>> {
+    let mut sessions = storage.load_focus_sessions()?;
+    if let Some(task_id) = task_id {
+        sessions.retain(|s| s.task_id.as_deref() == Some(task_id));
+    }
+    sessions.reverse();
+    Ok(sessions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(minutes: i64) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2025-01-01T09:00:00Z").unwrap().with_timezone(&Utc) + Duration::minutes(minutes)
+    }
+
+    #[test]
+    fn finished_focus_records_configured_length() {
+        let mut state = PomodoroState::default();
+        start_running(&mut state, at(0));
+        let (_, session) = advance(&mut state, true, at(25));
+        let session = session.unwrap();
+        assert_eq!((session.start, session.end, session.minutes), (at(0), at(25), 25));
+        assert_eq!(state.current_mode, PomodoroMode::Break);
+        assert_eq!(state.ends_at, Some(at(30)));
+        assert_eq!((state.started_at, state.focused_seconds), (None, 0));
+    }
+
+    #[test]
+    fn paused_time_is_not_counted_as_focus() {
+        let mut state = PomodoroState::default();
+        start_running(&mut state, at(0));
+        pause_running(&mut state, at(10));
+        assert_eq!((state.time_left, state.focused_seconds), (15 * 60, 10 * 60));
+
+        // 一小时后继续，再计时 15 分钟到时
+        start_running(&mut state, at(70));
+        assert_eq!(state.started_at, Some(at(0)));
+        let (_, session) = advance(&mut state, true, at(85));
+        let session = session.unwrap();
+        assert_eq!((session.start, session.end), (at(0), at(85)));
+        assert_eq!((session.minutes, session.interruptions), (25, 1));
+    }
+
+    #[test]
+    fn downtime_after_restart_is_not_counted_as_focus() {
+        // 专注中关闭应用，五小时后重新打开，计时线程才结束这一阶段
+        let mut state = PomodoroState::default();
+        start_running(&mut state, at(0));
+        let (_, session) = advance(&mut state, true, at(300));
+        let session = session.unwrap();
+        assert_eq!((session.end, session.minutes), (at(25), 25));
+        // 下一阶段从现在开始
+        assert_eq!(state.ends_at, Some(at(305)));
+    }
+
+    #[test]
+    fn skipped_focus_is_not_recorded() {
+        let mut state = PomodoroState::default();
+        start_running(&mut state, at(0));
+        pause_running(&mut state, at(10));
+        let (_, session) = advance(&mut state, false, at(12));
+        assert!(session.is_none());
+        assert!(!state.is_active);
+        assert_eq!((state.current_mode, state.focused_seconds, state.interruptions), (PomodoroMode::Break, 0, 0));
+    }
+}
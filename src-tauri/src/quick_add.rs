@@ -0,0 +1,140 @@
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager, WebviewUrl, WebviewWindow, WebviewWindowBuilder, WindowEvent};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutState};
+use crate::error::{AppError, AppResult};
+use crate::i18n::{fill, Msg};
+use crate::models::{Settings, ShortcutStatus};
+
+pub const QUICK_ADD_WINDOW: &str = "quick-add";
+
+// 每次唤出时通知快速添加窗口清空输入并聚焦
+pub const QUICK_ADD_SHOWN_EVENT: &str = "quick-add-shown";
+
+/// 托管在应用状态中，记录当前注册的快捷键和最近一次注册失败的原因
+#[derive(Default)]
+pub struct QuickAddState {
+    registered: Mutex<Option<Shortcut>>,
+    last_error: Mutex<Option<String>>,
+}
+
+/// 按设置注册、更换或注销唤出快速添加窗口的全局快捷键
+///
+/// 快捷键被其他程序占用时返回错误并记录原因，供设置页提示
+pub fn apply_settings(app: &AppHandle, settings: &Settings) -> AppResult<()> {
+    let state = app.state::<QuickAddState>();
+    let mut registered = state.registered.lock()?;
+    *state.last_error.lock()? = None;
+
+    let result = register(app, &mut registered, settings.quick_add_shortcut.trim());
+    if let Err(e) = &result {
+        *state.last_error.lock()? = Some(e.to_string());
+    }
+    result
+}
+
+fn register(app: &AppHandle, registered: &mut Option<Shortcut>, accelerator: &str) -> AppResult<()> {
+    let wanted = if accelerator.is_empty() {
+        None
+    } else {
+        let shortcut = accelerator.parse::<Shortcut>()
+            .map_err(|_| AppError::invalid_input(fill(Msg::InvalidShortcut.text(), &[&accelerator])))?;
+        Some(shortcut)
+    };
+    if *registered == wanted {
+        return Ok(());
+    }
+
+    // 先注册新快捷键，成功后再注销旧的，新快捷键不可用时旧的仍然有效
+    let shortcuts = app.global_shortcut();
+    if let Some(shortcut) = wanted {
+        shortcuts
+            .on_shortcut(shortcut, |app, _, event| {
+                if event.state == ShortcutState::Pressed {
+                    toggle_quick_add(app);
+                }
+            })
+            .map_err(|e| AppError::invalid_input(fill(Msg::ShortcutUnavailable.text(), &[&accelerator, &e])))?;
+        log::info!("⌨️ 全局快捷键已注册: {}", accelerator);
+    }
+
+    if let Some(previous) = registered.take() {
+        if let Err(e) = shortcuts.unregister(previous) {
+            log::warn!("Failed to unregister shortcut: {}", e);
+        }
+    }
+    *registered = wanted;
+    Ok(())
+}
+
+pub fn status(app: &AppHandle, settings: &Settings) -> AppResult<ShortcutStatus> {
+    let state = app.state::<QuickAddState>();
+    let registered = state.registered.lock()?.is_some();
+    let error = state.last_error.lock()?.clone();
+    Ok(ShortcutStatus {
+        shortcut: settings.quick_add_shortcut.clone(),
+        registered,
+        error,
+    })
+}
+
+/// 快速添加窗口已在前台时收起，否则唤出
+fn toggle_quick_add(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window(QUICK_ADD_WINDOW) {
+        if window.is_visible().unwrap_or(false) && window.is_focused().unwrap_or(false) {
+            let _ = window.hide();
+            return;
+        }
+    }
+    show_quick_add(app);
+}
+
+/// 在屏幕中央显示快速添加窗口，主窗口隐藏在托盘时同样可用
+pub fn show_quick_add(app: &AppHandle) {
+    let window = match app.get_webview_window(QUICK_ADD_WINDOW) {
+        Some(window) => window,
+        None => match build_window(app) {
+            Ok(window) => window,
+            Err(e) => {
+                log::warn!("Failed to create quick add window: {}", e);
+                return;
+            }
+        },
+    };
+
+    let _ = window.center();
+    let _ = window.show();
+    let _ = window.set_focus();
+    if let Err(e) = app.emit_to(QUICK_ADD_WINDOW, QUICK_ADD_SHOWN_EVENT, ()) {
+        log::warn!("Failed to emit {}: {}", QUICK_ADD_SHOWN_EVENT, e);
+    }
+}
+
+pub fn hide_quick_add(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window(QUICK_ADD_WINDOW) {
+        let _ = window.hide();
+    }
+}
+
+// 无边框、置顶、不占任务栏的小窗口，首次唤出时创建，之后只隐藏不销毁
+fn build_window(app: &AppHandle) -> tauri::Result<WebviewWindow> {
+    let window = WebviewWindowBuilder::new(app, QUICK_ADD_WINDOW, WebviewUrl::App("index.html".into()))
+        .title("SemiDone")
+        .inner_size(480.0, 160.0)
+        .resizable(false)
+        .maximizable(false)
+        .minimizable(false)
+        .decorations(false)
+        .always_on_top(true)
+        .skip_taskbar(true)
+        .visible(false)
+        .build()?;
+
+    // 点击其他地方即收起
+    let handle = window.clone();
+    window.on_window_event(move |event| {
+        if let WindowEvent::Focused(false) = event {
+            let _ = handle.hide();
+        }
+    });
+    Ok(window)
+}
@@ -0,0 +1,542 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use chrono::{DateTime, Datelike, Duration, NaiveDate, TimeZone, Utc};
+use handlebars::{handlebars_helper, no_escape, Handlebars};
+use serde::Serialize;
+use crate::datetime::{due_deadline, local_date, parse_date, parse_datetime};
+use crate::error::{AppError, AppResult};
+use crate::i18n::{current_locale, fill, Locale, Msg};
+use crate::models::{FocusSession, Priority, ReportFormat, ReportKind, ReportTemplate, Task, TimeEntry, UsageSession};
+use crate::stats::{is_overdue, minutes_by_day};
+use crate::storage::Storage;
+
+// 报告中时间的显示格式
+const TIME_FORMAT: &str = "%Y-%m-%d %H:%M";
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+// 未自定义模板时使用的内置 Handlebars 模板
+const MARKDOWN_TEMPLATE_ZH: &str = include_str!("../templates/report.zh-CN.md.hbs");
+const MARKDOWN_TEMPLATE_EN: &str = include_str!("../templates/report.en-US.md.hbs");
+const HTML_TEMPLATE_ZH: &str = include_str!("../templates/report.zh-CN.html.hbs");
+const HTML_TEMPLATE_EN: &str = include_str!("../templates/report.en-US.html.hbs");
+
+// 导出时默认使用的自定义模板文件名
+const DEFAULT_MARKDOWN_TEMPLATE: &str = "report.md";
+const DEFAULT_HTML_TEMPLATE: &str = "report.html";
+
+// Excel 需要 BOM 才能按 UTF-8 识别中文
+const UTF8_BOM: &str = "\u{feff}";
+
+/// 生成报告所需的全部原始数据
+pub struct ReportSource {
+    pub tasks: Vec<Task>,
+    pub focus_sessions: Vec<FocusSession>,
+    pub time_entries: Vec<TimeEntry>,
+    pub usage_sessions: Vec<UsageSession>,
+}
+
+impl ReportSource {
+    pub fn load(storage: &Storage) -> AppResult<Self> {
+        Ok(Self {
+            tasks: storage.load_tasks()?,
+            focus_sessions: storage.load_focus_sessions()?,
+            time_entries: storage.load_time_entries()?,
+            usage_sessions: storage.load_usage_sessions()?,
+        })
+    }
+}
+
+// 以下结构即报告模板可使用的数据，字段名为 camelCase，变更时同步更新 README 中的说明
+
+/// 报告中的一个待办，专注与计时只统计报告区间内的部分
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportTask {
+    pub id: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub completed: bool,
+    pub priority: Priority,
+    // 当前语言下的优先级名称与图标（🔴🟡🟢）
+    pub priority_label: String,
+    pub priority_icon: String,
+    // 截止时间，只有日期时为 YYYY-MM-DD，否则为 YYYY-MM-DD HH:MM
+    pub due: Option<String>,
+    pub created_date: String,
+    pub completed_at: Option<String>,
+    pub estimated_minutes: Option<u32>,
+    pub focus_minutes: u32,
+    pub tracked_minutes: u32,
+    // 未完成且已过截止时间时为逾期天数
+    pub overdue_days: Option<i64>,
+    // 内置模板使用的一行说明，如 完成于 …… · 专注 25 分钟 · 计时 30 分钟
+    pub details: String,
+}
+
+/// 同一优先级的已完成待办
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportTaskGroup {
+    pub priority: Priority,
+    pub label: String,
+    pub icon: String,
+    pub tasks: Vec<ReportTask>,
+}
+
+/// 报告区间内某一天的情况
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportDay {
+    pub date: String,
+    pub created: usize,
+    pub completed: usize,
+    pub focus_minutes: u32,
+    pub tracked_minutes: u32,
+    pub usage_minutes: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportSummary {
+    // 区间内新建、完成的待办数，未完成与逾期为截至生成时的数量
+    pub created: usize,
+    pub completed: usize,
+    pub pending: usize,
+    pub overdue: usize,
+    // 百分比，保留一位小数
+    pub completion_rate: f64,
+    pub focus_minutes: u32,
+    pub focus_sessions: usize,
+    pub tracked_minutes: u32,
+    pub usage_minutes: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportData {
+    pub kind: ReportKind,
+    pub title: String,
+    pub start_date: String,
+    pub end_date: String,
+    pub generated_at: String,
+    pub summary: ReportSummary,
+    pub days: Vec<ReportDay>,
+    // 已完成按完成时间排列；未完成的先按优先级，再按截止时间
+    pub completed_tasks: Vec<ReportTask>,
+    // 已完成的待办按优先级分组，只包含有待办的分组
+    pub completed_groups: Vec<ReportTaskGroup>,
+    pub pending_tasks: Vec<ReportTask>,
+    pub overdue_tasks: Vec<ReportTask>,
+    pub most_productive_day: Option<String>,
+}
+
+/// `today` 所在的周（周一起）或月
+pub fn period_range(kind: ReportKind, today: NaiveDate) -> (NaiveDate, NaiveDate) {
+    match kind {
+        ReportKind::Week => {
+            let start = today - Duration::days(today.weekday().num_days_from_monday() as i64);
+            (start, start + Duration::days(6))
+        }
+        ReportKind::Month => {
+            let start = today.with_day(1).unwrap_or(today);
+            let next_month = if start.month() == 12 {
+                NaiveDate::from_ymd_opt(start.year() + 1, 1, 1)
+            } else {
+                NaiveDate::from_ymd_opt(start.year(), start.month() + 1, 1)
+            };
+            (start, next_month.map_or(today, |d| d - Duration::days(1)))
+        }
+    }
+}
+
+pub fn report_title(kind: ReportKind) -> &'static str {
+    match kind {
+        ReportKind::Week => Msg::ReportWeekly.text(),
+        ReportKind::Month => Msg::ReportMonthly.text(),
+    }
+}
+
+/// 默认的报告文件名，如 事半SemiDone_周报_2025-01-06_2025-01-12.md
+pub fn default_file_name(kind: ReportKind, format: ReportFormat, start: NaiveDate, end: NaiveDate) -> String {
+    let name = fill(Msg::ReportFileName.text(), &[&report_title(kind), &start, &end]);
+    format!("{}.{}", name, format.extension())
+}
+
+fn format_time<Tz: TimeZone>(value: &str, tz: &Tz) -> Option<String>
+where
+    Tz::Offset: std::fmt::Display,
+{
+    parse_datetime(value, tz).map(|dt| dt.format(TIME_FORMAT).to_string())
+}
+
+/// 截止时间只有日期时原样显示
+fn format_due<Tz: TimeZone>(value: &str, tz: &Tz) -> String
+where
+    Tz::Offset: std::fmt::Display,
+{
+    if parse_date(value).is_some() {
+        return value.trim().to_string();
+    }
+    format_time(value, tz).unwrap_or_else(|| value.to_string())
+}
+
+/// 计时记录转换为时段，正在计时的记录计算到 `now`
+fn entry_span(entry: &TimeEntry, now: DateTime<Utc>) -> UsageSession {
+    UsageSession {
+        id: entry.id.clone(),
+        start: entry.start,
+        end: entry.end.unwrap_or(now).max(entry.start),
+    }
+}
+
+fn sum_in_range(days: &BTreeMap<NaiveDate, u32>, start: NaiveDate, end: NaiveDate) -> u32 {
+    days.range(start..=end).map(|(_, minutes)| minutes).sum()
+}
+
+/// 汇总 [start, end] 区间内（含两端）的待办、专注、计时与使用时长，时区取自 `now`
+pub fn build_report<Tz: TimeZone>(
+    source: &ReportSource,
+    kind: ReportKind,
+    start: NaiveDate,
+    end: NaiveDate,
+    now: &DateTime<Tz>,
+) -> ReportData
+where
+    Tz::Offset: std::fmt::Display,
+{
+    let tz = now.timezone();
+    let now_utc = now.with_timezone(&Utc);
+    let in_range = |date: NaiveDate| date >= start && date <= end;
+
+    // 专注按结束时间计入当天，计时与使用时长跨零点时分别计入两天
+    let mut focus_by_day: BTreeMap<NaiveDate, u32> = BTreeMap::new();
+    let mut focus_by_task: BTreeMap<&str, u32> = BTreeMap::new();
+    let mut focus_sessions = 0;
+    for session in &source.focus_sessions {
+        let day = session.end.with_timezone(&tz).date_naive();
+        if !in_range(day) {
+            continue;
+        }
+        focus_sessions += 1;
+        *focus_by_day.entry(day).or_default() += session.minutes;
+        if let Some(task_id) = &session.task_id {
+            *focus_by_task.entry(task_id).or_default() += session.minutes;
+        }
+    }
+
+    let spans: Vec<UsageSession> = source.time_entries.iter().map(|e| entry_span(e, now_utc)).collect();
+    let tracked_by_day = minutes_by_day(&spans, &tz);
+    let mut tracked_by_task: BTreeMap<&str, u32> = BTreeMap::new();
+    for (entry, span) in source.time_entries.iter().zip(&spans) {
+        let minutes = sum_in_range(&minutes_by_day(std::slice::from_ref(span), &tz), start, end);
+        *tracked_by_task.entry(&entry.task_id).or_default() += minutes;
+    }
+
+    let usage_by_day = minutes_by_day(&source.usage_sessions, &tz);
+
+    let report_task = |task: &Task| {
+        let mut report_task = ReportTask {
+            id: task.id.clone(),
+            title: task.title.clone(),
+            description: task.description.clone().filter(|d| !d.trim().is_empty()),
+            completed: task.completed,
+            priority: task.priority,
+            priority_label: priority_label(task.priority).to_string(),
+            priority_icon: priority_icon(task.priority).to_string(),
+            due: task.due_date.as_deref().map(|d| format_due(d, &tz)),
+            created_date: local_date(&task.created_at, &tz)
+                .map(|d| d.format(DATE_FORMAT).to_string())
+                .unwrap_or_default(),
+            completed_at: task.completion_time().and_then(|t| format_time(t, &tz)),
+            estimated_minutes: task.estimated_minutes,
+            focus_minutes: focus_by_task.get(task.id.as_str()).copied().unwrap_or(0),
+            tracked_minutes: tracked_by_task.get(task.id.as_str()).copied().unwrap_or(0),
+            overdue_days: if is_overdue(task, now) {
+                task.due_date.as_deref()
+                    .and_then(|d| due_deadline(d, &tz))
+                    .map(|deadline| (now.clone() - deadline).num_days())
+            } else {
+                None
+            },
+            details: String::new(),
+        };
+        report_task.details = task_details(&report_task);
+        report_task
+    };
+
+    let mut created_by_day: BTreeMap<NaiveDate, usize> = BTreeMap::new();
+    let mut completed_by_day: BTreeMap<NaiveDate, usize> = BTreeMap::new();
+    let mut completed_tasks = Vec::new();
+    let mut pending_tasks = Vec::new();
+    for task in &source.tasks {
+        let created = local_date(&task.created_at, &tz);
+        if let Some(day) = created.filter(|d| in_range(*d)) {
+            *created_by_day.entry(day).or_default() += 1;
+        }
+
+        if let Some(day) = task.completion_time().and_then(|t| local_date(t, &tz)) {
+            if in_range(day) {
+                *completed_by_day.entry(day).or_default() += 1;
+                completed_tasks.push(report_task(task));
+            }
+        } else if !task.completed && created.map_or(true, |d| d <= end) {
+            pending_tasks.push(report_task(task));
+        }
+    }
+
+    // 已完成按完成时间排列；未完成的先按优先级，再按截止时间
+    completed_tasks.sort_by(|a, b| a.completed_at.cmp(&b.completed_at));
+    pending_tasks.sort_by_key(|t| (priority_rank(t.priority), t.due.is_none(), t.due.clone()));
+    let overdue_tasks: Vec<ReportTask> = pending_tasks.iter().filter(|t| t.overdue_days.is_some()).cloned().collect();
+    let completed_groups = [Priority::High, Priority::Medium, Priority::Low]
+        .into_iter()
+        .map(|priority| ReportTaskGroup {
+            priority,
+            label: priority_label(priority).to_string(),
+            icon: priority_icon(priority).to_string(),
+            tasks: completed_tasks.iter().filter(|t| t.priority == priority).cloned().collect(),
+        })
+        .filter(|group| !group.tasks.is_empty())
+        .collect();
+
+    let mut days = Vec::new();
+    let mut day = start;
+    while day <= end {
+        days.push(ReportDay {
+            date: day.format(DATE_FORMAT).to_string(),
+            created: created_by_day.get(&day).copied().unwrap_or(0),
+            completed: completed_by_day.get(&day).copied().unwrap_or(0),
+            focus_minutes: focus_by_day.get(&day).copied().unwrap_or(0),
+            tracked_minutes: tracked_by_day.get(&day).copied().unwrap_or(0),
+            usage_minutes: usage_by_day.get(&day).copied().unwrap_or(0),
+        });
+        day += Duration::days(1);
+    }
+
+    // 完成最多的一天，数量相同时取较早的
+    let most_productive_day = days.iter()
+        .filter(|d| d.completed > 0)
+        .max_by(|a, b| a.completed.cmp(&b.completed).then(b.date.cmp(&a.date)))
+        .map(|d| d.date.clone());
+
+    let completed = completed_tasks.len();
+    let pending = pending_tasks.len();
+    let completion_rate = if completed + pending == 0 {
+        0.0
+    } else {
+        (completed as f64 * 1000.0 / (completed + pending) as f64).round() / 10.0
+    };
+
+    ReportData {
+        kind,
+        title: report_title(kind).to_string(),
+        start_date: start.format(DATE_FORMAT).to_string(),
+        end_date: end.format(DATE_FORMAT).to_string(),
+        generated_at: now.format(TIME_FORMAT).to_string(),
+        summary: ReportSummary {
+            created: created_by_day.values().sum(),
+            completed,
+            pending,
+            overdue: overdue_tasks.len(),
+            completion_rate,
+            focus_minutes: focus_by_day.values().sum(),
+            focus_sessions,
+            tracked_minutes: sum_in_range(&tracked_by_day, start, end),
+            usage_minutes: sum_in_range(&usage_by_day, start, end),
+        },
+        days,
+        completed_tasks,
+        completed_groups,
+        pending_tasks,
+        overdue_tasks,
+        most_productive_day,
+    }
+}
+
+fn priority_rank(priority: Priority) -> u8 {
+    match priority {
+        Priority::High => 0,
+        Priority::Medium => 1,
+        Priority::Low => 2,
+    }
+}
+
+fn priority_icon(priority: Priority) -> &'static str {
+    match priority {
+        Priority::High => "🔴",
+        Priority::Medium => "🟡",
+        Priority::Low => "🟢",
+    }
+}
+
+fn priority_label(priority: Priority) -> &'static str {
+    match priority {
+        Priority::High => Msg::ReportPriorityHigh.text(),
+        Priority::Medium => Msg::ReportPriorityMedium.text(),
+        Priority::Low => Msg::ReportPriorityLow.text(),
+    }
+}
+
+/// 待办标题后的说明，如 完成于 …… · 专注 25 分钟 · 计时 30 分钟 · 预计 60 分钟
+fn task_details(task: &ReportTask) -> String {
+    let mut parts = Vec::new();
+    if let Some(completed_at) = &task.completed_at {
+        parts.push(fill(Msg::ReportCompletedAt.text(), &[completed_at]));
+    } else if let Some(due) = &task.due {
+        parts.push(fill(Msg::ReportDue.text(), &[due]));
+    }
+    if let Some(days) = task.overdue_days {
+        parts.push(fill(Msg::ReportOverdueDays.text(), &[&days.max(1)]));
+    }
+    if task.focus_minutes > 0 || task.tracked_minutes > 0 {
+        parts.push(fill(Msg::ReportEffort.text(), &[&task.focus_minutes, &task.tracked_minutes]));
+    }
+    if let Some(estimate) = task.estimated_minutes {
+        parts.push(fill(Msg::ReportEstimate.text(), &[&estimate]));
+    }
+    parts.join(" · ")
+}
+
+fn format_duration(minutes: u64) -> String {
+    if minutes < 60 {
+        fill(Msg::ReportDurationMinutes.text(), &[&minutes])
+    } else {
+        fill(Msg::ReportDurationHours.text(), &[&(minutes / 60), &(minutes % 60)])
+    }
+}
+
+// 模板中使用 {{duration summary.focusMinutes}} 将分钟数显示为 1 小时 5 分钟
+handlebars_helper!(duration: |minutes: u64| format_duration(minutes));
+
+/// 自定义模板的输出格式由扩展名决定：.md、.html，可再加 .hbs 后缀
+pub fn template_format(name: &str) -> Option<ReportFormat> {
+    let name = name.to_ascii_lowercase();
+    let name = name.strip_suffix(".hbs").unwrap_or(&name);
+    match Path::new(name).extension()?.to_str()? {
+        "md" | "markdown" => Some(ReportFormat::Markdown),
+        "html" | "htm" => Some(ReportFormat::Html),
+        _ => None,
+    }
+}
+
+/// 实际导出的格式：指定了自定义模板时以模板为准
+pub fn effective_format(format: ReportFormat, template: Option<&str>) -> ReportFormat {
+    template.and_then(template_format).unwrap_or(format)
+}
+
+/// 数据目录 report_templates 下可用的自定义模板
+pub fn list_templates(storage: &Storage) -> AppResult<Vec<ReportTemplate>> {
+    Ok(storage.list_report_templates()?
+        .into_iter()
+        .filter_map(|name| template_format(&name).map(|format| ReportTemplate { name, format }))
+        .collect())
+}
+
+/// 指定的自定义模板，未指定时依次使用 report.md / report.html 和内置模板
+fn load_template(storage: &Storage, format: ReportFormat, name: Option<&str>) -> AppResult<String> {
+    if let Some(name) = name {
+        // 只允许模板目录中的文件
+        let is_plain_name = Path::new(name).file_name().is_some_and(|f| f == name);
+        if !is_plain_name || template_format(name).is_none() {
+            return Err(AppError::not_found(Msg::EntityReportTemplate, name));
+        }
+        return storage.load_report_template(name)?
+            .ok_or_else(|| AppError::not_found(Msg::EntityReportTemplate, name));
+    }
+
+    let default_name = match format {
+        ReportFormat::Html => DEFAULT_HTML_TEMPLATE,
+        _ => DEFAULT_MARKDOWN_TEMPLATE,
+    };
+    if let Some(template) = storage.load_report_template(default_name)? {
+        return Ok(template);
+    }
+
+    let builtin = match (format, current_locale()) {
+        (ReportFormat::Html, Locale::ZhCn) => HTML_TEMPLATE_ZH,
+        (ReportFormat::Html, Locale::EnUs) => HTML_TEMPLATE_EN,
+        (_, Locale::ZhCn) => MARKDOWN_TEMPLATE_ZH,
+        (_, Locale::EnUs) => MARKDOWN_TEMPLATE_EN,
+    };
+    Ok(builtin.to_string())
+}
+
+/// 用 Handlebars 渲染模板，HTML 报告中的变量会被转义，Markdown 原样输出
+fn render_template(template: &str, data: &ReportData, format: ReportFormat) -> AppResult<String> {
+    let mut handlebars = Handlebars::new();
+    if format != ReportFormat::Html {
+        handlebars.register_escape_fn(no_escape);
+    }
+    handlebars.register_helper("duration", Box::new(duration));
+
+    handlebars.render_template(template, data)
+        .map_err(|e| AppError::invalid_input(fill(Msg::InvalidReportTemplate.text(), &[&e])))
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// 每个待办一行，依次为已完成和未完成的待办
+fn render_csv(data: &ReportData) -> String {
+    let header = [
+        Msg::ReportColStatus.text(),
+        Msg::ReportColTitle.text(),
+        Msg::ReportColDescription.text(),
+        Msg::ReportColPriority.text(),
+        Msg::ReportColDue.text(),
+        Msg::ReportColCompletedAt.text(),
+        Msg::ReportColEstimate.text(),
+        Msg::ReportColFocus.text(),
+        Msg::ReportColTracked.text(),
+    ];
+    let mut csv = String::from(UTF8_BOM);
+    csv.push_str(&header.map(csv_field).join(","));
+    csv.push_str("\r\n");
+
+    for task in data.completed_tasks.iter().chain(&data.pending_tasks) {
+        let status = if task.completed_at.is_some() {
+            Msg::ReportStatusCompleted.text()
+        } else if task.overdue_days.is_some() {
+            Msg::ReportStatusOverdue.text()
+        } else {
+            Msg::ReportStatusPending.text()
+        };
+        let row = [
+            status.to_string(),
+            task.title.clone(),
+            task.description.clone().unwrap_or_default(),
+            task.priority_label.clone(),
+            task.due.clone().unwrap_or_default(),
+            task.completed_at.clone().unwrap_or_default(),
+            task.estimated_minutes.map(|m| m.to_string()).unwrap_or_default(),
+            task.focus_minutes.to_string(),
+            task.tracked_minutes.to_string(),
+        ];
+        csv.push_str(&row.iter().map(|v| csv_field(v)).collect::<Vec<_>>().join(","));
+        csv.push_str("\r\n");
+    }
+    csv
+}
+
+/// 按格式渲染报告；CSV 不使用模板
+pub fn render(storage: &Storage, data: &ReportData, format: ReportFormat, template: Option<&str>) -> AppResult<String> {
+    let format = effective_format(format, template);
+    if format == ReportFormat::Csv {
+        return Ok(render_csv(data));
+    }
+    let template = load_template(storage, format, template)?;
+    render_template(&template, data, format)
+}
+
+/// 生成报告并写入指定路径
+pub fn export(storage: &Storage, data: &ReportData, format: ReportFormat, template: Option<&str>, path: &Path) -> AppResult<()> {
+    let content = render(storage, data, format, template)?;
+    fs::write(path, content)?;
+    Ok(())
+}
@@ -1,4 +1,9 @@
-use std::sync::Mutex;
+use std::io::{BufRead, BufReader, Write};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use interprocess::local_socket::{prelude::*, Listener, ListenerOptions, Name, Stream};
+use serde::{Deserialize, Serialize};
 
 #[cfg(target_os = "windows")]
 use windows::Win32::System::Threading::{CreateMutexW};
@@ -7,6 +12,18 @@ use windows::Win32::Foundation::{CloseHandle, GetLastError, ERROR_ALREADY_EXISTS
 #[cfg(target_os = "windows")]
 use windows::core::PCWSTR;
 
+#[cfg(not(target_os = "windows"))]
+use std::{fs::File, path::PathBuf};
+#[cfg(not(target_os = "windows"))]
+use fs4::fs_std::FileExt;
+
+// 第二个进程连接主实例的重试次数与间隔（主实例可能仍在启动中）
+const CONNECT_RETRIES: u32 = 20;
+const CONNECT_RETRY_INTERVAL: Duration = Duration::from_millis(100);
+
+// 启动界面时锁被命令行或 MCP 占用，等待其释放的最长时间
+const LOCK_WAIT: Duration = Duration::from_secs(10);
+
 #[cfg(target_os = "windows")]
 #[derive(Debug)]
 struct SafeHandle(HANDLE);
@@ -16,42 +33,73 @@ unsafe impl Send for SafeHandle {}
 #[cfg(target_os = "windows")]
 unsafe impl Sync for SafeHandle {}
 
-// SingleInstance struct holds the mutex handle
+/// 第二个进程转发给主实例的启动参数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForwardedArgs {
+    pub args: Vec<String>,
+    pub cwd: Option<String>,
+}
+
+impl ForwardedArgs {
+    pub fn from_env() -> Self {
+        Self {
+            args: std::env::args().skip(1).collect(),
+            cwd: std::env::current_dir().ok().map(|p| p.to_string_lossy().to_string()),
+        }
+    }
+}
+
+// SingleInstance struct holds the lock for the lifetime of the process
+// Windows 使用命名互斥量，Linux / macOS 使用 flock 锁文件
 #[derive(Debug)]
 pub struct SingleInstance {
     #[cfg(target_os = "windows")]
     mutex_handle: SafeHandle,
+    #[cfg(not(target_os = "windows"))]
+    lock_file: File,
 }
 
 impl SingleInstance {
-    pub fn new(app_name: &str) -> Result<Self, String> {
+    /// 尝试获取单实例锁，已有实例运行时返回 Ok(None)
+    pub fn new(app_name: &str) -> Result<Option<Self>, String> {
         #[cfg(target_os = "windows")]
         {
             let mutex_name = format!("Global\\{}_SingleInstance", app_name);
             let wide_name: Vec<u16> = mutex_name.encode_utf16().chain(std::iter::once(0)).collect();
-            
+
             unsafe {
                 let handle = CreateMutexW(None, true, PCWSTR(wide_name.as_ptr()))
                     .map_err(|e| format!("Failed to create mutex: {}", e))?;
 
                 if GetLastError() == ERROR_ALREADY_EXISTS {
                     let _ = CloseHandle(handle);
-                    return Err("Another instance is already running".to_string());
+                    return Ok(None);
                 }
-                
-                println!("Single instance mutex created successfully");
-                Ok(SingleInstance { mutex_handle: SafeHandle(handle) })
+
+                log::info!("Single instance mutex created successfully");
+                Ok(Some(SingleInstance { mutex_handle: SafeHandle(handle) }))
             }
         }
-        
+
         #[cfg(not(target_os = "windows"))]
         {
-            Ok(SingleInstance {})
+            let lock_path = user_file_path(app_name, "lock");
+            let lock_file = File::create(&lock_path)
+                .map_err(|e| format!("Failed to open lock file {}: {}", lock_path.display(), e))?;
+
+            match lock_file.try_lock_exclusive() {
+                Ok(true) => {
+                    log::info!("Single instance lock acquired: {}", lock_path.display());
+                    Ok(Some(SingleInstance { lock_file }))
+                }
+                Ok(false) => Ok(None),
+                Err(e) => Err(format!("Failed to lock {}: {}", lock_path.display(), e)),
+            }
         }
     }
 }
 
-// The Drop trait ensures the mutex handle is closed when SingleInstance goes out of scope
+// The Drop trait ensures the lock is released when SingleInstance goes out of scope
 impl Drop for SingleInstance {
     fn drop(&mut self) {
         #[cfg(target_os = "windows")]
@@ -60,38 +108,205 @@ impl Drop for SingleInstance {
                 unsafe {
                     let _ = CloseHandle(self.mutex_handle.0);
                 }
-                println!("Single instance mutex released on drop");
+                log::info!("Single instance mutex released on drop");
             }
         }
+
+        #[cfg(not(target_os = "windows"))]
+        {
+            let _ = FileExt::unlock(&self.lock_file);
+            log::info!("Single instance lock released on drop");
+        }
+    }
+}
+
+/// 锁文件与套接字所在目录：优先使用 XDG_RUNTIME_DIR，其次为系统临时目录
+#[cfg(not(target_os = "windows"))]
+fn runtime_dir() -> PathBuf {
+    dirs::runtime_dir().unwrap_or_else(std::env::temp_dir)
+}
+
+/// 主实例监听的本地套接字名：Windows 为命名管道，其余平台为 Unix 域套接字文件
+fn socket_name(app_name: &str) -> std::io::Result<Name<'static>> {
+    #[cfg(target_os = "windows")]
+    {
+        use interprocess::local_socket::GenericNamespaced;
+        format!("{}_SingleInstance", app_name).to_ns_name::<GenericNamespaced>()
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        use interprocess::local_socket::GenericFilePath;
+        socket_path(app_name)
+            .to_fs_name::<GenericFilePath>()
+            .map(|name| name.into_owned())
     }
 }
 
+#[cfg(not(target_os = "windows"))]
+fn socket_path(app_name: &str) -> PathBuf {
+    user_file_path(app_name, "sock")
+}
+
+/// 锁文件、套接字的路径；临时目录可能被多个用户共享，文件名中带上用户名
+#[cfg(not(target_os = "windows"))]
+fn user_file_path(app_name: &str, extension: &str) -> PathBuf {
+    let user = std::env::var("USER").unwrap_or_default();
+    runtime_dir().join(format!("{}-{}.{}", app_name, user, extension))
+}
+
+/// 获取单实例锁的结果
+pub enum InstanceRole {
+    /// 当前进程是主实例，需调用 `listen` 接收后续启动转发来的参数
+    Primary(InstanceServer),
+    /// 已有实例在运行，参数已转发，返回主实例的回复
+    Secondary(serde_json::Value),
+}
+
 // Global mutex holder to manage the lifetime of the SingleInstance object
 static SINGLE_INSTANCE: Mutex<Option<SingleInstance>> = Mutex::new(None);
 
-pub fn ensure_single_instance(app_name: &str) -> Result<(), String> {
+/// 确保单实例运行：获取到锁则成为主实例，否则把 `forwarded` 发送给已运行的实例
+///
+/// 持有锁的进程不接收转发时（命令行、MCP）等待锁释放，最多等待 `LOCK_WAIT`
+pub fn ensure_single_instance(app_name: &str, forwarded: &ForwardedArgs) -> Result<InstanceRole, String> {
     let mut instance_guard = SINGLE_INSTANCE.lock().unwrap();
-    
+
     if instance_guard.is_some() {
         // This case should ideally not be hit if logic is correct, but as a safeguard:
         return Err("Single instance lock already held by this process.".to_string());
     }
 
-    match SingleInstance::new(app_name) {
-        Ok(instance) => {
+    let deadline = Instant::now() + LOCK_WAIT;
+    loop {
+        if let Some(instance) = SingleInstance::new(app_name)? {
             *instance_guard = Some(instance);
-            Ok(())
+            return InstanceServer::bind(app_name).map(InstanceRole::Primary);
+        }
+
+        match forward_to_primary(app_name, forwarded) {
+            Ok(reply) => return Ok(InstanceRole::Secondary(reply)),
+            // 锁被命令行或 MCP 短暂持有，它们不接收转发，执行完即释放
+            Err(e) if Instant::now() < deadline => log::warn!("Waiting for the instance lock: {}", e),
+            Err(e) => return Err(e),
         }
-        Err(e) => {
-            // If we failed to create a new instance, it might be because another process is running.
-            Err(e)
+    }
+}
+
+/// 命令行、MCP 等无界面调用访问数据的方式
+pub enum Coordination {
+    /// 没有实例在运行，持有锁期间可以直接读写数据文件，drop 后释放
+    Local(SingleInstance),
+    /// 已转发给运行中的实例执行，返回其回复
+    Forwarded(serde_json::Value),
+}
+
+/// 短暂获取单实例锁；已有实例运行时把参数转发给它，避免两个进程同时写数据文件
+///
+/// 与 `ensure_single_instance` 不同，不会成为主实例，适合长时间运行但不需要界面的进程
+pub fn lock_or_forward(app_name: &str, forwarded: &ForwardedArgs) -> Result<Coordination, String> {
+    match SingleInstance::new(app_name)? {
+        Some(lock) => Ok(Coordination::Local(lock)),
+        None => forward_to_primary(app_name, forwarded).map(Coordination::Forwarded),
+    }
+}
+
+/// 将参数发送给主实例并等待回复（一行 JSON 请求，一行 JSON 回复）
+fn forward_to_primary(app_name: &str, forwarded: &ForwardedArgs) -> Result<serde_json::Value, String> {
+    let name = socket_name(app_name).map_err(|e| format!("Invalid socket name: {}", e))?;
+
+    let mut last_error = None;
+    for _ in 0..CONNECT_RETRIES {
+        match Stream::connect(name.borrow()) {
+            Ok(stream) => {
+                let mut stream = BufReader::new(stream);
+                let mut request = serde_json::to_string(forwarded).map_err(|e| e.to_string())?;
+                request.push('\n');
+                stream.get_mut().write_all(request.as_bytes())
+                    .map_err(|e| format!("Failed to forward arguments: {}", e))?;
+
+                let mut reply = String::new();
+                stream.read_line(&mut reply)
+                    .map_err(|e| format!("Failed to read reply: {}", e))?;
+                return Ok(serde_json::from_str(&reply).unwrap_or(serde_json::Value::Null));
+            }
+            Err(e) => {
+                last_error = Some(e);
+                thread::sleep(CONNECT_RETRY_INTERVAL);
+            }
         }
     }
+
+    Err(format!(
+        "Another instance is running but not responding: {}",
+        last_error.map(|e| e.to_string()).unwrap_or_default()
+    ))
+}
+
+/// 主实例的参数接收端
+///
+/// 获取锁后立即监听，setup 完成前到达的连接在队列中等待处理
+pub struct InstanceServer {
+    listener: Listener,
+}
+
+impl InstanceServer {
+    fn bind(app_name: &str) -> Result<Self, String> {
+        let name = socket_name(app_name).map_err(|e| format!("Invalid socket name: {}", e))?;
+
+        // 持有锁时残留的套接字文件必然来自已退出的进程，可以安全删除
+        #[cfg(not(target_os = "windows"))]
+        let _ = std::fs::remove_file(socket_path(app_name));
+
+        let listener = ListenerOptions::new()
+            .name(name)
+            .create_sync()
+            .map_err(|e| format!("Failed to listen for other instances: {}", e))?;
+
+        Ok(Self { listener })
+    }
+
+    /// 在后台线程处理后续启动转发来的参数，`handler` 的返回值作为回复发回
+    ///
+    /// 每个连接在单独的线程中处理，迟迟不发送请求的连接不会阻塞之后的转发
+    pub fn listen<F>(self, handler: F)
+    where
+        F: Fn(ForwardedArgs) -> serde_json::Value + Send + Sync + 'static,
+    {
+        let listener = self.listener;
+        let handler = Arc::new(handler);
+        thread::spawn(move || {
+            for conn in listener.incoming() {
+                let conn = match conn {
+                    Ok(conn) => conn,
+                    Err(e) => {
+                        log::warn!("Incoming instance connection failed: {}", e);
+                        continue;
+                    }
+                };
+
+                let handler = Arc::clone(&handler);
+                thread::spawn(move || {
+                    let mut conn = BufReader::new(conn);
+                    let mut line = String::new();
+                    if conn.read_line(&mut line).is_err() {
+                        return;
+                    }
+
+                    let reply = match serde_json::from_str::<ForwardedArgs>(&line) {
+                        Ok(forwarded) => handler(forwarded),
+                        Err(e) => serde_json::json!({ "success": false, "error": e.to_string() }),
+                    };
+                    let _ = conn.get_mut().write_all(format!("{}\n", reply).as_bytes());
+                });
+            }
+        });
+    }
 }
 
 #[allow(dead_code)]
 pub fn cleanup_single_instance() {
     let mut instance = SINGLE_INSTANCE.lock().unwrap();
     *instance = None;
-    println!("Single instance cleanup completed");
+    log::info!("Single instance cleanup completed");
 }
@@ -0,0 +1,412 @@
+use std::collections::BTreeMap;
+use chrono::{DateTime, Datelike, Duration, NaiveDate, TimeZone, Timelike};
+use crate::datetime::{due_deadline, local_date, parse_datetime, start_of_day};
+use crate::models::{HeatmapDay, Priority, PriorityCompletionTime, ProductivityStats, Task, TaskStats, TaskTrendPoint, UsageDetail, UsageSession, UsageStats};
+
+// 趋势统计允许的最大天数，防止前端传入过大的区间
+pub const MAX_TREND_DAYS: i64 = 366;
+
+// 日平均使用时长按最近多少天计算
+const USAGE_AVERAGE_DAYS: i64 = 30;
+
+/// 未完成且已过截止时间
+pub fn is_overdue<Tz: TimeZone>(task: &Task, now: &DateTime<Tz>) -> bool {
+    !task.completed && task.due_date.as_deref()
+        .and_then(|d| due_deadline(d, &now.timezone()))
+        .is_some_and(|deadline| deadline <= *now)
+}
+
+/// 截止日期为 `now` 所在的当天（不区分是否完成）
+pub fn is_due_today<Tz: TimeZone>(task: &Task, now: &DateTime<Tz>) -> bool {
+    task.due_date.as_deref()
+        .and_then(|d| local_date(d, &now.timezone()))
+        .is_some_and(|date| date == now.date_naive())
+}
+
+/// 以给定的“当前时间”计算待办统计，时区取自 `now`
+pub fn compute_task_stats<Tz: TimeZone>(tasks: &[Task], now: &DateTime<Tz>) -> TaskStats {
+    let total = tasks.len();
+    let completed = tasks.iter().filter(|t| t.completed).count();
+    let pending = total - completed;
+
+    let overdue = tasks.iter().filter(|t| is_overdue(t, now)).count();
+    let due_today = tasks.iter().filter(|t| is_due_today(t, now)).count();
+
+    let high_priority = tasks.iter()
+        .filter(|t| matches!(t.priority, Priority::High))
+        .count();
+    let medium_priority = tasks.iter()
+        .filter(|t| matches!(t.priority, Priority::Medium))
+        .count();
+    let low_priority = tasks.iter()
+        .filter(|t| matches!(t.priority, Priority::Low))
+        .count();
+
+    TaskStats {
+        total,
+        completed,
+        pending,
+        overdue,
+        today: due_today,
+        high_priority,
+        medium_priority,
+        low_priority,
+    }
+}
+
+/// 按天统计 [start, end] 区间内（含两端）新建与完成的待办数量
+pub fn compute_task_trend<Tz: TimeZone>(tasks: &[Task], start: NaiveDate, end: NaiveDate, tz: &Tz) -> Vec<TaskTrendPoint> {
+    let mut days: BTreeMap<NaiveDate, (usize, usize)> = BTreeMap::new();
+    let mut day = start;
+    while day <= end {
+        days.insert(day, (0, 0));
+        day += Duration::days(1);
+    }
+
+    for task in tasks {
+        if let Some(entry) = local_date(&task.created_at, tz).and_then(|d| days.get_mut(&d)) {
+            entry.0 += 1;
+        }
+        if let Some(entry) = task.completion_time().and_then(|t| local_date(t, tz)).and_then(|d| days.get_mut(&d)) {
+            entry.1 += 1;
+        }
+    }
+
+    days.into_iter()
+        .map(|(date, (created, completed))| TaskTrendPoint {
+            date: date.format("%Y-%m-%d").to_string(),
+            created,
+            completed,
+        })
+        .collect()
+}
+
+/// 某一天的使用情况，首次、最后使用时间为距当天零点的分钟数
+#[derive(Default)]
+struct DayUsage {
+    seconds: i64,
+    sessions: usize,
+    first_minute: Option<i64>,
+    last_minute: Option<i64>,
+}
+
+/// 按 `tz` 的日期切分使用时段，跨零点的时段分别计入两天
+fn usage_by_day<Tz: TimeZone>(sessions: &[UsageSession], tz: &Tz) -> BTreeMap<NaiveDate, DayUsage> {
+    let mut days: BTreeMap<NaiveDate, DayUsage> = BTreeMap::new();
+
+    for session in sessions {
+        let start = session.start.with_timezone(tz);
+        let end = session.end.with_timezone(tz);
+        let mut day = start.date_naive();
+        while day <= end.date_naive() {
+            let midnight = start_of_day(day, tz);
+            let from = midnight.clone().max(start.clone());
+            let to = start_of_day(day + Duration::days(1), tz).min(end.clone());
+            if to > from {
+                let entry = days.entry(day).or_default();
+                entry.seconds += (to.clone() - from.clone()).num_seconds();
+                entry.sessions += 1;
+                let first = (from - midnight.clone()).num_minutes();
+                let last = (to - midnight).num_minutes();
+                entry.first_minute = Some(entry.first_minute.map_or(first, |m| m.min(first)));
+                entry.last_minute = Some(entry.last_minute.map_or(last, |m| m.max(last)));
+            }
+            day += Duration::days(1);
+        }
+    }
+
+    days
+}
+
+fn to_minutes(seconds: i64) -> u32 {
+    (seconds.max(0) / 60) as u32
+}
+
+fn format_minute_of_day(minute: i64) -> String {
+    format!("{:02}:{:02}", minute / 60, minute % 60)
+}
+
+/// 按 `tz` 的日期汇总各时段的分钟数，跨零点的时段分别计入两天
+pub fn minutes_by_day<Tz: TimeZone>(sessions: &[UsageSession], tz: &Tz) -> BTreeMap<NaiveDate, u32> {
+    usage_by_day(sessions, tz)
+        .into_iter()
+        .map(|(day, usage)| (day, to_minutes(usage.seconds)))
+        .collect()
+}
+
+/// 以给定的“当前时间”汇总使用时长，本周从周一算起，时区取自 `now`
+pub fn compute_usage_stats<Tz: TimeZone>(sessions: &[UsageSession], now: &DateTime<Tz>) -> UsageStats {
+    let days = usage_by_day(sessions, &now.timezone());
+    let today = now.date_naive();
+    let week_start = today - Duration::days(today.weekday().num_days_from_monday() as i64);
+    let month_start = today.with_day(1).unwrap_or(today);
+    let seconds_since = |from: NaiveDate| -> i64 {
+        days.range(from..=today).map(|(_, day)| day.seconds).sum()
+    };
+
+    let recent: Vec<i64> = days.range(today - Duration::days(USAGE_AVERAGE_DAYS - 1)..=today)
+        .map(|(_, day)| day.seconds)
+        .filter(|seconds| *seconds > 0)
+        .collect();
+    let average_daily = if recent.is_empty() {
+        0
+    } else {
+        to_minutes(recent.iter().sum::<i64>() / recent.len() as i64)
+    };
+
+    let longest = sessions.iter()
+        .map(|s| (s.end - s.start).num_seconds())
+        .max()
+        .unwrap_or(0);
+
+    UsageStats {
+        today: to_minutes(seconds_since(today)),
+        this_week: to_minutes(seconds_since(week_start)),
+        this_month: to_minutes(seconds_since(month_start)),
+        average_daily,
+        total_sessions: sessions.len(),
+        longest_session: to_minutes(longest),
+    }
+}
+
+/// 按天列出 [start, end] 区间内（含两端）的使用情况
+pub fn compute_usage_details<Tz: TimeZone>(sessions: &[UsageSession], start: NaiveDate, end: NaiveDate, tz: &Tz) -> Vec<UsageDetail> {
+    let mut days = usage_by_day(sessions, tz);
+    let mut details = Vec::new();
+    let mut day = start;
+    while day <= end {
+        let usage = days.remove(&day).unwrap_or_default();
+        details.push(UsageDetail {
+            date: day.format("%Y-%m-%d").to_string(),
+            total_minutes: to_minutes(usage.seconds),
+            sessions: usage.sessions,
+            first_use: usage.first_minute.map(format_minute_of_day),
+            last_use: usage.last_minute.map(format_minute_of_day),
+        });
+        day += Duration::days(1);
+    }
+    details
+}
+
+fn priority_index(priority: Priority) -> usize {
+    match priority {
+        Priority::High => 0,
+        Priority::Medium => 1,
+        Priority::Low => 2,
+    }
+}
+
+/// 热力图颜色深浅：0 表示没有完成，其余按占区间最大值的比例分为 1-4 级
+fn heatmap_level(count: usize, max: usize) -> u8 {
+    if count == 0 || max == 0 {
+        return 0;
+    }
+    (count * 4).div_ceil(max).clamp(1, 4) as u8
+}
+
+/// 数量最多的下标，数量相同时取较小的；全为 0 时返回 None
+fn busiest(counts: &[usize]) -> Option<u8> {
+    counts.iter()
+        .enumerate()
+        .filter(|(_, count)| **count > 0)
+        .max_by(|a, b| a.1.cmp(b.1).then(b.0.cmp(&a.0)))
+        .map(|(index, _)| index as u8)
+}
+
+/// 分析 [start, end] 区间内（含两端）完成的待办，时区取自 `now`
+///
+/// 日期、星期与小时按完成时刻的本地时间计算；完成用时按实际经过的时间计算，不受夏令时切换影响
+pub fn compute_productivity_stats<Tz: TimeZone>(tasks: &[Task], start: NaiveDate, end: NaiveDate, now: &DateTime<Tz>) -> ProductivityStats {
+    let tz = now.timezone();
+    let mut by_day: BTreeMap<NaiveDate, usize> = BTreeMap::new();
+    let mut by_weekday = vec![0; 7];
+    let mut by_hour = vec![0; 24];
+    // 各优先级（高、中、低）的完成数量，以及能算出用时的数量与总秒数
+    let mut by_priority = [(0, 0, 0); 3];
+
+    for task in tasks {
+        let Some(completed_at) = task.completion_time().and_then(|t| parse_datetime(t, &tz)) else {
+            continue;
+        };
+        let day = completed_at.date_naive();
+        if day < start || day > end {
+            continue;
+        }
+
+        *by_day.entry(day).or_default() += 1;
+        by_weekday[day.weekday().num_days_from_monday() as usize] += 1;
+        by_hour[completed_at.hour() as usize] += 1;
+
+        let entry = &mut by_priority[priority_index(task.priority)];
+        entry.0 += 1;
+        if let Some(created_at) = parse_datetime(&task.created_at, &tz) {
+            let seconds = (completed_at - created_at).num_seconds();
+            if seconds >= 0 {
+                entry.1 += 1;
+                entry.2 += seconds;
+            }
+        }
+    }
+
+    let max = by_day.values().copied().max().unwrap_or(0);
+    let mut heatmap = Vec::new();
+    let mut longest_streak = 0;
+    let mut streak = 0;
+    let mut day = start;
+    while day <= end {
+        let completed = by_day.get(&day).copied().unwrap_or(0);
+        streak = if completed > 0 { streak + 1 } else { 0 };
+        longest_streak = longest_streak.max(streak);
+        heatmap.push(HeatmapDay {
+            date: day.format("%Y-%m-%d").to_string(),
+            completed,
+            level: heatmap_level(completed, max),
+        });
+        day += Duration::days(1);
+    }
+
+    // 今天还没结束，尚未完成待办时不中断连续天数
+    let today = now.date_naive();
+    let mut day = end.min(today);
+    if day == today && !by_day.contains_key(&day) {
+        day -= Duration::days(1);
+    }
+    let mut current_streak = 0;
+    while day >= start && by_day.contains_key(&day) {
+        current_streak += 1;
+        day -= Duration::days(1);
+    }
+
+    let average_completion = [Priority::High, Priority::Medium, Priority::Low]
+        .into_iter()
+        .map(|priority| {
+            let (completed, counted, seconds) = by_priority[priority_index(priority)];
+            PriorityCompletionTime {
+                priority,
+                completed,
+                // 小时，保留一位小数
+                average_hours: (counted > 0).then(|| (seconds as f64 / counted as f64 / 360.0).round() / 10.0),
+            }
+        })
+        .collect();
+
+    ProductivityStats {
+        start_date: start.format("%Y-%m-%d").to_string(),
+        end_date: end.format("%Y-%m-%d").to_string(),
+        total_completed: by_day.values().sum(),
+        heatmap,
+        current_streak,
+        longest_streak,
+        average_completion,
+        most_productive_weekday: busiest(&by_weekday),
+        most_productive_hour: busiest(&by_hour),
+        by_weekday,
+        by_hour,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{FixedOffset, Utc};
+    use chrono_tz::America::New_York;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn utc(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value).unwrap().with_timezone(&Utc)
+    }
+
+    fn completed_at(value: &str) -> Task {
+        let mut task = Task::new("task".to_string(), None, None, None, None);
+        task.completed = true;
+        task.created_at = "2024-01-01T00:00:00Z".to_string();
+        task.completed_at = Some(value.to_string());
+        task
+    }
+
+    fn session(start: &str, end: &str) -> UsageSession {
+        UsageSession { id: start.to_string(), start: utc(start), end: utc(end) }
+    }
+
+    fn beijing() -> FixedOffset {
+        FixedOffset::east_opt(8 * 3600).unwrap()
+    }
+
+    #[test]
+    fn streak_ending_yesterday_continues_until_today_is_over() {
+        let now = utc("2025-01-10T04:00:00Z").with_timezone(&beijing());
+        let mut tasks: Vec<Task> = ["2025-01-07T02:00:00Z", "2025-01-08T02:00:00Z", "2025-01-09T02:00:00Z"]
+            .into_iter()
+            .map(completed_at)
+            .collect();
+
+        let stats = compute_productivity_stats(&tasks, date(2025, 1, 1), date(2025, 1, 10), &now);
+        assert_eq!(stats.current_streak, 3);
+        assert_eq!(stats.longest_streak, 3);
+
+        // 今天完成后计入今天
+        tasks.push(completed_at("2025-01-10T03:00:00Z"));
+        let stats = compute_productivity_stats(&tasks, date(2025, 1, 1), date(2025, 1, 10), &now);
+        assert_eq!(stats.current_streak, 4);
+        assert_eq!(stats.longest_streak, 4);
+    }
+
+    #[test]
+    fn streak_breaks_when_yesterday_has_no_completion() {
+        let now = utc("2025-01-10T04:00:00Z").with_timezone(&beijing());
+        let tasks: Vec<Task> = ["2025-01-07T02:00:00Z", "2025-01-08T02:00:00Z"]
+            .into_iter()
+            .map(completed_at)
+            .collect();
+
+        let stats = compute_productivity_stats(&tasks, date(2025, 1, 1), date(2025, 1, 10), &now);
+        assert_eq!(stats.current_streak, 0);
+        assert_eq!(stats.longest_streak, 2);
+    }
+
+    #[test]
+    fn heatmap_days_follow_local_midnight_of_offset() {
+        let now = utc("2025-01-10T04:00:00Z").with_timezone(&beijing());
+        // 北京时间 2025-01-01 23:59 与 2025-01-02 00:30
+        let tasks = vec![completed_at("2025-01-01T15:59:00Z"), completed_at("2025-01-01T16:30:00Z")];
+
+        let stats = compute_productivity_stats(&tasks, date(2025, 1, 1), date(2025, 1, 2), &now);
+        let days: Vec<(&str, usize)> = stats.heatmap.iter().map(|d| (d.date.as_str(), d.completed)).collect();
+        assert_eq!(days, vec![("2025-01-01", 1), ("2025-01-02", 1)]);
+        assert_eq!(stats.by_hour[23], 1);
+        assert_eq!(stats.by_hour[0], 1);
+
+        // 同样的时刻在 UTC-5 都落在 1 月 1 日
+        let west = FixedOffset::west_opt(5 * 3600).unwrap();
+        let stats = compute_productivity_stats(&tasks, date(2025, 1, 1), date(2025, 1, 2), &now.with_timezone(&west));
+        let days: Vec<(&str, usize)> = stats.heatmap.iter().map(|d| (d.date.as_str(), d.completed)).collect();
+        assert_eq!(days, vec![("2025-01-01", 2), ("2025-01-02", 0)]);
+        assert_eq!(stats.heatmap[0].level, 4);
+        assert_eq!(stats.heatmap[1].level, 0);
+    }
+
+    #[test]
+    fn minutes_by_day_counts_short_and_long_dst_days() {
+        // 纽约当地整天的使用时段：夏令时开始当天 23 小时，结束当天 25 小时
+        let sessions = vec![
+            session("2024-03-10T05:00:00Z", "2024-03-11T04:00:00Z"),
+            session("2024-11-03T04:00:00Z", "2024-11-04T05:00:00Z"),
+        ];
+        let minutes = minutes_by_day(&sessions, &New_York);
+        assert_eq!(minutes.get(&date(2024, 3, 10)), Some(&(23 * 60)));
+        assert_eq!(minutes.get(&date(2024, 11, 3)), Some(&(25 * 60)));
+        assert_eq!(minutes.len(), 2);
+    }
+
+    #[test]
+    fn minutes_by_day_splits_session_at_local_midnight() {
+        // 纽约 2024-11-02 23:30 EDT 到 2024-11-03 01:30 EST，跨零点并经过回拨
+        let sessions = vec![session("2024-11-03T03:30:00Z", "2024-11-03T06:30:00Z")];
+        let minutes = minutes_by_day(&sessions, &New_York);
+        assert_eq!(minutes.get(&date(2024, 11, 2)), Some(&30));
+        assert_eq!(minutes.get(&date(2024, 11, 3)), Some(&150));
+    }
+}
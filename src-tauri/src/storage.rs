@@ -1,16 +1,30 @@
 use std::fs;
 use std::path::PathBuf;
-use serde_json;
-use crate::models::{Task, Settings, TaskStats, Priority};
+use serde_json::{self, Value};
+use chrono::{Duration, Local, NaiveDate, Utc};
+use uuid::Uuid;
+use crate::models::{FocusSession, PomodoroState, ProductivityStats, Task, Settings, TaskStats, TaskTrendPoint, TimeEntry, UsageSession, SETTINGS_VERSION};
+use crate::stats::{compute_productivity_stats, compute_task_stats, compute_task_trend};
+use crate::error::{AppError, AppResult};
+use crate::i18n::Msg;
+use crate::sync::{self, SyncConfig, SyncState};
+
+// 使用记录只保留最近一年
+const USAGE_RETENTION_DAYS: i64 = 366;
+
+// 指定数据目录的环境变量，可以在同一台电脑上运行相互独立的多份数据（如测试同步）
+pub const DATA_DIR_ENV: &str = "SEMIDONE_DATA_DIR";
 
 pub struct Storage {
     data_dir: PathBuf,
 }
 
 impl Storage {
-    pub fn new() -> Result<Self, Box<dyn std::error::Error>> {
-        let data_dir = Self::get_data_dir()?;
-        
+    pub fn new() -> AppResult<Self> {
+        Self::with_data_dir(Self::get_data_dir()?)
+    }
+
+    pub fn with_data_dir(data_dir: PathBuf) -> AppResult<Self> {
         // 确保数据目录存在
         if !data_dir.exists() {
             fs::create_dir_all(&data_dir)?;
@@ -19,9 +33,13 @@ impl Storage {
         Ok(Self { data_dir })
     }
 
-    fn get_data_dir() -> Result<PathBuf, Box<dyn std::error::Error>> {
+    fn get_data_dir() -> AppResult<PathBuf> {
+        if let Some(dir) = std::env::var_os(DATA_DIR_ENV).filter(|dir| !dir.is_empty()) {
+            return Ok(PathBuf::from(dir));
+        }
+
         let home_dir = dirs::home_dir()
-            .ok_or("无法获取用户主目录")?;
+            .ok_or_else(|| AppError::Internal(Msg::HomeDirMissing.text().to_string()))?;
         
         Ok(home_dir.join(".todo-app"))
     }
@@ -38,28 +56,104 @@ impl Storage {
         self.data_dir.join("settings.json")
     }
 
-    pub fn load_tasks(&self) -> Result<Vec<Task>, Box<dyn std::error::Error>> {
+    fn get_usage_file(&self) -> PathBuf {
+        self.data_dir.join("usage.json")
+    }
+
+    fn get_pomodoro_file(&self) -> PathBuf {
+        self.data_dir.join("pomodoro.json")
+    }
+
+    fn get_focus_sessions_file(&self) -> PathBuf {
+        self.data_dir.join("focus_sessions.json")
+    }
+
+    fn get_time_entries_file(&self) -> PathBuf {
+        self.data_dir.join("time_entries.json")
+    }
+
+    /// 用户自定义的报告模板目录，模板文件不存在时使用内置模板
+    pub fn get_report_templates_dir(&self) -> PathBuf {
+        self.data_dir.join("report_templates")
+    }
+
+    fn get_sync_config_file(&self) -> PathBuf {
+        self.data_dir.join("sync.json")
+    }
+
+    fn get_sync_state_file(&self) -> PathBuf {
+        self.data_dir.join("sync_state.json")
+    }
+
+    fn get_api_token_file(&self) -> PathBuf {
+        self.data_dir.join("api_token")
+    }
+
+    /// 读取本地接口的访问令牌，不存在时生成
+    pub fn load_or_create_api_token(&self) -> AppResult<String> {
+        if let Ok(token) = fs::read_to_string(self.get_api_token_file()) {
+            let token = token.trim();
+            if !token.is_empty() {
+                return Ok(token.to_string());
+            }
+        }
+        self.reset_api_token()
+    }
+
+    /// 生成新的访问令牌，旧令牌立即失效
+    pub fn reset_api_token(&self) -> AppResult<String> {
+        let file_path = self.get_api_token_file();
+        let token = format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple());
+        fs::write(&file_path, &token)?;
+
+        // 令牌等同于数据访问权限，仅当前用户可读
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&file_path, fs::Permissions::from_mode(0o600))?;
+        }
+
+        Ok(token)
+    }
+
+    pub fn load_tasks(&self) -> AppResult<Vec<Task>> {
         let file_path = self.get_tasks_file();
         
         if !file_path.exists() {
             return Ok(Vec::new());
         }
 
-        let content = fs::read_to_string(file_path)?;
+        let content = fs::read_to_string(&file_path)?;
+        // 解析失败时报错而不是返回空列表，避免下一次保存覆盖掉原有数据
         let tasks: Vec<Task> = serde_json::from_str(&content)
-            .unwrap_or_else(|_| Vec::new());
+            .map_err(|e| AppError::corrupt(file_path.to_string_lossy(), e))?;
         
         Ok(tasks)
     }
 
-    pub fn save_tasks(&self, tasks: &[Task]) -> Result<(), Box<dyn std::error::Error>> {
+    pub fn save_tasks(&self, tasks: &[Task]) -> AppResult<()> {
+        self.write_tasks(tasks)?;
+
+        // 同步文件夹暂时不可用时不影响本地保存，修改会在下次保存或同步时补记
+        if let Err(e) = sync::record_local_changes(self, tasks) {
+            log::warn!("Failed to record changes for sync: {}", e);
+        }
+        Ok(())
+    }
+
+    /// 写入合并了其他设备修改的待办，不再记为本机的修改
+    pub fn replace_tasks_from_sync(&self, tasks: &[Task]) -> AppResult<()> {
+        self.write_tasks(tasks)
+    }
+
+    fn write_tasks(&self, tasks: &[Task]) -> AppResult<()> {
         let file_path = self.get_tasks_file();
         let content = serde_json::to_string_pretty(tasks)?;
         fs::write(file_path, content)?;
         Ok(())
     }
 
-    pub fn load_settings(&self) -> Result<Settings, Box<dyn std::error::Error>> {
+    pub fn load_settings(&self) -> AppResult<Settings> {
         let file_path = self.get_settings_file();
         
         if !file_path.exists() {
@@ -69,45 +163,217 @@ impl Storage {
         }
 
         let content = fs::read_to_string(file_path)?;
-        let settings: Settings = serde_json::from_str(&content)
-            .unwrap_or_else(|_| Settings::default());
+        let mut value: Value = match serde_json::from_str(&content) {
+            Ok(value) => value,
+            Err(_) => return Ok(Settings::default()),
+        };
+
+        let migrated = migrate_settings(&mut value);
+        let mut settings = settings_from_value(value);
+        settings.normalize();
+
+        // 旧版本的设置文件迁移后立即写回
+        if migrated {
+            self.save_settings(&settings)?;
+        }
         
         Ok(settings)
     }
 
-    pub fn save_settings(&self, settings: &Settings) -> Result<(), Box<dyn std::error::Error>> {
+    pub fn save_settings(&self, settings: &Settings) -> AppResult<()> {
         let file_path = self.get_settings_file();
-        let content = serde_json::to_string_pretty(settings)?;
+        let mut settings = settings.clone();
+        settings.version = SETTINGS_VERSION;
+        settings.normalize();
+        let content = serde_json::to_string_pretty(&settings)?;
         fs::write(file_path, content)?;
         Ok(())
     }
 
+    pub fn load_usage_sessions(&self) -> AppResult<Vec<UsageSession>> {
+        let file_path = self.get_usage_file();
+
+        if !file_path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let content = fs::read_to_string(&file_path)?;
+        let sessions: Vec<UsageSession> = serde_json::from_str(&content)
+            .map_err(|e| AppError::corrupt(file_path.to_string_lossy(), e))?;
+
+        Ok(sessions)
+    }
+
+    /// 保存使用记录，同时清理超出保留期限的旧记录
+    pub fn save_usage_sessions(&self, sessions: &mut Vec<UsageSession>) -> AppResult<()> {
+        let cutoff = Utc::now() - Duration::days(USAGE_RETENTION_DAYS);
+        sessions.retain(|s| s.end >= cutoff);
+        sessions.sort_by_key(|s| s.start);
+
+        let content = serde_json::to_string(sessions)?;
+        fs::write(self.get_usage_file(), content)?;
+        Ok(())
+    }
+
+    /// 新增或按 id 覆盖一段使用记录，进行中的时段会被反复写入
+    pub fn save_usage_session(&self, session: &UsageSession) -> AppResult<()> {
+        let mut sessions = self.load_usage_sessions()?;
+        sessions.retain(|s| s.id != session.id);
+        sessions.push(session.clone());
+        self.save_usage_sessions(&mut sessions)
+    }
+
+    /// 读取番茄钟状态，文件不存在或无法解析时使用默认设置
+    pub fn load_pomodoro(&self) -> AppResult<PomodoroState> {
+        let file_path = self.get_pomodoro_file();
+
+        if !file_path.exists() {
+            return Ok(PomodoroState::default());
+        }
+
+        let content = fs::read_to_string(file_path)?;
+        Ok(serde_json::from_str(&content).unwrap_or_default())
+    }
+
+    pub fn save_pomodoro(&self, state: &PomodoroState) -> AppResult<()> {
+        let content = serde_json::to_string_pretty(state)?;
+        fs::write(self.get_pomodoro_file(), content)?;
+        Ok(())
+    }
+
+    pub fn load_focus_sessions(&self) -> AppResult<Vec<FocusSession>> {
+        let file_path = self.get_focus_sessions_file();
+
+        if !file_path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let content = fs::read_to_string(&file_path)?;
+        let sessions: Vec<FocusSession> = serde_json::from_str(&content)
+            .map_err(|e| AppError::corrupt(file_path.to_string_lossy(), e))?;
+
+        Ok(sessions)
+    }
+
+    /// 保存一次完成的专注，并把专注时长累加到对应待办上
+    ///
+    /// 待办已被删除时只保存专注记录
+    pub fn record_focus_session(&self, session: &FocusSession) -> AppResult<()> {
+        let mut sessions = self.load_focus_sessions()?;
+        sessions.push(session.clone());
+        let content = serde_json::to_string(&sessions)?;
+        fs::write(self.get_focus_sessions_file(), content)?;
+
+        if let Some(task_id) = &session.task_id {
+            let mut tasks = self.load_tasks()?;
+            if let Some(task) = tasks.iter_mut().find(|t| &t.id == task_id) {
+                task.focus_minutes += session.minutes;
+                task.update();
+                self.save_tasks(&tasks)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// 读取同步设置，未设置过时返回默认值
+    pub fn load_sync_config(&self) -> AppResult<SyncConfig> {
+        let file_path = self.get_sync_config_file();
+
+        if !file_path.exists() {
+            return Ok(SyncConfig::default());
+        }
+
+        let content = fs::read_to_string(&file_path)?;
+        serde_json::from_str(&content).map_err(|e| AppError::corrupt(file_path.to_string_lossy(), e))
+    }
+
+    pub fn save_sync_config(&self, config: &SyncConfig) -> AppResult<()> {
+        let content = serde_json::to_string_pretty(config)?;
+        fs::write(self.get_sync_config_file(), content)?;
+        Ok(())
+    }
+
+    /// 读取同步的合并进度，无法解析时报错，避免重新合并产生重复的冲突
+    pub fn load_sync_state(&self) -> AppResult<SyncState> {
+        let file_path = self.get_sync_state_file();
+
+        if !file_path.exists() {
+            return Ok(SyncState::default());
+        }
+
+        let content = fs::read_to_string(&file_path)?;
+        serde_json::from_str(&content).map_err(|e| AppError::corrupt(file_path.to_string_lossy(), e))
+    }
+
+    pub fn save_sync_state(&self, state: &SyncState) -> AppResult<()> {
+        let content = serde_json::to_string(state)?;
+        fs::write(self.get_sync_state_file(), content)?;
+        Ok(())
+    }
+
+    /// 读取手动计时记录，正在计时的记录也保存在其中，应用异常退出后可以继续计时
+    pub fn load_time_entries(&self) -> AppResult<Vec<TimeEntry>> {
+        let file_path = self.get_time_entries_file();
+
+        if !file_path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let content = fs::read_to_string(&file_path)?;
+        let entries: Vec<TimeEntry> = serde_json::from_str(&content)
+            .map_err(|e| AppError::corrupt(file_path.to_string_lossy(), e))?;
+
+        Ok(entries)
+    }
+
+    pub fn save_time_entries(&self, entries: &mut [TimeEntry]) -> AppResult<()> {
+        entries.sort_by_key(|e| e.start);
+        let content = serde_json::to_string(entries)?;
+        fs::write(self.get_time_entries_file(), content)?;
+        Ok(())
+    }
+
+    /// 报告模板目录中的文件名，按名称排序
+    pub fn list_report_templates(&self) -> AppResult<Vec<String>> {
+        let dir = self.get_report_templates_dir();
+
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut names: Vec<String> = fs::read_dir(dir)?
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().is_file())
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .collect();
+        names.sort();
+        Ok(names)
+    }
+
+    /// 读取自定义报告模板，如 report.md，未自定义时返回 None
+    pub fn load_report_template(&self, file_name: &str) -> AppResult<Option<String>> {
+        let file_path = self.get_report_templates_dir().join(file_name);
+
+        if !file_path.exists() {
+            return Ok(None);
+        }
+
+        Ok(Some(fs::read_to_string(file_path)?))
+    }
+
     pub fn get_task_stats(&self, tasks: &[Task]) -> TaskStats {
-        let total = tasks.len();
-        let completed = tasks.iter().filter(|t| t.completed).count();
-        let pending = total - completed;
-        
-        let high_priority = tasks.iter()
-            .filter(|t| matches!(t.priority, Priority::High))
-            .count();
-        let medium_priority = tasks.iter()
-            .filter(|t| matches!(t.priority, Priority::Medium))
-            .count();
-        let low_priority = tasks.iter()
-            .filter(|t| matches!(t.priority, Priority::Low))
-            .count();
-
-        TaskStats {
-            total,
-            completed,
-            pending,
-            high_priority,
-            medium_priority,
-            low_priority,
-        }
-    }
-
-    pub fn add_task(&self, mut task: Task) -> Result<Task, Box<dyn std::error::Error>> {
+        compute_task_stats(tasks, &Local::now())
+    }
+
+    pub fn get_task_trend(&self, tasks: &[Task], start: NaiveDate, end: NaiveDate) -> Vec<TaskTrendPoint> {
+        compute_task_trend(tasks, start, end, &Local)
+    }
+
+    pub fn get_productivity_stats(&self, tasks: &[Task], start: NaiveDate, end: NaiveDate) -> ProductivityStats {
+        compute_productivity_stats(tasks, start, end, &Local::now())
+    }
+
+    pub fn add_task(&self, mut task: Task) -> AppResult<Task> {
         let mut tasks = self.load_tasks()?;
         task.update();
         tasks.push(task.clone());
@@ -115,7 +381,14 @@ impl Storage {
         Ok(task)
     }
 
-    pub fn update_task(&self, id: &str, updates: &crate::models::UpdateTaskRequest) -> Result<Option<Task>, Box<dyn std::error::Error>> {
+    /// 追加导入的待办，保留其原有的创建与完成时间
+    pub fn add_tasks(&self, new_tasks: &[Task]) -> AppResult<()> {
+        let mut tasks = self.load_tasks()?;
+        tasks.extend_from_slice(new_tasks);
+        self.save_tasks(&tasks)
+    }
+
+    pub fn update_task(&self, id: &str, updates: &crate::models::UpdateTaskRequest) -> AppResult<Task> {
         let mut tasks = self.load_tasks()?;
         
         if let Some(task) = tasks.iter_mut().find(|t| t.id == id) {
@@ -126,10 +399,10 @@ impl Storage {
                 task.description = Some(description.clone());
             }
             if let Some(completed) = updates.completed {
-                task.completed = completed;
+                task.set_completed(completed);
             }
-            if let Some(priority_str) = &updates.priority {
-                task.priority = Priority::from_string(priority_str);
+            if let Some(priority) = updates.priority {
+                task.priority = priority;
             }
             if let Some(due_date) = &updates.due_date {
                 task.due_date = Some(due_date.clone());
@@ -137,26 +410,194 @@ impl Storage {
             if let Some(attachments) = &updates.attachments {
                 task.attachments = Some(attachments.clone());
             }
+            if let Some(estimated_minutes) = updates.estimated_minutes {
+                task.estimated_minutes = Some(estimated_minutes).filter(|m| *m > 0);
+            }
             
             task.update();
             let updated_task = task.clone();
             self.save_tasks(&tasks)?;
-            Ok(Some(updated_task))
+            Ok(updated_task)
         } else {
-            Ok(None)
+            Err(AppError::not_found(Msg::EntityTask, id))
         }
     }
 
-    pub fn delete_task(&self, id: &str) -> Result<bool, Box<dyn std::error::Error>> {
+    pub fn delete_task(&self, id: &str) -> AppResult<()> {
         let mut tasks = self.load_tasks()?;
         let initial_len = tasks.len();
         tasks.retain(|t| t.id != id);
         
         if tasks.len() < initial_len {
             self.save_tasks(&tasks)?;
-            Ok(true)
+            Ok(())
         } else {
-            Ok(false)
+            Err(AppError::not_found(Msg::EntityTask, id))
         }
     }
-}
\ No newline at end of file
+}
+
+/// 将旧版本 settings.json 升级到当前结构，并修正无法解析的已知字段，返回是否发生了版本迁移
+///
+/// 不认识的字段保持原样
+fn migrate_settings(value: &mut Value) -> bool {
+    let Some(obj) = value.as_object_mut() else {
+        *value = Value::Object(serde_json::Map::new());
+        return true;
+    };
+
+    let version = obj.get("version").and_then(Value::as_u64).unwrap_or(1);
+
+    // v1：没有折叠模式、胶囊模式、透明度和边缘吸附字段，折叠模式由 isCollapsed 推断
+    if version < 2 && !obj.contains_key("collapseMode") {
+        let is_collapsed = obj.get("isCollapsed").and_then(Value::as_bool).unwrap_or(false);
+        obj.insert("collapseMode".to_string(), Value::from(if is_collapsed { "bar" } else { "expanded" }));
+    }
+
+    // 单个字段取值非法时只重置该字段，而不是整份设置
+    let theme = obj.get("theme")
+        .and_then(Value::as_str)
+        .map(|t| t.to_ascii_lowercase())
+        .filter(|t| matches!(t.as_str(), "light" | "dark" | "pink"))
+        .unwrap_or_else(|| "light".to_string());
+    obj.insert("theme".to_string(), Value::from(theme));
+
+    // 语言按前缀识别（如 en、en_GB），无法识别时使用中文
+    let locale = obj.get("locale")
+        .and_then(Value::as_str)
+        .map(|l| l.trim().to_ascii_lowercase())
+        .and_then(|l| if l.starts_with("en") { Some("en-US") } else if l.starts_with("zh") { Some("zh-CN") } else { None })
+        .unwrap_or("zh-CN");
+    obj.insert("locale".to_string(), Value::from(locale));
+
+    let collapse_valid = obj.get("collapseMode")
+        .and_then(Value::as_str)
+        .is_some_and(|m| matches!(m, "expanded" | "bar" | "floating"));
+    if !collapse_valid {
+        obj.remove("collapseMode");
+    }
+
+    if let Some(level) = obj.get("transparentLevel").and_then(Value::as_f64) {
+        obj.insert("transparentLevel".to_string(), Value::from(level.round().clamp(0.0, 100.0) as u8));
+    }
+
+    let edge_valid = obj.get("edgePosition")
+        .and_then(Value::as_str)
+        .is_some_and(|p| matches!(p, "left" | "right"));
+    if !edge_valid {
+        obj.remove("edgePosition");
+    }
+
+    if obj.get("apiEnabled").is_some_and(|v| !v.is_boolean()) {
+        obj.remove("apiEnabled");
+    }
+    let port_valid = obj.get("apiPort")
+        .and_then(Value::as_u64)
+        .is_some_and(|p| (1..=u16::MAX as u64).contains(&p));
+    if !port_valid {
+        obj.remove("apiPort");
+    }
+
+    if obj.get("quickAddShortcut").is_some_and(|v| !v.is_string()) {
+        obj.remove("quickAddShortcut");
+    }
+    if obj.get("launchAtLogin").is_some_and(|v| !v.is_boolean()) {
+        obj.remove("launchAtLogin");
+    }
+
+    obj.insert("version".to_string(), Value::from(SETTINGS_VERSION));
+    version < SETTINGS_VERSION as u64
+}
+
+/// 解析设置；类型不符的字段（如手动修改过或由新版本写入）单独使用默认值，其余字段与未知字段照常保留
+fn settings_from_value(value: Value) -> Settings {
+    if let Ok(settings) = serde_json::from_value(value.clone()) {
+        return settings;
+    }
+    let Value::Object(fields) = value else {
+        return Settings::default();
+    };
+
+    let mut accepted = match serde_json::to_value(Settings::default()) {
+        Ok(Value::Object(defaults)) => defaults,
+        _ => serde_json::Map::new(),
+    };
+    for (key, field) in fields {
+        let mut candidate = accepted.clone();
+        candidate.insert(key.clone(), field.clone());
+        if serde_json::from_value::<Settings>(Value::Object(candidate)).is_ok() {
+            accepted.insert(key, field);
+        } else {
+            log::warn!("Ignoring invalid setting {}: {}", key, field);
+        }
+    }
+    serde_json::from_value(Value::Object(accepted)).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::i18n::Locale;
+    use crate::models::Theme;
+
+    fn settings_from(value: Value) -> Settings {
+        let mut value = value;
+        migrate_settings(&mut value);
+        settings_from_value(value)
+    }
+
+    #[test]
+    fn unknown_locale_does_not_reset_other_settings() {
+        let settings = settings_from(serde_json::json!({ "version": SETTINGS_VERSION, "theme": "dark", "locale": "fr-FR" }));
+        assert_eq!(settings.locale, Locale::ZhCn);
+        assert_eq!(settings.theme, Theme::Dark);
+    }
+
+    #[test]
+    fn locale_variants_are_normalized() {
+        assert_eq!(settings_from(serde_json::json!({ "locale": "en_GB" })).locale, Locale::EnUs);
+        assert_eq!(settings_from(serde_json::json!({ "locale": "EN-us" })).locale, Locale::EnUs);
+        assert_eq!(settings_from(serde_json::json!({ "locale": "zh" })).locale, Locale::ZhCn);
+        assert_eq!(settings_from(serde_json::json!({ "locale": 1 })).locale, Locale::ZhCn);
+    }
+
+    #[test]
+    fn invalid_field_falls_back_alone() {
+        let defaults = Settings::default();
+        let settings = settings_from(serde_json::json!({
+            "version": SETTINGS_VERSION,
+            "theme": "dark",
+            "transparentLevel": "x",
+            "notifications": "yes",
+            "username": 5,
+            "isPinned": true,
+            "quickAddShortcut": "Alt+Space",
+            "futureOption": { "enabled": true },
+        }));
+        assert_eq!(settings.transparent_level, defaults.transparent_level);
+        assert_eq!(settings.notifications, defaults.notifications);
+        assert_eq!(settings.username, None);
+        assert_eq!(settings.theme, Theme::Dark);
+        assert!(settings.is_pinned);
+        assert_eq!(settings.quick_add_shortcut, "Alt+Space");
+        assert_eq!(settings.extra.get("futureOption"), Some(&serde_json::json!({ "enabled": true })));
+    }
+
+    #[test]
+    fn load_settings_keeps_valid_fields_of_a_mistyped_file() {
+        let dir = std::env::temp_dir().join(format!("semidone-settings-{}", Uuid::new_v4()));
+        let storage = Storage::with_data_dir(dir.clone()).unwrap();
+        let content = serde_json::json!({ "version": SETTINGS_VERSION, "isPinned": true, "transparentLevel": "x", "futureOption": 1 });
+        fs::write(storage.get_settings_file(), content.to_string()).unwrap();
+
+        let settings = storage.load_settings().unwrap();
+        storage.save_settings(&settings).unwrap();
+        let saved: Value = serde_json::from_str(&fs::read_to_string(storage.get_settings_file()).unwrap()).unwrap();
+        let _ = fs::remove_dir_all(dir);
+
+        assert!(settings.is_pinned);
+        assert_eq!(saved["isPinned"], true);
+        assert_eq!(saved["futureOption"], 1);
+        assert_eq!(saved["transparentLevel"], Settings::default().transparent_level);
+    }
+}
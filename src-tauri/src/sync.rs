@@ -0,0 +1,947 @@
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, SystemTime};
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use tauri::{AppHandle, Manager};
+use uuid::Uuid;
+use crate::commands::notify_tasks_changed;
+use crate::error::{AppError, AppResult};
+use crate::i18n::{fill, Msg};
+use crate::models::{SyncConflict, SyncDevice, SyncStatus, Task};
+use crate::storage::Storage;
+
+// 共享文件夹中存放变更日志的子目录，每台设备只追加写自己的 <设备 id>.jsonl，云盘同步时不会互相覆盖
+const SYNC_DIR: &str = "semidone-sync";
+const LOG_EXTENSION: &str = "jsonl";
+
+// 表示待办已被删除的字段
+pub const DELETED_FIELD: &str = "_deleted";
+
+// 本版本待办的字段；新版本增加的字段本机不认识，不能当作被清空
+const TASK_FIELDS: &[&str] = &[
+    "title", "description", "completed", "priority", "dueDate", "createdAt", "updatedAt",
+    "completedAt", "attachments", "estimatedMinutes", "focusMinutes",
+];
+
+// 每次修改都会变化的字段，按最后写入的保留，不记为冲突
+const UNTRACKED_CONFLICT_FIELDS: &[&str] = &["updatedAt"];
+
+// 检查其他设备日志的间隔
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// 本机的同步设置，保存在数据目录中
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncConfig {
+    #[serde(default)]
+    pub device_id: String,
+    #[serde(default)]
+    pub device_name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub folder: Option<PathBuf>,
+}
+
+impl SyncConfig {
+    fn log_dir(&self) -> Option<PathBuf> {
+        self.folder.as_ref().map(|folder| folder.join(SYNC_DIR))
+    }
+}
+
+/// 字段的写入时间与设备；先比较时间，相同时比较设备 id，各设备合并的结果一致
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Stamp {
+    at: DateTime<Utc>,
+    device: String,
+}
+
+/// 副本中一个字段的当前值
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldEntry {
+    value: Value,
+    stamp: Stamp,
+    // 写入的设备当时所知的版本，用于判断其他设备的修改是否与它同时发生
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    base: Option<Stamp>,
+}
+
+type TaskEntries = BTreeMap<String, FieldEntry>;
+
+/// 合并进度与所有待办按字段保存的副本，保存在数据目录中
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncState {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    last_sync_at: Option<DateTime<Utc>>,
+    // 其他设备日志的合并进度
+    #[serde(default)]
+    logs: BTreeMap<String, LogProgress>,
+    #[serde(default)]
+    devices: BTreeMap<String, SyncDevice>,
+    #[serde(default)]
+    replica: BTreeMap<String, TaskEntries>,
+    #[serde(default)]
+    conflicts: Vec<SyncConflict>,
+}
+
+/// 一台设备的日志已合并到的位置
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LogProgress {
+    // 已合并部分的字节数，下次从这里继续读取
+    offset: u64,
+    // 已合并的最新一条修改的时间，日志被替换后从头读取时跳过此前的修改
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    latest: Option<DateTime<Utc>>,
+}
+
+/// 变更日志中的一行：一台设备对一个待办若干字段的修改
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ChangeRecord {
+    task_id: String,
+    at: DateTime<Utc>,
+    device: String,
+    device_name: String,
+    changes: BTreeMap<String, FieldChange>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct FieldChange {
+    value: Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    base: Option<Stamp>,
+}
+
+fn default_device_name() -> String {
+    std::env::var("COMPUTERNAME")
+        .or_else(|_| std::env::var("HOSTNAME"))
+        .ok()
+        .or_else(|| fs::read_to_string("/etc/hostname").ok())
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| crate::APP_NAME.to_string())
+}
+
+fn format_time(at: &DateTime<Utc>) -> String {
+    at.to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+fn is_deleted(entries: &TaskEntries) -> bool {
+    entries.get(DELETED_FIELD).is_some_and(|entry| entry.value == Value::Bool(true))
+}
+
+fn task_fields(task: &Task) -> AppResult<Map<String, Value>> {
+    match serde_json::to_value(task)? {
+        Value::Object(mut fields) => {
+            fields.remove("id");
+            Ok(fields)
+        }
+        _ => Err(AppError::Internal(format!("unexpected task JSON: {}", task.id))),
+    }
+}
+
+/// 按副本中的字段还原待办，缺少必需字段（如只收到了其他设备的修改）时返回 None
+fn replica_task(id: &str, entries: &TaskEntries) -> Option<Task> {
+    let mut fields = Map::new();
+    fields.insert("id".to_string(), Value::from(id));
+    for (field, entry) in entries {
+        if field != DELETED_FIELD && !entry.value.is_null() {
+            fields.insert(field.clone(), entry.value.clone());
+        }
+    }
+    serde_json::from_value(Value::Object(fields)).ok()
+}
+
+/// 比较本机待办与副本，得到本机的修改
+///
+/// 加入同步文件夹时副本中有而本机没有的待办是其他设备的，不当作本机删除
+fn local_changes(config: &SyncConfig, state: &SyncState, tasks: &[Task], now: DateTime<Utc>, joining: bool) -> AppResult<Vec<ChangeRecord>> {
+    let record = |task_id: &str, changes| ChangeRecord {
+        task_id: task_id.to_string(),
+        at: now,
+        device: config.device_id.clone(),
+        device_name: config.device_name.clone(),
+        changes,
+    };
+    let no_entries = TaskEntries::new();
+    let mut records = Vec::new();
+
+    for task in tasks {
+        let entries = state.replica.get(&task.id).unwrap_or(&no_entries);
+        let fields = task_fields(task)?;
+        let mut changes = BTreeMap::new();
+
+        // 本机恢复了其他设备删除的待办
+        if is_deleted(entries) {
+            let base = entries.get(DELETED_FIELD).map(|e| e.stamp.clone());
+            changes.insert(DELETED_FIELD.to_string(), FieldChange { value: Value::Bool(false), base });
+        }
+        let keys: BTreeSet<&str> = fields.keys().map(String::as_str)
+            .chain(entries.keys().map(String::as_str).filter(|k| TASK_FIELDS.contains(k)))
+            .collect();
+        for key in keys {
+            let value = fields.get(key).cloned().unwrap_or(Value::Null);
+            let current = entries.get(key);
+            if current.map_or(&Value::Null, |e| &e.value) != &value {
+                changes.insert(key.to_string(), FieldChange { value, base: current.map(|e| e.stamp.clone()) });
+            }
+        }
+
+        if !changes.is_empty() {
+            records.push(record(&task.id, changes));
+        }
+    }
+
+    if !joining {
+        let present: HashSet<&str> = tasks.iter().map(|t| t.id.as_str()).collect();
+        for (id, entries) in &state.replica {
+            if present.contains(id.as_str()) || is_deleted(entries) {
+                continue;
+            }
+            // 删除时所知的最新版本，之后其他设备的修改会记为冲突
+            let base = entries.values().map(|e| &e.stamp).max().cloned();
+            let changes = BTreeMap::from([(DELETED_FIELD.to_string(), FieldChange { value: Value::Bool(true), base })]);
+            records.push(record(id, changes));
+        }
+    }
+
+    Ok(records)
+}
+
+/// 追加到本机的日志；共享文件夹不可用时返回错误，副本保持不变，下次保存或合并时重新比较
+fn append_records(config: &SyncConfig, records: &[ChangeRecord]) -> AppResult<()> {
+    let (Some(folder), Some(dir)) = (&config.folder, config.log_dir()) else {
+        return Err(AppError::invalid_input(Msg::SyncNotEnabled.text()));
+    };
+    if records.is_empty() {
+        return Ok(());
+    }
+    if !folder.is_dir() {
+        return Err(AppError::invalid_input(fill(Msg::SyncFolderMissing.text(), &[&folder.display()])));
+    }
+    fs::create_dir_all(&dir)?;
+
+    let mut lines = String::new();
+    for record in records {
+        lines.push_str(&serde_json::to_string(record)?);
+        lines.push('\n');
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(dir.join(format!("{}.{}", config.device_id, LOG_EXTENSION)))?;
+    file.write_all(lines.as_bytes())?;
+    Ok(())
+}
+
+fn apply_own(state: &mut SyncState, record: &ChangeRecord) {
+    let stamp = Stamp { at: record.at, device: record.device.clone() };
+    let entries = state.replica.entry(record.task_id.clone()).or_default();
+    for (field, change) in &record.changes {
+        entries.insert(field.clone(), FieldEntry { value: change.value.clone(), stamp: stamp.clone(), base: change.base.clone() });
+    }
+}
+
+fn device_name(config: &SyncConfig, state: &SyncState, device: &str) -> String {
+    if device == config.device_id {
+        return config.device_name.clone();
+    }
+    state.devices.get(device).map_or_else(|| device.to_string(), |d| d.name.clone())
+}
+
+/// 按字段合并一条其他设备的变更，返回副本中的值是否改变
+///
+/// 写入方已知当前版本时直接采用；否则两次修改互不知情，保留时间较晚的，值不同时记为冲突
+fn apply_remote(config: &SyncConfig, state: &mut SyncState, record: &ChangeRecord) -> bool {
+    let device = state.devices.entry(record.device.clone()).or_insert_with(|| SyncDevice {
+        id: record.device.clone(),
+        name: record.device_name.clone(),
+        changes: 0,
+        last_change_at: None,
+    });
+    device.name = record.device_name.clone();
+    device.changes += 1;
+    device.last_change_at = Some(format_time(&record.at));
+
+    let stamp = Stamp { at: record.at, device: record.device.clone() };
+    let entries = state.replica.entry(record.task_id.clone()).or_default();
+    let mut conflicts: Vec<(String, FieldEntry, FieldEntry)> = Vec::new();
+    // 写入方处理冲突后的修改，之前记录的冲突不再需要处理
+    let mut superseded: Vec<&str> = Vec::new();
+    let mut changed = false;
+
+    let deleting = record.changes.get(DELETED_FIELD).is_some_and(|c| c.value == Value::Bool(true));
+    if deleting && !is_deleted(entries) {
+        // 删除的设备不知道的其他修改：待办仍被删除，冲突中可以恢复
+        let base = record.changes.get(DELETED_FIELD).and_then(|c| c.base.as_ref());
+        let unseen = entries.iter()
+            .filter(|(field, e)| *field != DELETED_FIELD && e.stamp.device != stamp.device && base.map_or(true, |b| e.stamp > *b))
+            .map(|(_, e)| e.stamp.clone())
+            .max();
+        if let Some(edited) = unseen {
+            let kept = FieldEntry { value: Value::Bool(true), stamp: stamp.clone(), base: None };
+            conflicts.push((DELETED_FIELD.to_string(), kept, FieldEntry { value: Value::Bool(false), stamp: edited, base: None }));
+        }
+    } else if let Some(deleted) = entries.get(DELETED_FIELD).filter(|_| is_deleted(entries)) {
+        // 修改了已被删除的待办，删除时并不知道这次修改
+        let restoring = record.changes.contains_key(DELETED_FIELD);
+        if !restoring && deleted.stamp.device != stamp.device && deleted.base.as_ref().map_or(true, |b| stamp > *b) {
+            let other = FieldEntry { value: Value::Bool(false), stamp: stamp.clone(), base: None };
+            conflicts.push((DELETED_FIELD.to_string(), deleted.clone(), other));
+        }
+    }
+
+    for (field, change) in &record.changes {
+        let incoming = FieldEntry { value: change.value.clone(), stamp: stamp.clone(), base: change.base.clone() };
+        let Some(current) = entries.get(field) else {
+            entries.insert(field.clone(), incoming);
+            changed = true;
+            continue;
+        };
+        if current.stamp == stamp {
+            continue;
+        }
+
+        let (remote_wins, concurrent) = if change.base.as_ref().is_some_and(|b| *b >= current.stamp) {
+            (true, false)
+        } else if current.stamp.device == stamp.device {
+            (stamp > current.stamp, false)
+        } else {
+            (stamp > current.stamp, true)
+        };
+
+        if concurrent && current.value != incoming.value && !UNTRACKED_CONFLICT_FIELDS.contains(&field.as_str()) {
+            let (kept, other) = if remote_wins { (incoming.clone(), current.clone()) } else { (current.clone(), incoming.clone()) };
+            conflicts.push((field.clone(), kept, other));
+        }
+        if remote_wins && !concurrent {
+            superseded.push(field);
+        }
+        if remote_wins {
+            changed |= current.value != incoming.value;
+            entries.insert(field.clone(), incoming);
+        }
+    }
+
+    state.conflicts.retain(|c| !(c.task_id == record.task_id && superseded.contains(&c.field.as_str())));
+    for (field, kept, other) in conflicts {
+        let title = state.replica.get(&record.task_id)
+            .and_then(|entries| entries.get("title"))
+            .and_then(|entry| entry.value.as_str())
+            .unwrap_or_default()
+            .to_string();
+        let conflict = SyncConflict {
+            id: Uuid::new_v4().to_string(),
+            task_id: record.task_id.clone(),
+            task_title: title,
+            field: field.clone(),
+            kept_value: kept.value.to_string(),
+            kept_device: device_name(config, state, &kept.stamp.device),
+            kept_at: format_time(&kept.stamp.at),
+            other_value: other.value.to_string(),
+            other_device: device_name(config, state, &other.stamp.device),
+            other_at: format_time(&other.stamp.at),
+        };
+        // 同一字段只保留最近一次冲突
+        state.conflicts.retain(|c| !(c.task_id == record.task_id && c.field == field));
+        state.conflicts.push(conflict);
+    }
+
+    changed
+}
+
+/// 其他设备的日志文件，按设备 id 排序
+fn remote_logs(config: &SyncConfig) -> AppResult<Vec<(String, PathBuf)>> {
+    let Some(dir) = config.log_dir().filter(|dir| dir.is_dir()) else {
+        return Ok(Vec::new());
+    };
+    let mut logs: Vec<(String, PathBuf)> = fs::read_dir(&dir)?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == LOG_EXTENSION))
+        .filter_map(|path| Some((path.file_stem()?.to_str()?.to_string(), path)))
+        .filter(|(device, _)| *device != config.device_id)
+        .collect();
+    logs.sort();
+    Ok(logs)
+}
+
+/// 一台设备日志中新增的完整行
+struct LogChunk {
+    path: PathBuf,
+    // 读取时的合并进度，合并前进度已变化时需要重新读取
+    from: u64,
+    end: u64,
+    // 日志变短说明文件被替换过，从头读取
+    replay: bool,
+    records: Vec<ChangeRecord>,
+}
+
+/// 从上次合并到的位置读取日志，只读取新增的部分
+fn read_log(device: &str, path: &Path, progress: &LogProgress) -> AppResult<LogChunk> {
+    let mut file = File::open(path)?;
+    let len = file.metadata()?.len();
+    let replay = progress.offset > len;
+    let start = if replay { 0 } else { progress.offset };
+
+    let mut bytes = Vec::new();
+    file.seek(SeekFrom::Start(start))?;
+    file.read_to_end(&mut bytes)?;
+    // 云盘可能只同步了文件的一部分，不完整的最后一行留到下次
+    let complete = bytes.iter().rposition(|b| *b == b'\n').map_or(0, |i| i + 1);
+
+    let mut records = Vec::new();
+    for line in String::from_utf8_lossy(&bytes[..complete]).lines() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str::<ChangeRecord>(line) {
+            Ok(record) if record.device == device => records.push(record),
+            Ok(_) => log::warn!("Skipping sync record from another device in {}", path.display()),
+            Err(e) => log::warn!("Skipping malformed sync record in {}: {}", path.display(), e),
+        }
+    }
+
+    Ok(LogChunk {
+        path: path.to_path_buf(),
+        from: progress.offset,
+        end: start + complete as u64,
+        replay,
+        records,
+    })
+}
+
+/// 读取其他设备日志中尚未合并的部分，不修改合并进度
+fn read_remote(config: &SyncConfig, state: &SyncState) -> AppResult<Vec<LogChunk>> {
+    remote_logs(config)?
+        .into_iter()
+        .map(|(device, path)| {
+            let progress = state.logs.get(&device).cloned().unwrap_or_default();
+            read_log(&device, &path, &progress)
+        })
+        .collect()
+}
+
+/// 合并其他设备日志中新增的行，返回副本中有变化的待办 id
+///
+/// 优先使用预先读取的部分；读取后进度已被其他合并推进时重新读取
+fn pull_remote(config: &SyncConfig, state: &mut SyncState, mut prefetched: Vec<LogChunk>) -> AppResult<HashSet<String>> {
+    let mut touched = HashSet::new();
+
+    for (device, path) in remote_logs(config)? {
+        let progress = state.logs.get(&device).cloned().unwrap_or_default();
+        let chunk = match prefetched.iter().position(|c| c.path == path && c.from == progress.offset) {
+            Some(index) => prefetched.swap_remove(index),
+            None => read_log(&device, &path, &progress)?,
+        };
+
+        let mut latest = progress.latest;
+        for record in chunk.records {
+            // 从头读取被替换的日志时，已合并过的修改不再计入
+            if chunk.replay && progress.latest.is_some_and(|at| record.at <= at) {
+                continue;
+            }
+            latest = latest.max(Some(record.at));
+            if apply_remote(config, state, &record) {
+                touched.insert(record.task_id);
+            }
+        }
+        state.logs.insert(device, LogProgress { offset: chunk.end, latest });
+    }
+
+    Ok(touched)
+}
+
+/// 用副本更新有变化的待办：保持原有顺序，已删除的移除，其他设备新建的按创建时间追加
+fn materialize(state: &SyncState, tasks: Vec<Task>, touched: &HashSet<String>) -> Vec<Task> {
+    let local_ids: HashSet<String> = tasks.iter().map(|t| t.id.clone()).collect();
+
+    let mut merged: Vec<Task> = tasks.into_iter()
+        .filter_map(|task| match state.replica.get(&task.id) {
+            Some(entries) if touched.contains(&task.id) => {
+                if is_deleted(entries) {
+                    None
+                } else {
+                    Some(replica_task(&task.id, entries).unwrap_or(task))
+                }
+            }
+            _ => Some(task),
+        })
+        .collect();
+
+    let mut added: Vec<Task> = touched.iter()
+        .filter(|id| !local_ids.contains(*id))
+        .filter_map(|id| state.replica.get(id).filter(|e| !is_deleted(e)).and_then(|e| replica_task(id, e)))
+        .collect();
+    added.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+    merged.extend(added);
+    merged
+}
+
+/// 记录本机修改，`Storage::save_tasks` 写入待办后调用；未启用同步时直接返回
+pub fn record_local_changes(storage: &Storage, tasks: &[Task]) -> AppResult<()> {
+    let config = storage.load_sync_config()?;
+    if config.folder.is_none() {
+        return Ok(());
+    }
+
+    let mut state = storage.load_sync_state()?;
+    let records = local_changes(&config, &state, tasks, Utc::now(), false)?;
+    if records.is_empty() {
+        return Ok(());
+    }
+    append_records(&config, &records)?;
+    for record in &records {
+        apply_own(&mut state, record);
+    }
+    storage.save_sync_state(&state)
+}
+
+/// 先补记本机的修改，再合并其他设备的日志；加入文件夹时顺序相反，本机与其他设备不同的值作为新的修改
+///
+/// 返回待办是否被其他设备的修改改变
+fn merge(storage: &Storage, config: &SyncConfig, state: &mut SyncState, joining: bool, prefetched: Vec<LogChunk>) -> AppResult<bool> {
+    let tasks = storage.load_tasks()?;
+    let now = Utc::now();
+
+    // 加入时合并进度为空，不使用预先读取的部分
+    let mut touched = if joining { pull_remote(config, state, Vec::new())? } else { HashSet::new() };
+    let records = local_changes(config, state, &tasks, now, joining)?;
+    append_records(config, &records)?;
+    for record in &records {
+        apply_own(state, record);
+    }
+    if !joining {
+        touched = pull_remote(config, state, prefetched)?;
+    }
+
+    let changed = !touched.is_empty();
+    if changed {
+        storage.replace_tasks_from_sync(&materialize(state, tasks, &touched))?;
+    }
+    state.last_sync_at = Some(now);
+    storage.save_sync_state(state)?;
+    Ok(changed)
+}
+
+/// 与共享文件夹同步，返回待办是否有变化
+pub fn sync(storage: &Storage) -> AppResult<bool> {
+    merge_prefetched(storage, Vec::new())
+}
+
+fn merge_prefetched(storage: &Storage, prefetched: Vec<LogChunk>) -> AppResult<bool> {
+    let config = storage.load_sync_config()?;
+    if config.folder.is_none() {
+        return Err(AppError::invalid_input(Msg::SyncNotEnabled.text()));
+    }
+    let mut state = storage.load_sync_state()?;
+    merge(storage, &config, &mut state, false, prefetched)
+}
+
+/// 与 `sync` 相同，但读取其他设备的日志时不持有存储锁，只在合并时加锁，日志较大时不会阻塞其他命令
+pub fn sync_shared(storage: &Mutex<Storage>) -> AppResult<bool> {
+    let (config, state) = {
+        let storage = storage.lock()?;
+        (storage.load_sync_config()?, storage.load_sync_state()?)
+    };
+    let prefetched = read_remote(&config, &state)?;
+
+    let storage = storage.lock()?;
+    merge_prefetched(&storage, prefetched)
+}
+
+/// 设置同步文件夹与本机名称，返回待办是否有变化
+///
+/// 更换文件夹时重新加入：之前的合并进度与冲突作废，本机待办与文件夹中已有的待办合并；
+/// folder 为 None 时关闭同步
+pub fn configure(storage: &Storage, folder: Option<PathBuf>, device_name: Option<String>) -> AppResult<bool> {
+    let mut config = storage.load_sync_config()?;
+    if config.device_id.is_empty() {
+        config.device_id = Uuid::new_v4().to_string();
+    }
+    if let Some(name) = device_name.map(|n| n.trim().to_string()).filter(|n| !n.is_empty()) {
+        config.device_name = name;
+    }
+    if config.device_name.is_empty() {
+        config.device_name = default_device_name();
+    }
+
+    if folder == config.folder {
+        storage.save_sync_config(&config)?;
+        return Ok(false);
+    }
+    if let Some(folder) = &folder {
+        if !folder.is_dir() {
+            return Err(AppError::invalid_input(fill(Msg::SyncFolderMissing.text(), &[&folder.display()])));
+        }
+    }
+
+    config.folder = folder;
+    storage.save_sync_config(&config)?;
+    let mut state = SyncState::default();
+    if config.folder.is_none() {
+        storage.save_sync_state(&state)?;
+        return Ok(false);
+    }
+    merge(storage, &config, &mut state, true, Vec::new())
+}
+
+pub fn status(storage: &Storage) -> AppResult<SyncStatus> {
+    let config = storage.load_sync_config()?;
+    let state = storage.load_sync_state()?;
+    Ok(SyncStatus {
+        folder: config.folder.as_ref().map(|f| f.to_string_lossy().into_owned()),
+        device_id: config.device_id.clone(),
+        device_name: if config.device_name.is_empty() { default_device_name() } else { config.device_name.clone() },
+        devices: state.devices.values().cloned().collect(),
+        last_sync_at: state.last_sync_at.as_ref().map(format_time),
+        conflicts: state.conflicts.len(),
+    })
+}
+
+pub fn conflicts(storage: &Storage) -> AppResult<Vec<SyncConflict>> {
+    Ok(storage.load_sync_state()?.conflicts)
+}
+
+/// 处理冲突：use_other 为 true 时改用被覆盖的值，作为本机的新修改同步到其他设备；返回待办是否有变化
+pub fn resolve_conflict(storage: &Storage, id: &str, use_other: bool) -> AppResult<bool> {
+    let mut state = storage.load_sync_state()?;
+    let index = state.conflicts.iter()
+        .position(|c| c.id == id)
+        .ok_or_else(|| AppError::not_found(Msg::EntitySyncConflict, id))?;
+    let conflict = state.conflicts.remove(index);
+    storage.save_sync_state(&state)?;
+    if !use_other {
+        return Ok(false);
+    }
+
+    let other: Value = serde_json::from_str(&conflict.other_value)?;
+    let mut tasks = storage.load_tasks()?;
+    let task_id = conflict.task_id.as_str();
+
+    if conflict.field == DELETED_FIELD {
+        if other == Value::Bool(true) {
+            tasks.retain(|t| t.id != task_id);
+        } else if !tasks.iter().any(|t| t.id == task_id) {
+            // 恢复被删除的待办，包含删除后其他设备的修改
+            let task = state.replica.get(task_id)
+                .and_then(|entries| replica_task(task_id, entries))
+                .ok_or_else(|| AppError::not_found(Msg::EntityTask, task_id))?;
+            tasks.push(task);
+        }
+    } else {
+        let task = tasks.iter_mut()
+            .find(|t| t.id == task_id)
+            .ok_or_else(|| AppError::not_found(Msg::EntityTask, task_id))?;
+        let mut fields = task_fields(task)?;
+        fields.insert("id".to_string(), Value::from(task_id));
+        if other.is_null() {
+            fields.remove(&conflict.field);
+        } else {
+            fields.insert(conflict.field.clone(), other);
+        }
+        *task = serde_json::from_value(Value::Object(fields))?;
+    }
+
+    storage.save_tasks(&tasks)?;
+    Ok(true)
+}
+
+/// 其他设备日志的文件名、大小与修改时间，变化时才需要合并
+fn log_signature(config: &SyncConfig) -> Vec<(String, u64, Option<SystemTime>)> {
+    remote_logs(config)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(device, path)| {
+            let metadata = fs::metadata(&path).ok();
+            let len = metadata.as_ref().map_or(0, |m| m.len());
+            (device, len, metadata.and_then(|m| m.modified().ok()))
+        })
+        .collect()
+}
+
+/// 启动后合并一次，之后定期检查共享文件夹，其他设备的日志有变化时合并并通知前端
+pub fn start_watcher(app: AppHandle) {
+    thread::spawn(move || {
+        let mut last_signature = None;
+        loop {
+            let config = {
+                let state = app.state::<Mutex<Storage>>();
+                let config = state.lock().map_err(AppError::from).and_then(|storage| storage.load_sync_config());
+                config.ok().filter(|c| c.folder.is_some())
+            };
+
+            match config {
+                Some(config) => {
+                    let signature = Some((config.folder.clone(), log_signature(&config)));
+                    if signature != last_signature {
+                        match sync_shared(&app.state::<Mutex<Storage>>()) {
+                            Ok(changed) => {
+                                if changed {
+                                    notify_tasks_changed(&app);
+                                }
+                                last_signature = signature;
+                            }
+                            Err(e) => log::warn!("Failed to sync: {}", e),
+                        }
+                    }
+                }
+                None => last_signature = None,
+            }
+
+            thread::sleep(POLL_INTERVAL);
+        }
+    });
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Priority, UpdateTaskRequest};
+
+    /// 两台设备各自的数据目录与共享的同步文件夹，结束时删除
+    struct Fixture {
+        root: PathBuf,
+    }
+
+    impl Fixture {
+        fn new() -> Self {
+            let root = std::env::temp_dir().join(format!("semidone-sync-test-{}", Uuid::new_v4()));
+            fs::create_dir_all(root.join("shared")).unwrap();
+            Self { root }
+        }
+
+        fn shared(&self) -> PathBuf {
+            self.root.join("shared")
+        }
+
+        fn device(&self, name: &str) -> Storage {
+            Storage::with_data_dir(self.root.join(name)).unwrap()
+        }
+
+        fn join(&self, name: &str) -> Storage {
+            let storage = self.device(name);
+            configure(&storage, Some(self.shared()), Some(name.to_string())).unwrap();
+            storage
+        }
+
+        fn log_path(&self, storage: &Storage) -> PathBuf {
+            let device_id = storage.load_sync_config().unwrap().device_id;
+            self.shared().join(SYNC_DIR).join(format!("{}.{}", device_id, LOG_EXTENSION))
+        }
+    }
+
+    impl Drop for Fixture {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.root);
+        }
+    }
+
+    // 让前后两次修改的时间戳不同
+    fn pause() {
+        std::thread::sleep(Duration::from_millis(5));
+    }
+
+    fn add(storage: &Storage, title: &str) -> Task {
+        storage.add_task(Task::new(title.to_string(), None, None, None, None)).unwrap()
+    }
+
+    fn edit(storage: &Storage, id: &str, updates: UpdateTaskRequest) {
+        pause();
+        storage.update_task(id, &updates).unwrap();
+    }
+
+    fn find(storage: &Storage, id: &str) -> Option<Task> {
+        storage.load_tasks().unwrap().into_iter().find(|t| t.id == id)
+    }
+
+    fn title(value: &str) -> UpdateTaskRequest {
+        UpdateTaskRequest { title: Some(value.to_string()), ..Default::default() }
+    }
+
+    /// A 先加入并带着一个待办，B 随后加入
+    fn two_devices(fixture: &Fixture) -> (Storage, Storage, Task) {
+        let a = fixture.device("a");
+        let task = add(&a, "shared");
+        configure(&a, Some(fixture.shared()), Some("a".to_string())).unwrap();
+        let b = fixture.join("b");
+        sync(&a).unwrap();
+        (a, b, task)
+    }
+
+    #[test]
+    fn joining_merges_tasks_from_both_devices() {
+        let fixture = Fixture::new();
+        let a = fixture.device("a");
+        let b = fixture.device("b");
+        let from_a = add(&a, "from a");
+        let from_b = add(&b, "from b");
+
+        assert!(!configure(&a, Some(fixture.shared()), Some("a".to_string())).unwrap());
+        assert!(configure(&b, Some(fixture.shared()), Some("b".to_string())).unwrap());
+        assert!(sync(&a).unwrap());
+
+        for storage in [&a, &b] {
+            assert!(find(storage, &from_a.id).is_some());
+            assert!(find(storage, &from_b.id).is_some());
+            assert!(conflicts(storage).unwrap().is_empty());
+        }
+        // 没有新的修改时再次同步不改变待办
+        assert!(!sync(&a).unwrap());
+        assert!(!sync(&b).unwrap());
+    }
+
+    #[test]
+    fn edits_to_different_fields_merge_without_conflict() {
+        let fixture = Fixture::new();
+        let (a, b, task) = two_devices(&fixture);
+
+        edit(&a, &task.id, title("renamed on a"));
+        edit(&b, &task.id, UpdateTaskRequest { priority: Some(Priority::High), ..Default::default() });
+        sync(&a).unwrap();
+        // 另一台设备通过不持有存储锁读取日志的方式同步
+        sync_shared(&Mutex::new(b)).unwrap();
+        let b = fixture.device("b");
+
+        for storage in [&a, &b] {
+            let merged = find(storage, &task.id).unwrap();
+            assert_eq!(merged.title, "renamed on a");
+            assert_eq!(merged.priority, Priority::High);
+            assert!(conflicts(storage).unwrap().is_empty());
+        }
+    }
+
+    #[test]
+    fn concurrent_edits_to_same_field_converge_and_record_conflict() {
+        let fixture = Fixture::new();
+        let (a, b, task) = two_devices(&fixture);
+
+        edit(&a, &task.id, title("title from a"));
+        edit(&b, &task.id, title("title from b"));
+        sync(&a).unwrap();
+        sync(&b).unwrap();
+
+        // 较晚的修改保留，另一个值记为冲突
+        for storage in [&a, &b] {
+            assert_eq!(find(storage, &task.id).unwrap().title, "title from b");
+            let conflicts = conflicts(storage).unwrap();
+            assert_eq!(conflicts.len(), 1);
+            let conflict = &conflicts[0];
+            assert_eq!(conflict.task_id, task.id);
+            assert_eq!(conflict.field, "title");
+            assert_eq!(conflict.kept_value, "\"title from b\"");
+            assert_eq!(conflict.kept_device, "b");
+            assert_eq!(conflict.other_value, "\"title from a\"");
+            assert_eq!(conflict.other_device, "a");
+        }
+
+        // 在 A 上改用被覆盖的值，B 同步后采用该值，B 上的冲突随之消失
+        pause();
+        let conflict = conflicts(&a).unwrap().remove(0);
+        assert!(resolve_conflict(&a, &conflict.id, true).unwrap());
+        assert!(sync(&b).unwrap());
+        for storage in [&a, &b] {
+            assert_eq!(find(storage, &task.id).unwrap().title, "title from a");
+            assert!(conflicts(storage).unwrap().is_empty());
+        }
+    }
+
+    #[test]
+    fn delete_vs_edit_keeps_task_deleted_and_records_conflict() {
+        let fixture = Fixture::new();
+        let (a, b, task) = two_devices(&fixture);
+
+        pause();
+        a.delete_task(&task.id).unwrap();
+        edit(&b, &task.id, UpdateTaskRequest { completed: Some(true), ..Default::default() });
+        sync(&a).unwrap();
+        sync(&b).unwrap();
+
+        for storage in [&a, &b] {
+            assert!(find(storage, &task.id).is_none());
+            let conflicts = conflicts(storage).unwrap();
+            assert_eq!(conflicts.len(), 1);
+            assert_eq!(conflicts[0].field, DELETED_FIELD);
+            assert_eq!(conflicts[0].kept_value, "true");
+            assert_eq!(conflicts[0].other_value, "false");
+        }
+    }
+
+    #[test]
+    fn restoring_deleted_task_brings_it_back_on_both_devices() {
+        let fixture = Fixture::new();
+        let (a, b, task) = two_devices(&fixture);
+
+        pause();
+        a.delete_task(&task.id).unwrap();
+        edit(&b, &task.id, UpdateTaskRequest { completed: Some(true), ..Default::default() });
+        sync(&a).unwrap();
+        sync(&b).unwrap();
+
+        pause();
+        let conflict = conflicts(&b).unwrap().remove(0);
+        assert!(resolve_conflict(&b, &conflict.id, true).unwrap());
+        assert!(sync(&a).unwrap());
+
+        // 恢复的待办包含删除后另一台设备的修改
+        for storage in [&a, &b] {
+            let restored = find(storage, &task.id).unwrap();
+            assert!(restored.completed);
+            assert!(conflicts(storage).unwrap().is_empty());
+        }
+    }
+
+    #[test]
+    fn partial_last_line_is_merged_once_complete() {
+        let fixture = Fixture::new();
+        let (a, b, task) = two_devices(&fixture);
+
+        edit(&a, &task.id, title("after partial write"));
+        let log = fixture.log_path(&a);
+        let content = fs::read_to_string(&log).unwrap();
+        // 模拟网盘只同步了最后一行的前半部分
+        let cut = content.len() - content.lines().last().unwrap().len() / 2 - 1;
+        fs::write(&log, &content[..cut]).unwrap();
+
+        assert!(!sync(&b).unwrap());
+        assert_eq!(find(&b, &task.id).unwrap().title, "shared");
+
+        fs::write(&log, &content).unwrap();
+        assert!(sync(&b).unwrap());
+        assert_eq!(find(&b, &task.id).unwrap().title, "after partial write");
+        assert!(conflicts(&b).unwrap().is_empty());
+    }
+
+    #[test]
+    fn shortened_log_is_replayed_without_duplicates() {
+        let fixture = Fixture::new();
+        let (a, b, task) = two_devices(&fixture);
+
+        edit(&a, &task.id, title("first"));
+        edit(&a, &task.id, title("second"));
+        sync(&b).unwrap();
+        let changes = status(&b).unwrap().devices[0].changes;
+
+        // 日志被替换为较旧的版本
+        let log = fixture.log_path(&a);
+        let content = fs::read_to_string(&log).unwrap();
+        fs::write(&log, format!("{}\n", content.lines().next().unwrap())).unwrap();
+        assert!(!sync(&b).unwrap());
+        assert_eq!(find(&b, &task.id).unwrap().title, "second");
+        assert_eq!(status(&b).unwrap().devices[0].changes, changes);
+        assert!(conflicts(&b).unwrap().is_empty());
+
+        // 之后追加的修改照常合并
+        edit(&a, &task.id, title("third"));
+        assert!(sync(&b).unwrap());
+        assert_eq!(find(&b, &task.id).unwrap().title, "third");
+        assert_eq!(status(&b).unwrap().devices[0].changes, changes + 1);
+    }
+}
@@ -0,0 +1,462 @@
+use std::fs;
+use std::path::Path;
+use chrono::{NaiveDate, NaiveTime, TimeZone, Utc};
+use csv::{ReaderBuilder, StringRecord, Terminator, WriterBuilder};
+use crate::datetime::{parse_date, parse_datetime, parse_loose_datetime, resolve_local, DUE_FORMAT};
+use crate::error::{AppError, AppResult};
+use crate::i18n::{fill, Locale, Msg};
+use crate::models::{CsvInspection, ImportRowError, Priority, Task, TaskField, TaskImportResult};
+
+// Excel 需要 BOM 才能按 UTF-8 识别中文
+const UTF8_BOM: &str = "\u{feff}";
+
+// 导出的时间格式，导入时同样可以识别
+const TIME_FORMAT: &str = "%Y-%m-%d %H:%M";
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+// 选择列时展示的示例行数
+const SAMPLE_ROWS: usize = 5;
+
+/// 表头中常见的字段名（比较时忽略大小写、空格、下划线与括号），两种语言下的列名也会被识别
+fn field_aliases(field: TaskField) -> &'static [&'static str] {
+    match field {
+        TaskField::Title => &["title", "name", "task", "taskname", "subject", "content", "任务", "任务名称", "名称", "待办", "内容"],
+        TaskField::Description => &["description", "notes", "note", "details", "body", "备注", "说明", "详情"],
+        TaskField::Completed => &["completed", "done", "status", "iscompleted", "状态", "完成", "是否完成"],
+        TaskField::Priority => &["priority", "importance", "重要性", "优先"],
+        TaskField::DueDate => &["due", "duedate", "deadline", "dueat", "截止", "截止日期", "到期", "到期日"],
+        TaskField::CreatedAt => &["created", "createdat", "createddate", "creationdate", "创建日期"],
+        TaskField::CompletedAt => &["completedat", "completeddate", "completiondate", "donedate", "完成日期"],
+        TaskField::EstimatedMinutes => &["estimate", "estimatedminutes", "estimatemin", "预计", "预计耗时", "预计分钟"],
+        TaskField::FocusMinutes => &["focus", "focusminutes", "focusmin", "专注", "专注时长", "专注分钟"],
+    }
+}
+
+pub fn field_label(field: TaskField) -> Msg {
+    match field {
+        TaskField::Title => Msg::FieldTitle,
+        TaskField::Description => Msg::FieldDescription,
+        TaskField::Completed => Msg::FieldCompleted,
+        TaskField::Priority => Msg::FieldPriority,
+        TaskField::DueDate => Msg::FieldDueDate,
+        TaskField::CreatedAt => Msg::FieldCreatedAt,
+        TaskField::CompletedAt => Msg::FieldCompletedAt,
+        TaskField::EstimatedMinutes => Msg::FieldEstimatedMinutes,
+        TaskField::FocusMinutes => Msg::FieldFocusMinutes,
+    }
+}
+
+fn normalize_header(header: &str) -> String {
+    header.trim()
+        .to_lowercase()
+        .chars()
+        .filter(|c| !c.is_whitespace() && !matches!(c, '_' | '-' | '(' | ')' | '（' | '）'))
+        .collect()
+}
+
+/// 按表头推测对应的字段，每个字段最多对应一列
+fn suggest_mapping(headers: &[String]) -> Vec<Option<TaskField>> {
+    let mut used = Vec::new();
+    headers.iter()
+        .map(|header| {
+            let name = normalize_header(header);
+            let field = TaskField::ALL.into_iter().find(|field| {
+                !used.contains(field)
+                    && ([Locale::ZhCn, Locale::EnUs].iter().any(|l| normalize_header(field_label(*field).text_in(*l)) == name)
+                        || field_aliases(*field).contains(&name.as_str()))
+            });
+            used.extend(field);
+            field
+        })
+        .collect()
+}
+
+/// Excel 在部分语言下以分号分隔，制表符分隔的文件也一并支持
+fn detect_delimiter(content: &str) -> u8 {
+    let first_line = content.lines().next().unwrap_or_default();
+    [b',', b';', b'\t']
+        .into_iter()
+        .max_by_key(|d| first_line.bytes().filter(|b| b == d).count())
+        .filter(|d| first_line.as_bytes().contains(d))
+        .unwrap_or(b',')
+}
+
+fn reader(content: &str) -> csv::Reader<&[u8]> {
+    let content = content.strip_prefix(UTF8_BOM).unwrap_or(content);
+    ReaderBuilder::new()
+        .delimiter(detect_delimiter(content))
+        .flexible(true)
+        .from_reader(content.as_bytes())
+}
+
+fn headers(reader: &mut csv::Reader<&[u8]>) -> AppResult<Vec<String>> {
+    let headers: Vec<String> = reader.headers()
+        .map_err(|e| AppError::invalid_input(fill(Msg::ImportMalformedRow.text(), &[&e])))?
+        .iter()
+        .map(|h| h.trim().to_string())
+        .collect();
+    if headers.iter().all(|h| h.is_empty()) {
+        return Err(AppError::invalid_input(Msg::CsvEmpty.text()));
+    }
+    Ok(headers)
+}
+
+/// 读取表头与前几行，供选择每列对应的字段
+pub fn inspect(content: &str) -> AppResult<CsvInspection> {
+    let mut reader = reader(content);
+    let headers = headers(&mut reader)?;
+
+    let mut sample_rows = Vec::new();
+    let mut total_rows = 0;
+    for record in reader.records().flatten() {
+        if is_blank(&record) {
+            continue;
+        }
+        if sample_rows.len() < SAMPLE_ROWS {
+            sample_rows.push(record.iter().map(str::to_string).collect());
+        }
+        total_rows += 1;
+    }
+
+    Ok(CsvInspection {
+        suggested_mapping: suggest_mapping(&headers),
+        headers,
+        sample_rows,
+        total_rows,
+    })
+}
+
+fn is_blank(record: &StringRecord) -> bool {
+    record.iter().all(|v| v.trim().is_empty())
+}
+
+/// 识别常见的完成状态写法，包括报告 CSV 中的状态列
+pub fn parse_completed(value: &str) -> Option<bool> {
+    match value.trim().to_lowercase().as_str() {
+        "true" | "yes" | "y" | "1" | "x" | "✓" | "✔" | "done" | "completed" | "complete" | "是" | "已完成" | "完成" => Some(true),
+        "" | "false" | "no" | "n" | "0" | "todo" | "pending" | "open" | "overdue" | "否" | "未完成" | "已逾期" | "进行中" => Some(false),
+        _ => None,
+    }
+}
+
+/// 识别常见的优先级写法，数字 1-3 按 1 为最高理解
+pub fn parse_priority(value: &str) -> Option<Priority> {
+    match value.trim().to_lowercase().as_str() {
+        "high" | "h" | "urgent" | "important" | "1" | "!!!" | "高" | "紧急" | "重要" => Some(Priority::High),
+        "" | "medium" | "m" | "normal" | "2" | "!!" | "中" | "普通" => Some(Priority::Medium),
+        "low" | "l" | "3" | "!" | "none" | "低" => Some(Priority::Low),
+        _ => None,
+    }
+}
+
+/// 截止时间按应用内的格式保存：只有日期时为 YYYY-MM-DD
+pub fn due_from_parts(date: NaiveDate, time: Option<NaiveTime>) -> String {
+    match time {
+        Some(time) => date.and_time(time).format(DUE_FORMAT).to_string(),
+        None => date.format(DATE_FORMAT).to_string(),
+    }
+}
+
+/// 创建、完成时间保存为 RFC 3339，只有日期时取当天零点
+pub fn timestamp_from_parts<Tz: TimeZone>(date: NaiveDate, time: Option<NaiveTime>, tz: &Tz) -> String {
+    let naive = date.and_time(time.unwrap_or(NaiveTime::MIN));
+    resolve_local(naive, tz).with_timezone(&Utc).to_rfc3339()
+}
+
+fn invalid(msg: Msg, value: &str) -> String {
+    fill(msg.text(), &[&value.trim()])
+}
+
+/// 按映射把一行转换为待办，失败时返回原因
+fn parse_row<Tz: TimeZone>(record: &StringRecord, mapping: &[Option<TaskField>], tz: &Tz) -> Result<Task, String> {
+    let mut task = Task::new(String::new(), None, None, None, None);
+    let mut completed = None;
+    let mut completed_at = None;
+
+    for (value, field) in record.iter().zip(mapping) {
+        let Some(field) = field else {
+            continue;
+        };
+        let value = value.trim();
+        let date = || parse_loose_datetime(value, tz).ok_or_else(|| invalid(Msg::ImportInvalidDate, value));
+        let minutes = || value.parse::<f64>()
+            .ok()
+            .filter(|m| *m >= 0.0)
+            .map(|m| m.round() as u32)
+            .ok_or_else(|| invalid(Msg::ImportInvalidNumber, value));
+
+        match field {
+            TaskField::Title => task.title = value.to_string(),
+            TaskField::Description => task.description = Some(value.to_string()).filter(|d| !d.is_empty()),
+            TaskField::Completed => {
+                completed = Some(parse_completed(value).ok_or_else(|| invalid(Msg::ImportInvalidCompleted, value))?);
+            }
+            TaskField::Priority => {
+                task.priority = parse_priority(value).ok_or_else(|| invalid(Msg::ImportInvalidPriority, value))?;
+            }
+            _ if value.is_empty() => {}
+            TaskField::DueDate => {
+                let (date, time) = date()?;
+                task.due_date = Some(due_from_parts(date, time));
+            }
+            TaskField::CreatedAt => {
+                let (date, time) = date()?;
+                task.created_at = timestamp_from_parts(date, time, tz);
+                task.updated_at = task.created_at.clone();
+            }
+            TaskField::CompletedAt => {
+                let (date, time) = date()?;
+                completed_at = Some(timestamp_from_parts(date, time, tz));
+            }
+            TaskField::EstimatedMinutes => task.estimated_minutes = Some(minutes()?).filter(|m| *m > 0),
+            TaskField::FocusMinutes => task.focus_minutes = minutes()?,
+        }
+    }
+
+    if task.title.is_empty() {
+        return Err(Msg::ImportMissingTitle.text().to_string());
+    }
+
+    // 只给出完成时间时视为已完成
+    if completed.unwrap_or(completed_at.is_some()) {
+        task.completed = true;
+        task.completed_at = completed_at.or_else(|| Some(Utc::now().to_rfc3339()));
+    }
+    Ok(task)
+}
+
+/// 校验列映射：与表头列数一致、标题必须对应、每个字段最多对应一列
+fn validate_mapping(headers: &[String], mapping: &[Option<TaskField>]) -> AppResult<()> {
+    if headers.len() != mapping.len() {
+        return Err(AppError::invalid_input(Msg::CsvMappingMismatch.text()));
+    }
+    if !mapping.contains(&Some(TaskField::Title)) {
+        return Err(AppError::invalid_input(Msg::CsvTitleNotMapped.text()));
+    }
+    for (i, field) in mapping.iter().enumerate() {
+        if let Some(field) = field {
+            if mapping[..i].contains(&Some(*field)) {
+                return Err(AppError::invalid_input(fill(Msg::CsvDuplicateField.text(), &[&field_label(*field).text()])));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// 按列映射解析 CSV，无法识别的行记录原因后跳过，不影响其他行
+pub fn parse<Tz: TimeZone>(content: &str, mapping: &[Option<TaskField>], tz: &Tz) -> AppResult<TaskImportResult> {
+    let mut reader = reader(content);
+    let headers = headers(&mut reader)?;
+    validate_mapping(&headers, mapping)?;
+
+    let mut tasks = Vec::new();
+    let mut errors = Vec::new();
+    for (index, record) in reader.records().enumerate() {
+        // 引号内换行时一条记录跨多行，行号取记录开始的位置
+        let row = |record: &StringRecord| record.position().map_or(index + 2, |p| p.line() as usize);
+        match record {
+            Ok(record) if is_blank(&record) => {}
+            Ok(record) => match parse_row(&record, mapping, tz) {
+                Ok(task) => tasks.push(task),
+                Err(message) => errors.push(ImportRowError { row: row(&record), message }),
+            },
+            Err(e) => errors.push(ImportRowError {
+                row: e.position().map_or(index + 2, |p| p.line() as usize),
+                message: fill(Msg::ImportMalformedRow.text(), &[&e]),
+            }),
+        }
+    }
+
+    if tasks.is_empty() && errors.is_empty() {
+        return Err(AppError::invalid_input(Msg::CsvEmpty.text()));
+    }
+    Ok(TaskImportResult { tasks, errors })
+}
+
+fn format_timestamp<Tz: TimeZone>(value: &str, tz: &Tz) -> String
+where
+    Tz::Offset: std::fmt::Display,
+{
+    parse_datetime(value, tz).map_or_else(|| value.to_string(), |dt| dt.format(TIME_FORMAT).to_string())
+}
+
+fn field_value<Tz: TimeZone>(task: &Task, field: TaskField, tz: &Tz) -> String
+where
+    Tz::Offset: std::fmt::Display,
+{
+    match field {
+        TaskField::Title => task.title.clone(),
+        TaskField::Description => task.description.clone().unwrap_or_default(),
+        TaskField::Completed => if task.completed { Msg::CsvYes } else { Msg::CsvNo }.text().to_string(),
+        TaskField::Priority => match task.priority {
+            Priority::High => Msg::ReportPriorityHigh.text(),
+            Priority::Medium => Msg::ReportPriorityMedium.text(),
+            Priority::Low => Msg::ReportPriorityLow.text(),
+        }.to_string(),
+        // 只有日期的截止时间原样导出
+        TaskField::DueDate => task.due_date.as_deref().map_or_else(String::new, |due| {
+            if parse_date(due).is_some() { due.to_string() } else { format_timestamp(due, tz) }
+        }),
+        TaskField::CreatedAt => format_timestamp(&task.created_at, tz),
+        TaskField::CompletedAt => task.completion_time().map(|t| format_timestamp(t, tz)).unwrap_or_default(),
+        TaskField::EstimatedMinutes => task.estimated_minutes.map(|m| m.to_string()).unwrap_or_default(),
+        TaskField::FocusMinutes => task.focus_minutes.to_string(),
+    }
+}
+
+/// 生成带 BOM 的 CSV，表头使用当前语言的字段名
+pub fn render<Tz: TimeZone>(tasks: &[Task], columns: &[TaskField], tz: &Tz) -> AppResult<String>
+where
+    Tz::Offset: std::fmt::Display,
+{
+    let mut writer = WriterBuilder::new()
+        .terminator(Terminator::CRLF)
+        .from_writer(UTF8_BOM.as_bytes().to_vec());
+    let write_err = |e: csv::Error| AppError::Internal(e.to_string());
+
+    writer.write_record(columns.iter().map(|c| field_label(*c).text())).map_err(write_err)?;
+    for task in tasks {
+        writer.write_record(columns.iter().map(|c| field_value(task, *c, tz))).map_err(write_err)?;
+    }
+
+    let bytes = writer.into_inner().map_err(|e| AppError::Internal(e.to_string()))?;
+    String::from_utf8(bytes).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+/// 导出待办到指定路径
+pub fn export<Tz: TimeZone>(tasks: &[Task], columns: &[TaskField], tz: &Tz, path: &Path) -> AppResult<()>
+where
+    Tz::Offset: std::fmt::Display,
+{
+    fs::write(path, render(tasks, columns, tz)?)?;
+    Ok(())
+}
+
+/// 默认的文件名，如 事半SemiDone_待办_2025-01-06.csv
+pub fn default_file_name(today: NaiveDate) -> String {
+    format!("{}.csv", fill(Msg::CsvFileName.text(), &[&today]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{DateTime, FixedOffset};
+
+    fn tz() -> FixedOffset {
+        FixedOffset::east_opt(8 * 3600).unwrap()
+    }
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    fn instant(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn suggests_mapping_from_aliases_and_labels_in_both_languages() {
+        let headers = strings(&[
+            "Task Name", "备注", "Due_Date", "优先级", "专注时长（分钟）", "Estimate (min)", "Tags", "Title",
+        ]);
+        assert_eq!(suggest_mapping(&headers), vec![
+            Some(TaskField::Title),
+            Some(TaskField::Description),
+            Some(TaskField::DueDate),
+            Some(TaskField::Priority),
+            Some(TaskField::FocusMinutes),
+            Some(TaskField::EstimatedMinutes),
+            None,
+            // 标题已对应第一列
+            None,
+        ]);
+    }
+
+    #[test]
+    fn detects_delimiter_and_strips_bom() {
+        for content in ["Title;Due\nA;2025-01-02\n", "Title\tDue\nA\t2025-01-02\n", "\u{feff}Title,Due\nA,2025-01-02\n"] {
+            let inspection = inspect(content).unwrap();
+            assert_eq!(inspection.headers, strings(&["Title", "Due"]));
+            assert_eq!(inspection.sample_rows, vec![strings(&["A", "2025-01-02"])]);
+            assert_eq!(inspection.suggested_mapping, vec![Some(TaskField::Title), Some(TaskField::DueDate)]);
+        }
+        // 分号分隔时逗号属于内容
+        let inspection = inspect("Title;Notes\nA;x, y\n").unwrap();
+        assert_eq!(inspection.sample_rows, vec![strings(&["A", "x, y"])]);
+    }
+
+    #[test]
+    fn reports_row_errors_with_record_start_line() {
+        let content = "Title,Due,Estimate\n\
+            \"First\nline two\",2025-01-02,30\n\
+            Bad date,someday,\n\
+            Bad number,,abc\n\
+            ,2025-01-02,\n\
+            \n\
+            Ok,01/02/2025,1.6\n";
+        let mapping = [Some(TaskField::Title), Some(TaskField::DueDate), Some(TaskField::EstimatedMinutes)];
+        let result = parse(content, &mapping, &tz()).unwrap();
+
+        let titles: Vec<&str> = result.tasks.iter().map(|t| t.title.as_str()).collect();
+        assert_eq!(titles, ["First\nline two", "Ok"]);
+        assert_eq!(result.tasks[0].due_date.as_deref(), Some("2025-01-02"));
+        assert_eq!(result.tasks[0].estimated_minutes, Some(30));
+        assert_eq!(result.tasks[1].due_date.as_deref(), Some("2025-01-02"));
+        assert_eq!(result.tasks[1].estimated_minutes, Some(2));
+
+        let errors: Vec<(usize, String)> = result.errors.into_iter().map(|e| (e.row, e.message)).collect();
+        assert_eq!(errors, vec![
+            (4, fill(Msg::ImportInvalidDate.text(), &[&"someday"])),
+            (5, fill(Msg::ImportInvalidNumber.text(), &[&"abc"])),
+            (6, Msg::ImportMissingTitle.text().to_string()),
+        ]);
+    }
+
+    #[test]
+    fn rejects_invalid_mapping() {
+        let content = "Title,Due\nA,2025-01-02\n";
+        assert!(parse(content, &[Some(TaskField::Title)], &tz()).is_err());
+        assert!(parse(content, &[None, Some(TaskField::DueDate)], &tz()).is_err());
+        assert!(parse(content, &[Some(TaskField::Title), Some(TaskField::Title)], &tz()).is_err());
+        assert!(parse("Title\n\n", &[Some(TaskField::Title)], &tz()).is_err());
+    }
+
+    #[test]
+    fn rendered_csv_imports_back() {
+        let mut first = Task::new(
+            "Buy, milk".to_string(),
+            Some("line one\nline \"two\"".to_string()),
+            Some(Priority::High),
+            Some("2025-01-02".to_string()),
+            None,
+        );
+        first.created_at = "2025-01-01T01:30:00+00:00".to_string();
+        first.estimated_minutes = Some(30);
+        first.focus_minutes = 15;
+
+        let mut second = Task::new("Call".to_string(), None, Some(Priority::Low), Some("2025-01-03T14:00".to_string()), None);
+        second.created_at = "2024-12-31T23:00:00+00:00".to_string();
+        second.completed = true;
+        second.completed_at = Some("2025-01-02T08:00:00+00:00".to_string());
+
+        let content = render(&[first.clone(), second.clone()], &TaskField::ALL, &tz()).unwrap();
+        assert!(content.starts_with(UTF8_BOM));
+        let inspection = inspect(&content).unwrap();
+        assert_eq!(inspection.suggested_mapping, TaskField::ALL.map(Some).to_vec());
+
+        let result = parse(&content, &inspection.suggested_mapping, &tz()).unwrap();
+        assert!(result.errors.is_empty());
+        for (imported, original) in result.tasks.iter().zip([&first, &second]) {
+            assert_eq!(imported.title, original.title);
+            assert_eq!(imported.description, original.description);
+            assert_eq!(imported.completed, original.completed);
+            assert_eq!(imported.priority, original.priority);
+            assert_eq!(imported.due_date, original.due_date);
+            assert_eq!(instant(&imported.created_at), instant(&original.created_at));
+            assert_eq!(imported.completed_at.as_deref().map(instant), original.completed_at.as_deref().map(instant));
+            assert_eq!(imported.estimated_minutes, original.estimated_minutes);
+            assert_eq!(imported.focus_minutes, original.focus_minutes);
+        }
+        assert_eq!(result.tasks.len(), 2);
+    }
+}
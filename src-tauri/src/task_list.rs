@@ -0,0 +1,224 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use chrono::{DateTime, NaiveDate, TimeZone};
+use crate::datetime::{due_deadline, local_date, parse_date, parse_datetime};
+use crate::error::AppResult;
+use crate::i18n::{fill, Msg};
+use crate::models::{Priority, Task, TaskListFormat, TaskListGroup};
+
+const TIME_FORMAT: &str = "%Y-%m-%d %H:%M";
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// 一组待办，不分组时没有标题
+struct Group<'a> {
+    heading: Option<String>,
+    tasks: Vec<&'a Task>,
+}
+
+fn priority_rank(priority: Priority) -> u8 {
+    match priority {
+        Priority::High => 0,
+        Priority::Medium => 1,
+        Priority::Low => 2,
+    }
+}
+
+fn priority_heading(priority: Priority) -> String {
+    let (icon, label) = match priority {
+        Priority::High => ("🔴", Msg::ReportPriorityHigh),
+        Priority::Medium => ("🟡", Msg::ReportPriorityMedium),
+        Priority::Low => ("🟢", Msg::ReportPriorityLow),
+    };
+    format!("{} {}", icon, fill(Msg::TaskListPriorityGroup.text(), &[&label.text()]))
+}
+
+/// 未完成的在前，其次按截止时间（没有的排最后）、优先级与创建时间排序
+fn sort_tasks<Tz: TimeZone>(tasks: &mut [&Task], tz: &Tz) {
+    tasks.sort_by_cached_key(|task| {
+        let deadline = task.due_date.as_deref().and_then(|due| due_deadline(due, tz)).map(|d| d.timestamp());
+        (task.completed, deadline.is_none(), deadline, priority_rank(task.priority), task.created_at.clone())
+    });
+}
+
+fn group_tasks<'a, Tz: TimeZone>(tasks: Vec<&'a Task>, group_by: TaskListGroup, now: &DateTime<Tz>) -> Vec<Group<'a>> {
+    let tz = now.timezone();
+    match group_by {
+        TaskListGroup::None => vec![Group { heading: None, tasks }],
+        TaskListGroup::Priority => [Priority::High, Priority::Medium, Priority::Low].into_iter()
+            .map(|priority| Group {
+                heading: Some(priority_heading(priority)),
+                tasks: tasks.iter().copied().filter(|t| t.priority == priority).collect(),
+            })
+            .collect(),
+        // 未完成且已过截止时间的单独成组，其余按截止日期分组，没有截止时间的排最后
+        TaskListGroup::DueDate => {
+            let today = now.date_naive();
+            let mut overdue = Vec::new();
+            let mut days: BTreeMap<NaiveDate, Vec<&Task>> = BTreeMap::new();
+            let mut no_due = Vec::new();
+            for task in tasks {
+                let Some(due) = task.due_date.as_deref() else {
+                    no_due.push(task);
+                    continue;
+                };
+                if !task.completed && due_deadline(due, &tz).is_some_and(|deadline| deadline <= *now) {
+                    overdue.push(task);
+                } else if let Some(date) = local_date(due, &tz) {
+                    days.entry(date).or_default().push(task);
+                } else {
+                    no_due.push(task);
+                }
+            }
+
+            let mut groups = vec![Group { heading: Some(Msg::TaskListOverdue.text().to_string()), tasks: overdue }];
+            groups.extend(days.into_iter().map(|(date, tasks)| {
+                let label = date.format(DATE_FORMAT).to_string();
+                let heading = if date == today {
+                    fill(Msg::TaskListToday.text(), &[&label])
+                } else if today.succ_opt() == Some(date) {
+                    fill(Msg::TaskListTomorrow.text(), &[&label])
+                } else {
+                    label
+                };
+                Group { heading: Some(heading), tasks }
+            }));
+            groups.push(Group { heading: Some(Msg::TaskListNoDue.text().to_string()), tasks: no_due });
+            groups
+        }
+    }
+}
+
+/// 截止时间只有日期时原样显示
+fn format_due<Tz: TimeZone>(value: &str, tz: &Tz) -> String
+where
+    Tz::Offset: std::fmt::Display,
+{
+    if parse_date(value).is_some() {
+        return value.trim().to_string();
+    }
+    parse_datetime(value, tz).map_or_else(|| value.to_string(), |dt| dt.format(TIME_FORMAT).to_string())
+}
+
+// 清单中每个待办只占一行，标题中的换行合并为空格
+fn single_line(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// `- [ ] 标题 · 截止 …`，描述缩进写在下面，作为该项的内容
+fn markdown_item<Tz: TimeZone>(task: &Task, tz: &Tz) -> String
+where
+    Tz::Offset: std::fmt::Display,
+{
+    let mut item = format!("- [{}] {}", if task.completed { "x" } else { " " }, single_line(&task.title));
+    if let Some(due) = &task.due_date {
+        item.push_str(" · ");
+        item.push_str(&fill(Msg::ReportDue.text(), &[&format_due(due, tz)]));
+    }
+    for line in task.description.iter().flat_map(|d| d.lines()).map(str::trim_end).filter(|l| !l.trim().is_empty()) {
+        item.push_str("\n  ");
+        item.push_str(line);
+    }
+    item
+}
+
+fn render_markdown<Tz: TimeZone>(groups: &[Group], tz: &Tz) -> String
+where
+    Tz::Offset: std::fmt::Display,
+{
+    let mut out = format!("# {}\n", Msg::TaskListTitle.text());
+    for group in groups {
+        out.push('\n');
+        if let Some(heading) = &group.heading {
+            out.push_str(&format!("## {} ({})\n\n", heading, group.tasks.len()));
+        }
+        for task in &group.tasks {
+            out.push_str(&markdown_item(task, tz));
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// todo.txt 格式：`(A) 创建日期 标题 due:日期`，已完成为 `x 完成日期 创建日期 标题 pri:A`
+///
+/// 高、中、低优先级分别为 A、B、C；格式不支持描述与截止时刻，只保留截止日期
+pub fn todo_txt_line<Tz: TimeZone>(task: &Task, tz: &Tz) -> String {
+    let letter = match task.priority {
+        Priority::High => 'A',
+        Priority::Medium => 'B',
+        Priority::Low => 'C',
+    };
+    let created = local_date(&task.created_at, tz);
+
+    let mut parts = Vec::new();
+    if task.completed {
+        parts.push("x".to_string());
+        let completed = task.completion_time().and_then(|t| local_date(t, tz)).or(created);
+        // 只有写了完成日期才能写创建日期
+        if let Some(completed) = completed {
+            parts.push(completed.format(DATE_FORMAT).to_string());
+            parts.extend(created.map(|d| d.format(DATE_FORMAT).to_string()));
+        }
+    } else {
+        parts.push(format!("({})", letter));
+        parts.extend(created.map(|d| d.format(DATE_FORMAT).to_string()));
+    }
+    parts.push(single_line(&task.title));
+    if let Some(due) = task.due_date.as_deref().and_then(|due| local_date(due, tz)) {
+        parts.push(format!("due:{}", due.format(DATE_FORMAT)));
+    }
+    if task.completed {
+        parts.push(format!("pri:{}", letter));
+    }
+    parts.join(" ")
+}
+
+/// 渲染待办清单；todo.txt 没有分组标题，按分组后的顺序逐行输出
+pub fn render<Tz: TimeZone>(
+    tasks: &[Task],
+    format: TaskListFormat,
+    group_by: TaskListGroup,
+    include_completed: bool,
+    now: &DateTime<Tz>,
+) -> String
+where
+    Tz::Offset: std::fmt::Display,
+{
+    let tz = now.timezone();
+    let mut tasks: Vec<&Task> = tasks.iter().filter(|t| include_completed || !t.completed).collect();
+    sort_tasks(&mut tasks, &tz);
+    let groups: Vec<Group> = group_tasks(tasks, group_by, now)
+        .into_iter()
+        .filter(|g| !g.tasks.is_empty())
+        .collect();
+
+    match format {
+        TaskListFormat::Markdown => render_markdown(&groups, &tz),
+        TaskListFormat::TodoTxt => groups.iter()
+            .flat_map(|g| g.tasks.iter())
+            .map(|task| todo_txt_line(task, &tz) + "\n")
+            .collect(),
+    }
+}
+
+/// 导出待办清单到指定路径
+pub fn export<Tz: TimeZone>(
+    tasks: &[Task],
+    format: TaskListFormat,
+    group_by: TaskListGroup,
+    include_completed: bool,
+    now: &DateTime<Tz>,
+    path: &Path,
+) -> AppResult<()>
+where
+    Tz::Offset: std::fmt::Display,
+{
+    fs::write(path, render(tasks, format, group_by, include_completed, now))?;
+    Ok(())
+}
+
+/// 默认的文件名，如 事半SemiDone_待办清单_2025-01-06.md
+pub fn default_file_name(format: TaskListFormat, today: NaiveDate) -> String {
+    format!("{}.{}", fill(Msg::TaskListFileName.text(), &[&today]), format.extension())
+}
@@ -0,0 +1,148 @@
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+use crate::error::{AppError, AppResult};
+use crate::i18n::Msg;
+use crate::models::{AddTimeEntryRequest, TimeEntry, UpdateTimeEntryRequest};
+use crate::storage::Storage;
+
+fn ensure_task_exists(storage: &Storage, task_id: &str) -> AppResult<()> {
+    if storage.load_tasks()?.iter().any(|t| t.id == task_id) {
+        Ok(())
+    } else {
+        Err(AppError::not_found(Msg::EntityTask, task_id))
+    }
+}
+
+/// 结束时间须晚于开始时间，且都不能晚于当前时间
+fn validate(entry: &TimeEntry, now: DateTime<Utc>) -> AppResult<()> {
+    if entry.start > now || entry.end.is_some_and(|end| end > now) {
+        return Err(AppError::invalid_input(Msg::TimeEntryInFuture.text()));
+    }
+    if entry.end.is_some_and(|end| end <= entry.start) {
+        return Err(AppError::invalid_input(Msg::TimeEntryEndBeforeStart.text()));
+    }
+    Ok(())
+}
+
+fn normalize_note(note: Option<String>) -> Option<String> {
+    note.map(|n| n.trim().to_string()).filter(|n| !n.is_empty())
+}
+
+/// 正在计时的记录
+///
+/// 计时以开始时间为准且随时写入磁盘，应用异常退出后重新打开时计时仍在继续
+pub fn running(storage: &Storage) -> AppResult<Option<TimeEntry>> {
+    Ok(storage.load_time_entries()?.into_iter().find(|e| e.end.is_none()))
+}
+
+/// 在待办上开始计时，其他待办上正在进行的计时会先被停止
+///
+/// 该待办已在计时时直接返回正在进行的记录
+pub fn start(storage: &Storage, task_id: &str) -> AppResult<TimeEntry> {
+    ensure_task_exists(storage, task_id)?;
+
+    let mut entries = storage.load_time_entries()?;
+    let now = Utc::now();
+    if let Some(current) = entries.iter_mut().find(|e| e.end.is_none()) {
+        if current.task_id == task_id {
+            return Ok(current.clone());
+        }
+        current.end = Some(now);
+    }
+
+    let entry = TimeEntry {
+        id: Uuid::new_v4().to_string(),
+        task_id: task_id.to_string(),
+        start: now,
+        end: None,
+        note: None,
+    };
+    entries.push(entry.clone());
+    storage.save_time_entries(&mut entries)?;
+    Ok(entry)
+}
+
+/// 停止正在进行的计时，没有计时时返回 None
+pub fn stop(storage: &Storage) -> AppResult<Option<TimeEntry>> {
+    let mut entries = storage.load_time_entries()?;
+    let Some(current) = entries.iter_mut().find(|e| e.end.is_none()) else {
+        return Ok(None);
+    };
+
+    // 系统时间被调回时至少保留一秒，避免结束时间早于开始时间
+    let now = Utc::now().max(current.start + Duration::seconds(1));
+    current.end = Some(now);
+    let stopped = current.clone();
+    storage.save_time_entries(&mut entries)?;
+    Ok(Some(stopped))
+}
+
+/// 补录一段已经结束的计时
+pub fn add(storage: &Storage, request: AddTimeEntryRequest) -> AppResult<TimeEntry> {
+    ensure_task_exists(storage, &request.task_id)?;
+
+    let entry = TimeEntry {
+        id: Uuid::new_v4().to_string(),
+        task_id: request.task_id,
+        start: request.start,
+        end: Some(request.end),
+        note: normalize_note(request.note),
+    };
+    validate(&entry, Utc::now())?;
+
+    let mut entries = storage.load_time_entries()?;
+    entries.push(entry.clone());
+    storage.save_time_entries(&mut entries)?;
+    Ok(entry)
+}
+
+/// 修改计时记录的待办、起止时间或备注
+pub fn update(storage: &Storage, id: &str, request: UpdateTimeEntryRequest) -> AppResult<TimeEntry> {
+    if let Some(task_id) = &request.task_id {
+        ensure_task_exists(storage, task_id)?;
+    }
+
+    let mut entries = storage.load_time_entries()?;
+    let entry = entries.iter_mut()
+        .find(|e| e.id == id)
+        .ok_or_else(|| AppError::not_found(Msg::EntityTimeEntry, id))?;
+
+    let mut updated = entry.clone();
+    if let Some(task_id) = request.task_id {
+        updated.task_id = task_id;
+    }
+    if let Some(start) = request.start {
+        updated.start = start;
+    }
+    if let Some(end) = request.end {
+        updated.end = Some(end);
+    }
+    if request.note.is_some() {
+        updated.note = normalize_note(request.note);
+    }
+    validate(&updated, Utc::now())?;
+
+    *entry = updated.clone();
+    storage.save_time_entries(&mut entries)?;
+    Ok(updated)
+}
+
+pub fn delete(storage: &Storage, id: &str) -> AppResult<()> {
+    let mut entries = storage.load_time_entries()?;
+    let before = entries.len();
+    entries.retain(|e| e.id != id);
+    if entries.len() == before {
+        return Err(AppError::not_found(Msg::EntityTimeEntry, id));
+    }
+    storage.save_time_entries(&mut entries)
+}
+
+/// 计时记录，可按待办筛选，最新的排在最前
+pub fn entries(storage: &Storage, task_id: Option<&str>) -> AppResult<Vec<TimeEntry>> {
+    let mut entries = storage.load_time_entries()?;
+    if let Some(task_id) = task_id {
+        entries.retain(|e| e.task_id == task_id);
+    }
+    entries.reverse();
+    Ok(entries)
+}
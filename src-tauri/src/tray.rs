@@ -0,0 +1,436 @@
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::Duration;
+use chrono::{Local, Timelike};
+use tauri::{AppHandle, Emitter, Manager, Wry, image::Image, menu::{CheckMenuItem, Menu, MenuItem, PredefinedMenuItem}, tray::{TrayIcon, TrayIconBuilder, TrayIconEvent}};
+use crate::commands::notify_tasks_changed;
+use crate::datetime::due_deadline;
+use crate::error::AppResult;
+use crate::i18n::{fill, Msg};
+use crate::models::{Task, UpdateTaskRequest};
+use crate::quick_add::show_quick_add;
+use crate::stats::{is_due_today, is_overdue};
+use crate::storage::Storage;
+use crate::window::show_main_window;
+
+pub const TRAY_ID: &str = "main";
+
+// 置顶、折叠的状态在前端，托盘菜单通过事件交给前端执行；番茄钟由后端直接切换
+pub const TRAY_ACTION_EVENT: &str = "tray-action";
+
+// 托盘菜单项 id
+const MENU_SHOW: &str = "show";
+const MENU_QUIT: &str = "quit";
+const MENU_NEW_TASK: &str = "new-task";
+const MENU_POMODORO: &str = "pomodoro";
+const MENU_PIN: &str = "pin";
+const MENU_COLLAPSE: &str = "collapse";
+// 待办项的 id 为该前缀加待办 id，勾选即完成
+const MENU_TASK_PREFIX: &str = "task:";
+
+// 每组最多列出的待办数，其余合并为一行提示
+const MAX_TRAY_TASKS: usize = 8;
+// 菜单中标题的最大字符数
+const MAX_TITLE_CHARS: usize = 30;
+
+// 托盘图标角标颜色
+const OVERDUE_BADGE: [u8; 3] = [0xEF, 0x44, 0x44];
+const POMODORO_BADGE: [u8; 3] = [0xF9, 0x73, 0x16];
+
+/// 托盘菜单、提示文字和图标所需的数据；定时检查时与上次比较，有变化才更新
+#[derive(PartialEq)]
+struct TraySnapshot {
+    overdue: Vec<TrayTask>,
+    today: Vec<TrayTask>,
+    pinned: bool,
+    pomodoro_running: bool,
+}
+
+#[derive(PartialEq)]
+struct TrayTask {
+    id: String,
+    title: String,
+}
+
+/// 托管在应用状态中
+#[derive(Default)]
+pub struct TrayState {
+    // 番茄钟在后端计时，状态变化时由 pomodoro 模块更新
+    pomodoro_running: AtomicBool,
+    last_snapshot: Mutex<Option<TraySnapshot>>,
+}
+
+// 读取失败时按没有待办处理，托盘仍然可用
+fn load_snapshot(app: &AppHandle) -> TraySnapshot {
+    let state = app.state::<Mutex<Storage>>();
+    let (tasks, pinned) = match state.lock() {
+        Ok(storage) => (
+            storage.load_tasks().unwrap_or_default(),
+            storage.load_settings().map(|s| s.is_pinned).unwrap_or(false),
+        ),
+        Err(_) => (Vec::new(), false),
+    };
+
+    let now = Local::now();
+    let group = |filter: &dyn Fn(&Task) -> bool| {
+        let mut group: Vec<&Task> = tasks.iter().filter(|t| filter(t)).collect();
+        group.sort_by_key(|t| t.due_date.as_deref().and_then(|d| due_deadline(d, &Local)));
+        group.into_iter()
+            .map(|t| TrayTask { id: t.id.clone(), title: t.title.clone() })
+            .collect()
+    };
+
+    TraySnapshot {
+        overdue: group(&|t| is_overdue(t, &now)),
+        today: group(&|t| !t.completed && is_due_today(t, &now) && !is_overdue(t, &now)),
+        pinned,
+        pomodoro_running: app.state::<TrayState>().pomodoro_running.load(Ordering::Relaxed),
+    }
+}
+
+/// 按当前语言、今日及过期待办构建托盘右键菜单
+fn build_menu(app: &AppHandle, snapshot: &TraySnapshot) -> tauri::Result<Menu<Wry>> {
+    let menu = Menu::new(app)?;
+    if snapshot.overdue.is_empty() && snapshot.today.is_empty() {
+        menu.append(&MenuItem::new(app, Msg::TrayNoTasks.text(), false, None::<&str>)?)?;
+    }
+    append_task_group(app, &menu, Msg::TrayOverdue, &snapshot.overdue)?;
+    append_task_group(app, &menu, Msg::TrayDueToday, &snapshot.today)?;
+
+    menu.append(&PredefinedMenuItem::separator(app)?)?;
+    menu.append(&MenuItem::with_id(app, MENU_NEW_TASK, Msg::TrayNewTask.text(), true, None::<&str>)?)?;
+    menu.append(&MenuItem::with_id(app, MENU_POMODORO, Msg::TrayTogglePomodoro.text(), true, None::<&str>)?)?;
+    menu.append(&CheckMenuItem::with_id(app, MENU_PIN, Msg::TrayPin.text(), true, snapshot.pinned, None::<&str>)?)?;
+    menu.append(&MenuItem::with_id(app, MENU_COLLAPSE, Msg::TrayToggleCollapse.text(), true, None::<&str>)?)?;
+
+    menu.append(&PredefinedMenuItem::separator(app)?)?;
+    menu.append(&MenuItem::with_id(app, MENU_SHOW, Msg::TrayShow.text(), true, None::<&str>)?)?;
+    menu.append(&MenuItem::with_id(app, MENU_QUIT, Msg::TrayQuit.text(), true, None::<&str>)?)?;
+    Ok(menu)
+}
+
+fn append_task_group(app: &AppHandle, menu: &Menu<Wry>, title: Msg, tasks: &[TrayTask]) -> tauri::Result<()> {
+    if tasks.is_empty() {
+        return Ok(());
+    }
+
+    menu.append(&MenuItem::new(app, fill(title.text(), &[&tasks.len()]), false, None::<&str>)?)?;
+    for task in tasks.iter().take(MAX_TRAY_TASKS) {
+        let id = format!("{}{}", MENU_TASK_PREFIX, task.id);
+        menu.append(&CheckMenuItem::with_id(app, id, menu_title(&task.title), true, false, None::<&str>)?)?;
+    }
+    if tasks.len() > MAX_TRAY_TASKS {
+        let more = fill(Msg::TrayMoreTasks.text(), &[&(tasks.len() - MAX_TRAY_TASKS)]);
+        menu.append(&MenuItem::new(app, more, false, None::<&str>)?)?;
+    }
+    Ok(())
+}
+
+// 截断过长的标题；Windows 菜单把 & 当作助记符，需要转义
+fn menu_title(title: &str) -> String {
+    let mut text: String = title.chars().take(MAX_TITLE_CHARS).collect();
+    if title.chars().count() > MAX_TITLE_CHARS {
+        text.push('…');
+    }
+    text.replace('&', "&&")
+}
+
+/// 提示文字，如“3 项已过期 · 5 项今日到期”
+fn tooltip(snapshot: &TraySnapshot) -> String {
+    let mut parts = Vec::new();
+    if !snapshot.overdue.is_empty() {
+        parts.push(fill(Msg::TrayTooltipOverdue.text(), &[&snapshot.overdue.len()]));
+    }
+    if !snapshot.today.is_empty() {
+        parts.push(fill(Msg::TrayTooltipDueToday.text(), &[&snapshot.today.len()]));
+    }
+    if snapshot.pomodoro_running {
+        parts.push(Msg::TrayTooltipPomodoro.text().to_string());
+    }
+
+    if parts.is_empty() {
+        Msg::TrayTooltip.text().to_string()
+    } else {
+        format!("{}\n{}", Msg::TrayTooltip.text(), parts.join(" · "))
+    }
+}
+
+/// 有过期待办时显示红色角标，番茄钟进行中显示橙色角标
+fn tray_icon(app: &AppHandle, snapshot: &TraySnapshot) -> Option<Image<'static>> {
+    let base = app.default_window_icon()?;
+    let badge = if !snapshot.overdue.is_empty() {
+        Some(OVERDUE_BADGE)
+    } else if snapshot.pomodoro_running {
+        Some(POMODORO_BADGE)
+    } else {
+        None
+    };
+
+    let mut rgba = base.rgba().to_vec();
+    if let Some(color) = badge {
+        draw_badge(&mut rgba, base.width(), base.height(), color);
+    }
+    Some(Image::new_owned(rgba, base.width(), base.height()))
+}
+
+/// 在图标右上角画一个带白边的圆点，边缘按覆盖比例混合实现抗锯齿
+fn draw_badge(rgba: &mut [u8], width: u32, height: u32, color: [u8; 3]) {
+    let size = width.min(height) as f32;
+    let radius = size * 0.22;
+    let border = (size * 0.06).max(1.0);
+    let (cx, cy) = (width as f32 - radius - border, radius + border);
+
+    for y in 0..height {
+        for x in 0..width {
+            let (dx, dy) = (x as f32 + 0.5 - cx, y as f32 + 0.5 - cy);
+            let distance = (dx * dx + dy * dy).sqrt();
+            let coverage = (radius + border + 0.5 - distance).clamp(0.0, 1.0);
+            if coverage == 0.0 {
+                continue;
+            }
+
+            // 圆点内为角标颜色，外圈为白边
+            let inner = (radius + 0.5 - distance).clamp(0.0, 1.0);
+            let fill = color.map(|c| c as f32 * inner + 255.0 * (1.0 - inner));
+            let offset = ((y * width + x) * 4) as usize;
+            blend_over(&mut rgba[offset..offset + 4], fill, coverage);
+        }
+    }
+}
+
+// 非预乘 alpha 的 source-over 合成
+fn blend_over(pixel: &mut [u8], color: [f32; 3], alpha: f32) {
+    let dst_alpha = pixel[3] as f32 / 255.0;
+    let out_alpha = alpha + dst_alpha * (1.0 - alpha);
+    for i in 0..3 {
+        let value = (color[i] * alpha + pixel[i] as f32 * dst_alpha * (1.0 - alpha)) / out_alpha;
+        pixel[i] = value.round() as u8;
+    }
+    pixel[3] = (out_alpha * 255.0).round() as u8;
+}
+
+// 重建菜单、提示文字和图标；force 为 false 时数据没有变化则跳过
+fn update_tray(app: &AppHandle, force: bool) {
+    let state = app.state::<TrayState>();
+    // 持有该锁直到更新完成，避免并发刷新时旧数据覆盖新数据
+    let Ok(mut last) = state.last_snapshot.lock() else {
+        return;
+    };
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return;
+    };
+
+    let snapshot = load_snapshot(app);
+    if !force && last.as_ref() == Some(&snapshot) {
+        return;
+    }
+
+    let result = build_menu(app, &snapshot)
+        .and_then(|menu| tray.set_menu(Some(menu)))
+        .and_then(|_| tray.set_tooltip(Some(tooltip(&snapshot))))
+        .and_then(|_| tray.set_icon(tray_icon(app, &snapshot)));
+    match result {
+        Ok(()) => *last = Some(snapshot),
+        Err(e) => log::warn!("Failed to update tray: {}", e),
+    }
+}
+
+/// 待办、设置或语言变化后更新托盘菜单、提示文字和图标
+///
+/// 在后台线程中读取存储，调用方持有存储锁时也可以调用
+pub fn refresh_tray(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn_blocking(move || update_tray(&app, true));
+}
+
+/// 番茄钟开始或暂停时更新托盘图标的角标
+pub fn set_pomodoro_running(app: &AppHandle, running: bool) {
+    let previous = app.state::<TrayState>().pomodoro_running.swap(running, Ordering::Relaxed);
+    if previous != running {
+        refresh_tray(app);
+    }
+}
+
+/// 每分钟检查一次，待办到期或跨过零点时更新托盘
+pub fn start_refresh_timer(app: AppHandle) {
+    thread::spawn(move || loop {
+        // 截止时间精确到分钟，对齐到下一分钟开始时检查
+        let wait = 60 - u64::from(Local::now().second());
+        thread::sleep(Duration::from_secs(wait));
+        update_tray(&app, false);
+    });
+}
+
+fn complete_task(app: &AppHandle, id: &str) -> AppResult<Task> {
+    let updates = UpdateTaskRequest { completed: Some(true), ..Default::default() };
+    let state = app.state::<Mutex<Storage>>();
+    let task = state.lock()?.update_task(id, &updates)?;
+    Ok(task)
+}
+
+fn emit_tray_action(app: &AppHandle, action: &str) {
+    if let Err(e) = app.emit(TRAY_ACTION_EVENT, action) {
+        log::warn!("Failed to emit {}: {}", TRAY_ACTION_EVENT, e);
+    }
+}
+
+/// 创建系统托盘（左键单击唤醒，右键显示菜单）
+///
+/// 重要：必须保存返回的托盘对象到应用状态，否则会被释放导致功能失效
+pub fn create_tray(app: &AppHandle) -> tauri::Result<TrayIcon> {
+    let snapshot = load_snapshot(app);
+    let menu = build_menu(app, &snapshot)?;
+    let tooltip = tooltip(&snapshot);
+    let icon = tray_icon(app, &snapshot).unwrap();
+    if let Ok(mut last) = app.state::<TrayState>().last_snapshot.lock() {
+        *last = Some(snapshot);
+    }
+
+    TrayIconBuilder::with_id(TRAY_ID)
+        .tooltip(tooltip)
+        .icon(icon)
+        .menu(&menu)
+        .on_menu_event(|app, event| {
+            println!("🔔 托盘菜单事件: {}", event.id.as_ref());
+            match event.id.as_ref() {
+                MENU_SHOW => {
+                    println!("  └─ 显示窗口");
+                    show_main_window(app);
+                    println!("  └─ ✅ 窗口已显示并聚焦");
+                }
+                MENU_QUIT => {
+                    println!("  └─ 退出应用");
+                    app.exit(0);
+                }
+                MENU_NEW_TASK => show_quick_add(app),
+                MENU_POMODORO => {
+                    if let Err(e) = crate::pomodoro::toggle(app) {
+                        log::warn!("Failed to toggle pomodoro: {}", e);
+                    }
+                }
+                MENU_PIN => emit_tray_action(app, "toggle-pin"),
+                MENU_COLLAPSE => {
+                    show_main_window(app);
+                    emit_tray_action(app, "toggle-collapse");
+                }
+                id => {
+                    if let Some(task_id) = id.strip_prefix(MENU_TASK_PREFIX) {
+                        match complete_task(app, task_id) {
+                            Ok(task) => {
+                                log::info!("Completed task from tray: {}", task.title);
+                                notify_tasks_changed(app);
+                            }
+                            Err(e) => log::warn!("Failed to complete task from tray: {}", e),
+                        }
+                    }
+                }
+            }
+        })
+        .on_tray_icon_event(|tray, event| {
+            match event {
+                TrayIconEvent::Click { button, button_state, .. } => {
+                    println!("🔔 托盘点击事件 - 按钮: {:?}, 状态: {:?}", button, button_state);
+
+                    // 只响应左键释放事件
+                    if button == tauri::tray::MouseButton::Left && button_state == tauri::tray::MouseButtonState::Up {
+                        println!("  └─ ✅ 左键释放，立即处理（无阻塞）");
+
+                        if let Some(window) = tray.app_handle().get_webview_window("main") {
+                            println!("  ├─ 找到窗口: main");
+
+                            // 检查窗口是否可见
+                            match window.is_visible() {
+                                Ok(is_visible) => {
+                                    println!("  ├─ 窗口可见性: {}", is_visible);
+
+                                    if is_visible {
+                                        // 窗口已显示：聚焦并置顶到前台
+                                        println!("  ├─ 📍 窗口已显示，强制聚焦到前台");
+
+                                        // 先取消最小化
+                                        if let Err(e) = window.unminimize() {
+                                            println!("  │  ├─ ⚠️ 取消最小化失败: {:?}", e);
+                                        } else {
+                                            println!("  │  ├─ ✅ 取消最小化成功");
+                                        }
+
+                                        // 临时置顶（Windows需要这样才能强制前台）
+                                        if let Err(e) = window.set_always_on_top(true) {
+                                            println!("  │  ├─ ⚠️ 临时置顶失败: {:?}", e);
+                                        } else {
+                                            println!("  │  ├─ ✅ 临时置顶成功");
+                                        }
+
+                                        // 聚焦
+                                        if let Err(e) = window.set_focus() {
+                                            println!("  │  ├─ ⚠️ 聚焦失败: {:?}", e);
+                                        } else {
+                                            println!("  │  ├─ ✅ 聚焦成功");
+                                        }
+
+                                        // 立即取消置顶
+                                        if let Err(e) = window.set_always_on_top(false) {
+                                            println!("  │  └─ ⚠️ 取消置顶失败: {:?}", e);
+                                        } else {
+                                            println!("  │  └─ ✅ 取消置顶成功");
+                                        }
+                                    } else {
+                                        // 窗口隐藏：显示并聚焦
+                                        println!("  ├─ 👁️ 窗口隐藏，重新显示并聚焦");
+
+                                        // 先显示
+                                        if let Err(e) = window.show() {
+                                            println!("  │  ├─ ⚠️ 显示窗口失败: {:?}", e);
+                                        } else {
+                                            println!("  │  ├─ ✅ 显示窗口成功");
+                                        }
+
+                                        // 取消最小化
+                                        if let Err(e) = window.unminimize() {
+                                            println!("  │  ├─ ⚠️ 取消最小化失败: {:?}", e);
+                                        } else {
+                                            println!("  │  ├─ ✅ 取消最小化成功");
+                                        }
+
+                                        // 临时置顶以强制前台
+                                        if let Err(e) = window.set_always_on_top(true) {
+                                            println!("  │  ├─ ⚠️ 临时置顶失败: {:?}", e);
+                                        } else {
+                                            println!("  │  ├─ ✅ 临时置顶成功");
+                                        }
+
+                                        // 聚焦
+                                        if let Err(e) = window.set_focus() {
+                                            println!("  │  ├─ ⚠️ 聚焦失败: {:?}", e);
+                                        } else {
+                                            println!("  │  ├─ ✅ 聚焦成功");
+                                        }
+
+                                        // 取消置顶
+                                        if let Err(e) = window.set_always_on_top(false) {
+                                            println!("  │  └─ ⚠️ 取消置顶失败: {:?}", e);
+                                        } else {
+                                            println!("  │  └─ ✅ 取消置顶成功");
+                                        }
+                                    }
+
+                                    println!("✅ [处理完成] 托盘单击处理完成（无阻塞）\n");
+                                }
+                                Err(e) => {
+                                    println!("  └─ ❌ 检查窗口可见性失败: {:?}", e);
+                                }
+                            }
+                        } else {
+                            println!("  └─ ❌ 未找到窗口: main");
+                        }
+                    }
+                    // 不输出忽略消息，减少日志噪音
+                }
+                _ => {
+                    // 忽略其他事件（Move, Enter, Leave等），不输出任何日志
+                }
+            }
+        })
+        .build(app)
+}
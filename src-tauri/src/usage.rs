@@ -0,0 +1,149 @@
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration as StdDuration;
+use chrono::{Duration, Local, Utc};
+use tauri::{AppHandle, Manager};
+use uuid::Uuid;
+use crate::datetime::{parse_date, start_of_day};
+use crate::error::AppResult;
+use crate::models::{UsageDetail, UsageSession, UsageStats};
+use crate::stats::{compute_usage_details, compute_usage_stats};
+use crate::storage::Storage;
+use crate::window::MAIN_WINDOW;
+
+// 进行中的时段每隔一段时间写入一次，异常退出时最多丢失这段时间
+const CHECKPOINT_INTERVAL_SECS: u64 = 60;
+
+// 短于一分钟的时段不记录，如在托盘中误点打开又立即关闭
+const MIN_SESSION_SECS: i64 = 60;
+
+/// 托管在应用状态中，记录正在进行的使用时段
+#[derive(Default)]
+pub struct UsageState {
+    current: Mutex<Option<UsageSession>>,
+}
+
+/// 主窗口可见且未最小化即视为使用中
+///
+/// 窗口常驻在屏幕边缘或置顶时用户未必点击它，因此不要求获得焦点
+fn is_in_use(app: &AppHandle) -> bool {
+    app.get_webview_window(MAIN_WINDOW).is_some_and(|window| {
+        window.is_visible().unwrap_or(false) && !window.is_minimized().unwrap_or(false)
+    })
+}
+
+/// 按主窗口当前状态开始或结束使用时段，在窗口焦点、显示状态变化时调用
+pub fn sync(app: &AppHandle) {
+    if is_in_use(app) {
+        begin_session(app);
+    } else {
+        end_session(app);
+    }
+}
+
+fn begin_session(app: &AppHandle) {
+    let state = app.state::<UsageState>();
+    let Ok(mut current) = state.current.lock() else { return };
+    if current.is_none() {
+        let now = Utc::now();
+        *current = Some(UsageSession { id: Uuid::new_v4().to_string(), start: now, end: now });
+    }
+}
+
+/// 结束正在进行的时段并写入磁盘，应用退出时同样调用
+pub fn end_session(app: &AppHandle) {
+    // 先释放时段锁再写入，读取统计时是先取存储锁再取时段锁
+    let session = app.state::<UsageState>().current.lock().ok().and_then(|mut current| current.take());
+    if let Some(mut session) = session {
+        session.end = Utc::now();
+        save_session(app, &session);
+    }
+}
+
+fn save_session(app: &AppHandle, session: &UsageSession) {
+    if (session.end - session.start).num_seconds() < MIN_SESSION_SECS {
+        return;
+    }
+    if let Err(e) = write_session(app, session) {
+        log::warn!("Failed to save usage session: {}", e);
+    }
+}
+
+fn write_session(app: &AppHandle, session: &UsageSession) -> AppResult<()> {
+    let state = app.state::<Mutex<Storage>>();
+    let storage = state.lock()?;
+    storage.save_usage_session(session)
+}
+
+/// 定时检查主窗口状态并保存进行中的时段
+///
+/// 托盘切换显示等操作不一定触发窗口事件，由这里兜底
+pub fn start_tracking(app: AppHandle) {
+    sync(&app);
+    thread::spawn(move || loop {
+        thread::sleep(StdDuration::from_secs(CHECKPOINT_INTERVAL_SECS));
+        sync(&app);
+
+        let snapshot = app.state::<UsageState>().current.lock().ok().and_then(|current| current.clone());
+        if let Some(mut session) = snapshot {
+            session.end = Utc::now();
+            save_session(&app, &session);
+        }
+    });
+}
+
+/// 已保存的记录加上进行中的时段
+fn all_sessions(app: &AppHandle, storage: &Storage) -> AppResult<Vec<UsageSession>> {
+    let mut sessions = storage.load_usage_sessions()?;
+    let state = app.state::<UsageState>();
+    if let Some(mut session) = state.current.lock()?.clone() {
+        session.end = Utc::now();
+        sessions.retain(|s| s.id != session.id);
+        sessions.push(session);
+    }
+    Ok(sessions)
+}
+
+pub fn stats(app: &AppHandle, storage: &Storage) -> AppResult<UsageStats> {
+    let sessions = all_sessions(app, storage)?;
+    Ok(compute_usage_stats(&sessions, &Local::now()))
+}
+
+pub fn details(app: &AppHandle, storage: &Storage, start: chrono::NaiveDate, end: chrono::NaiveDate) -> AppResult<Vec<UsageDetail>> {
+    let sessions = all_sessions(app, storage)?;
+    Ok(compute_usage_details(&sessions, start, end, &Local))
+}
+
+/// 导入旧版本前端按天保存的使用分钟数，每天记为从零点开始的一段时段
+///
+/// 以日期作为记录 id，重复导入不会重复计算，返回导入的天数
+pub fn import_legacy(storage: &Storage, daily: &BTreeMap<String, u32>) -> AppResult<usize> {
+    let mut sessions = storage.load_usage_sessions()?;
+    let mut imported = 0;
+
+    for (date, minutes) in daily {
+        let Some(day) = parse_date(date) else { continue };
+        if *minutes == 0 {
+            continue;
+        }
+        let id = format!("legacy-{}", day);
+        if sessions.iter().any(|s| s.id == id) {
+            continue;
+        }
+
+        let start = start_of_day(day, &Local);
+        let end = (start + Duration::minutes(i64::from(*minutes))).min(start_of_day(day + Duration::days(1), &Local));
+        sessions.push(UsageSession {
+            id,
+            start: start.with_timezone(&Utc),
+            end: end.with_timezone(&Utc),
+        });
+        imported += 1;
+    }
+
+    if imported > 0 {
+        storage.save_usage_sessions(&mut sessions)?;
+    }
+    Ok(imported)
+}
@@ -0,0 +1,34 @@
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+
+pub const MAIN_WINDOW: &str = "main";
+
+// 通知前端切换页面，前端收到后调用 take_pending_route 取走目标路由
+pub const NAVIGATE_EVENT: &str = "navigate";
+
+/// 等待前端打开的路由；冷启动时前端尚未加载，事件会丢失，由前端加载完成后主动取走
+#[derive(Default)]
+pub struct PendingRoute(pub Mutex<Option<String>>);
+
+/// 显示主窗口并强制聚焦到前台（Windows 需要临时置顶才能抢到前台）
+pub fn show_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window(MAIN_WINDOW) {
+        let _ = window.show();
+        let _ = window.unminimize();
+        let _ = window.set_always_on_top(true);
+        let _ = window.set_focus();
+        let _ = window.set_always_on_top(false);
+    }
+}
+
+/// 唤醒主窗口并跳转到指定路由，如 /task/<id>
+pub fn navigate(app: &AppHandle, route: String) {
+    if let Ok(mut pending) = app.state::<PendingRoute>().0.lock() {
+        *pending = Some(route);
+    }
+
+    show_main_window(app);
+    if let Err(e) = app.emit(NAVIGATE_EVENT, ()) {
+        log::warn!("Failed to emit {}: {}", NAVIGATE_EVENT, e);
+    }
+}
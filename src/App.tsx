@@ -7,6 +7,8 @@ import Other from './pages/Other';
 import UsageStats from './pages/UsageStats';
 import StartupTip from './components/StartupTip';
 import { useSettingsStore } from './store/settingsStore'
+import { useTaskStore } from './store/taskStore'
+import { onTasksChanged } from './api/tauri'
 
 function App() {
   const { settings, loadSettings } = useSettingsStore()
@@ -15,6 +17,16 @@ function App() {
     loadSettings()
   }, [])
 
+  // 命令行等外部修改待办后重新加载
+  useEffect(() => {
+    const unlisten = onTasksChanged(() => {
+      useTaskStore.getState().loadTasks()
+    })
+    return () => {
+      unlisten.then(fn => fn())
+    }
+  }, [])
+
   useEffect(() => {
     const root = document.documentElement;
 
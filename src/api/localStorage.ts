@@ -1,4 +1,4 @@
-import type { Task, Settings, CreateTaskRequest, UpdateTaskRequest, TaskStats, ApiResponse, Theme } from '../types';
+import type { Task, Settings, CreateTaskRequest, UpdateTaskRequest, TaskStats, TaskTrendPoint, ApiResponse, Theme } from '../types';
 import { v4 as uuidv4 } from 'uuid';
 
 // localStorage键名常量
@@ -14,6 +14,15 @@ const DEFAULT_SETTINGS: Settings = {
   autoSave: true,
   isPinned: false,
   isCollapsed: false,
+  collapseMode: 'expanded',
+  useCapsuleMode: false,
+  transparentEnabled: false,
+  transparentLevel: 100,
+  isEdgeSnapped: false,
+  edgePosition: 'right',
+  locale: 'zh-CN',
+  apiEnabled: false,
+  apiPort: 27485,
 };
 
 // 辅助函数：安全的JSON解析
@@ -64,6 +73,8 @@ export const taskApi = {
         createdAt: new Date().toISOString(),
         updatedAt: new Date().toISOString(),
         attachments: request.attachments || [],
+        estimatedMinutes: request.estimatedMinutes || undefined,
+        focusMinutes: 0,
       };
       
       tasks.push(newTask);
@@ -86,11 +97,25 @@ export const taskApi = {
         return createResponse(null, false, '待办不存在');
       }
       
+      const now = new Date().toISOString();
+      const previous: Task = tasks[taskIndex];
       const updatedTask = {
-        ...tasks[taskIndex],
+        ...previous,
         ...updates,
-        updatedAt: new Date().toISOString(),
+        updatedAt: now,
       };
+
+      // 预计耗时为 0 表示清除
+      if (updates.estimatedMinutes === 0) {
+        delete updatedTask.estimatedMinutes;
+      }
+
+      // 记录完成时间，取消完成时清除
+      if (updates.completed === true && !previous.completed) {
+        updatedTask.completedAt = now;
+      } else if (updates.completed === false) {
+        delete updatedTask.completedAt;
+      }
       
       tasks[taskIndex] = updatedTask;
       localStorage.setItem(STORAGE_KEYS.TASKS, JSON.stringify(tasks));
@@ -146,6 +171,38 @@ export const taskApi = {
       return createResponse({ total: 0, completed: 0, pending: 0, overdue: 0, today: 0, highPriority: 0, mediumPriority: 0, lowPriority: 0 }, false, `获取统计失败: ${error}`);
     }
   },
+
+  // 获取每日新建/完成趋势
+  async getTaskTrend(startDate?: string, endDate?: string): Promise<ApiResponse<TaskTrendPoint[]>> {
+    try {
+      const tasksJson = localStorage.getItem(STORAGE_KEYS.TASKS);
+      const tasks: Task[] = safeJsonParse(tasksJson, []);
+
+      const toKey = (date: Date) =>
+        `${date.getFullYear()}-${String(date.getMonth() + 1).padStart(2, '0')}-${String(date.getDate()).padStart(2, '0')}`;
+      const end = endDate ? new Date(`${endDate}T00:00`) : new Date();
+      const start = startDate ? new Date(`${startDate}T00:00`) : new Date(end.getFullYear(), end.getMonth(), end.getDate() - 6);
+
+      const points = new Map<string, TaskTrendPoint>();
+      for (let day = new Date(start.getFullYear(), start.getMonth(), start.getDate()); day <= end; day.setDate(day.getDate() + 1)) {
+        const key = toKey(day);
+        points.set(key, { date: key, created: 0, completed: 0 });
+      }
+
+      tasks.forEach((task) => {
+        const created = points.get(toKey(new Date(task.createdAt)));
+        if (created) created.created += 1;
+        if (task.completed) {
+          const completed = points.get(toKey(new Date(task.completedAt || task.updatedAt)));
+          if (completed) completed.completed += 1;
+        }
+      });
+
+      return createResponse(Array.from(points.values()));
+    } catch (error) {
+      return createResponse([], false, `获取趋势统计失败: ${error}`);
+    }
+  },
 };
 
 // 设置相关API
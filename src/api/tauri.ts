@@ -1,5 +1,6 @@
 import { invoke } from '@tauri-apps/api/core';
-import type { Task, Settings, CreateTaskRequest, UpdateTaskRequest, TaskStats, ApiResponse } from '../types';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
+import type { Task, Settings, CreateTaskRequest, UpdateTaskRequest, TaskStats, TaskTrendPoint, ProductivityStats, ApiResponse, ApiStatus, ShortcutStatus, UsageStats, UsageDetail, PomodoroState, PomodoroSettings, FocusSession, TimeEntry, AddTimeEntryRequest, UpdateTimeEntryRequest, ExportReportRequest, ReportTemplate, ExportTasksCsvRequest, CsvInspection, ImportTasksCsvRequest, TaskImportResult, ImportFromAppRequest, TaskListRequest, SyncStatus, SyncConflict, ConfigureSyncRequest } from '../types';
 import * as localStorageApi from './localStorage';
 
 // 检测Tauri是否可用
@@ -85,6 +86,27 @@ export const taskApi = {
     }
     return await localStorageApi.taskApi.getTaskStats();
   },
+
+  // 获取每日新建/完成趋势（日期格式 YYYY-MM-DD，默认最近7天）
+  async getTaskTrend(startDate?: string, endDate?: string): Promise<ApiResponse<TaskTrendPoint[]>> {
+    if (isTauriAvailable) {
+      try {
+        return await invoke('get_task_trend', { startDate, endDate });
+      } catch (error) {
+        console.warn('Tauri API调用失败，使用localStorage fallback:', error);
+        return await localStorageApi.taskApi.getTaskTrend(startDate, endDate);
+      }
+    }
+    return await localStorageApi.taskApi.getTaskTrend(startDate, endDate);
+  },
+
+  // 完成热力图、连续完成天数等分析（日期格式 YYYY-MM-DD，默认截至今天的一年）
+  async getProductivityStats(startDate?: string, endDate?: string): Promise<ApiResponse<ProductivityStats>> {
+    if (!isTauriAvailable) {
+      return { success: false, data: null, error: '仅桌面端支持完成情况分析' };
+    }
+    return await invoke('get_productivity_stats', { startDate, endDate });
+  },
 };
 
 // 设置相关API
@@ -114,6 +136,164 @@ export const settingsApi = {
     }
     return await localStorageApi.settingsApi.updateSettings(settings);
   },
+
+  // 本地 HTTP 接口状态（仅桌面端可用）
+  async getApiStatus(): Promise<ApiResponse<ApiStatus>> {
+    if (!isTauriAvailable) {
+      return { success: false, data: null, error: '仅桌面端支持本地接口' };
+    }
+    return await invoke('get_api_status');
+  },
+
+  // 重新生成本地接口的访问令牌
+  async resetApiToken(): Promise<ApiResponse<string>> {
+    if (!isTauriAvailable) {
+      return { success: false, data: null, error: '仅桌面端支持本地接口' };
+    }
+    return await invoke('reset_api_token');
+  },
+};
+
+// 全局快捷键唤出的快速添加窗口（仅桌面端可用）
+export const quickAddApi = {
+  // 保存待办并收起快速添加窗口
+  async submit(request: CreateTaskRequest): Promise<ApiResponse<Task>> {
+    return await invoke('submit_quick_add', { request });
+  },
+
+  async hide(): Promise<void> {
+    if (isTauriAvailable) {
+      await invoke('hide_quick_add');
+    }
+  },
+
+  // 快捷键注册状态，被其他程序占用时包含错误信息
+  async getShortcutStatus(): Promise<ApiResponse<ShortcutStatus>> {
+    if (!isTauriAvailable) {
+      return { success: false, data: null, error: '仅桌面端支持全局快捷键' };
+    }
+    return await invoke('get_shortcut_status');
+  },
+};
+
+// 使用时长统计，由后端根据主窗口的显示状态记录（仅桌面端可用）
+export const usageApi = {
+  async getUsageStats(): Promise<ApiResponse<UsageStats>> {
+    if (!isTauriAvailable) {
+      return { success: false, data: null, error: '仅桌面端支持使用时长统计' };
+    }
+    return await invoke('get_usage_stats');
+  },
+
+  // 按天列出使用时长（日期格式 YYYY-MM-DD，默认最近7天）
+  async getUsageDetails(startDate?: string, endDate?: string): Promise<ApiResponse<UsageDetail[]>> {
+    if (!isTauriAvailable) {
+      return { success: false, data: null, error: '仅桌面端支持使用时长统计' };
+    }
+    return await invoke('get_usage_details', { startDate, endDate });
+  },
+
+  // 导入旧版本保存在浏览器缓存中的每日使用分钟数
+  async importLegacyUsage(daily: Record<string, number>): Promise<ApiResponse<number>> {
+    if (!isTauriAvailable) {
+      return { success: false, data: null, error: '仅桌面端支持使用时长统计' };
+    }
+    return await invoke('import_legacy_usage', { daily });
+  },
+};
+
+// 番茄钟由后端计时，窗口隐藏或刷新页面不影响（仅桌面端可用）
+export const pomodoroApi = {
+  async getState(): Promise<ApiResponse<PomodoroState>> {
+    if (!isTauriAvailable) {
+      return { success: false, data: null, error: '仅桌面端支持番茄钟' };
+    }
+    return await invoke('get_pomodoro_state');
+  },
+
+  // 传入待办 id 时专注时长计入该待办
+  async start(taskId?: string): Promise<ApiResponse<PomodoroState>> {
+    return await invoke('start_pomodoro', { taskId });
+  },
+
+  // 更换或清除专注的待办
+  async setTask(taskId: string | null): Promise<ApiResponse<PomodoroState>> {
+    return await invoke('set_pomodoro_task', { taskId });
+  },
+
+  // 已完成的专注记录，最新的在前
+  async getFocusSessions(taskId?: string): Promise<ApiResponse<FocusSession[]>> {
+    if (!isTauriAvailable) {
+      return { success: false, data: null, error: '仅桌面端支持番茄钟' };
+    }
+    return await invoke('get_focus_sessions', { taskId });
+  },
+
+  async pause(): Promise<ApiResponse<PomodoroState>> {
+    return await invoke('pause_pomodoro');
+  },
+
+  async reset(): Promise<ApiResponse<PomodoroState>> {
+    return await invoke('reset_pomodoro');
+  },
+
+  // 立即结束当前阶段并进入下一阶段
+  async skip(): Promise<ApiResponse<PomodoroState>> {
+    return await invoke('skip_pomodoro');
+  },
+
+  async updateSettings(settings: PomodoroSettings): Promise<ApiResponse<PomodoroState>> {
+    return await invoke('update_pomodoro_settings', { settings });
+  },
+};
+
+// 番茄钟开始、暂停或切换阶段（包括从托盘操作）时后端推送最新状态
+export async function onPomodoroChanged(handler: (state: PomodoroState) => void): Promise<UnlistenFn> {
+  if (!isTauriAvailable) {
+    return () => {};
+  }
+  return await listen<PomodoroState>('pomodoro-changed', (event) => handler(event.payload));
+}
+
+// 手动计时相关API，同一时间只有一个计时
+export const timeEntryApi = {
+  // 在待办上开始计时，其他待办上的计时会被停止
+  async start(taskId: string): Promise<ApiResponse<TimeEntry>> {
+    return await invoke('start_time_entry', { taskId });
+  },
+
+  async stop(): Promise<ApiResponse<TimeEntry | null>> {
+    return await invoke('stop_time_entry');
+  },
+
+  // 正在进行的计时，应用重启后据此恢复
+  async getRunning(): Promise<ApiResponse<TimeEntry | null>> {
+    if (!isTauriAvailable) {
+      return { success: false, data: null, error: '仅桌面端支持计时' };
+    }
+    return await invoke('get_running_time_entry');
+  },
+
+  // 计时记录，最新的在前
+  async getEntries(taskId?: string): Promise<ApiResponse<TimeEntry[]>> {
+    if (!isTauriAvailable) {
+      return { success: false, data: null, error: '仅桌面端支持计时' };
+    }
+    return await invoke('get_time_entries', { taskId });
+  },
+
+  // 补录一段已经结束的计时
+  async add(request: AddTimeEntryRequest): Promise<ApiResponse<TimeEntry>> {
+    return await invoke('add_time_entry', { request });
+  },
+
+  async update(id: string, updates: UpdateTimeEntryRequest): Promise<ApiResponse<TimeEntry>> {
+    return await invoke('update_time_entry', { id, updates });
+  },
+
+  async delete(id: string): Promise<ApiResponse<boolean>> {
+    return await invoke('delete_time_entry', { id });
+  },
 };
 
 // 数据管理API
@@ -158,6 +338,184 @@ export const dataApi = {
   },
 };
 
+// 报告相关API
+export const reportApi = {
+  // 由后端生成周报、月报并写入文件，未指定路径时弹出保存对话框；取消保存时 data 为 null
+  async exportReport(request: ExportReportRequest): Promise<ApiResponse<string | null>> {
+    if (!isTauriAvailable) {
+      return { success: false, data: null, error: '仅桌面端支持导出报告' };
+    }
+    return await invoke('export_report', { request });
+  },
+
+  // 按导出时的规则渲染报告内容，用于预览
+  async previewReport(request: ExportReportRequest): Promise<ApiResponse<string>> {
+    if (!isTauriAvailable) {
+      return { success: false, data: null, error: '仅桌面端支持预览报告' };
+    }
+    return await invoke('preview_report', { request });
+  },
+
+  // 数据目录 report_templates 下的自定义报告模板
+  async listTemplates(): Promise<ApiResponse<ReportTemplate[]>> {
+    if (!isTauriAvailable) {
+      return { success: true, data: [] };
+    }
+    return await invoke('list_report_templates');
+  },
+};
+
+// 待办 CSV 导入导出，仅桌面端支持
+export const csvApi = {
+  // 导出待办为带 BOM 的 CSV，未指定路径时弹出保存对话框；取消保存时 data 为 null
+  async exportTasks(request: ExportTasksCsvRequest = {}): Promise<ApiResponse<string | null>> {
+    if (!isTauriAvailable) {
+      return { success: false, data: null, error: '仅桌面端支持导出 CSV' };
+    }
+    return await invoke('export_tasks_csv', { request });
+  },
+
+  // 读取表头与前几行，并推测每列对应的字段
+  async inspect(content: string): Promise<ApiResponse<CsvInspection>> {
+    if (!isTauriAvailable) {
+      return { success: false, data: null, error: '仅桌面端支持导入 CSV' };
+    }
+    return await invoke('inspect_tasks_csv', { content });
+  },
+
+  // 按列映射导入，dryRun 为 true 时只返回解析结果
+  async importTasks(request: ImportTasksCsvRequest): Promise<ApiResponse<TaskImportResult>> {
+    if (!isTauriAvailable) {
+      return { success: false, data: null, error: '仅桌面端支持导入 CSV' };
+    }
+    return await invoke('import_tasks_csv', { request });
+  },
+
+  // 导入 Microsoft To Do、滴答清单、Todoist 或 todo.txt 的导出文件，dryRun 为 true 时只返回解析结果
+  async importFromApp(request: ImportFromAppRequest): Promise<ApiResponse<TaskImportResult>> {
+    if (!isTauriAvailable) {
+      return { success: false, data: null, error: '仅桌面端支持导入' };
+    }
+    return await invoke('import_from_app', { request });
+  },
+};
+
+// 待办清单 API（Markdown / todo.txt）
+export const taskListApi = {
+  // 生成清单文本，用于预览和复制到剪贴板
+  async render(request: TaskListRequest): Promise<ApiResponse<string>> {
+    if (!isTauriAvailable) {
+      return { success: false, data: null, error: '仅桌面端支持导出待办清单' };
+    }
+    return await invoke('render_task_list', { request });
+  },
+
+  // 导出到文件，未指定路径时弹出保存对话框；取消保存时 data 为 null
+  async exportList(request: TaskListRequest): Promise<ApiResponse<string | null>> {
+    if (!isTauriAvailable) {
+      return { success: false, data: null, error: '仅桌面端支持导出待办清单' };
+    }
+    return await invoke('export_task_list', { request });
+  },
+};
+
+// 文件夹同步相关API（仅桌面端）
+export const syncApi = {
+  async getStatus(): Promise<ApiResponse<SyncStatus>> {
+    if (!isTauriAvailable) {
+      return { success: false, data: null, error: '仅桌面端支持文件夹同步' };
+    }
+    return await invoke('get_sync_status');
+  },
+
+  // 弹出文件夹选择对话框，取消时 data 为 null
+  async chooseFolder(): Promise<ApiResponse<string | null>> {
+    if (!isTauriAvailable) {
+      return { success: false, data: null, error: '仅桌面端支持文件夹同步' };
+    }
+    return await invoke('choose_sync_folder');
+  },
+
+  // 设置同步文件夹与本机名称，folder 为空时关闭同步
+  async configure(request: ConfigureSyncRequest): Promise<ApiResponse<SyncStatus>> {
+    if (!isTauriAvailable) {
+      return { success: false, data: null, error: '仅桌面端支持文件夹同步' };
+    }
+    return await invoke('configure_sync', { request });
+  },
+
+  async syncNow(): Promise<ApiResponse<SyncStatus>> {
+    if (!isTauriAvailable) {
+      return { success: false, data: null, error: '仅桌面端支持文件夹同步' };
+    }
+    return await invoke('sync_now');
+  },
+
+  async getConflicts(): Promise<ApiResponse<SyncConflict[]>> {
+    if (!isTauriAvailable) {
+      return { success: false, data: null, error: '仅桌面端支持文件夹同步' };
+    }
+    return await invoke('get_sync_conflicts');
+  },
+
+  // useOther 为 true 时改用被覆盖的值，否则保留当前值
+  async resolveConflict(id: string, useOther: boolean): Promise<ApiResponse<boolean>> {
+    if (!isTauriAvailable) {
+      return { success: false, data: null, error: '仅桌面端支持文件夹同步' };
+    }
+    return await invoke('resolve_sync_conflict', { id, useOther });
+  },
+};
+
+// 待办在应用之外（命令行等）被修改时触发，返回取消监听的函数
+export async function onTasksChanged(handler: () => void): Promise<UnlistenFn> {
+  if (!isTauriAvailable) {
+    return () => {};
+  }
+  return await listen('tasks-changed', () => handler());
+}
+
+// 深链接等请求跳转页面时触发，收到后调用 takePendingRoute 取走目标路由
+export async function onNavigate(handler: () => void): Promise<UnlistenFn> {
+  if (!isTauriAvailable) {
+    return () => {};
+  }
+  return await listen('navigate', () => handler());
+}
+
+// 取走等待打开的路由，没有时返回 null
+export async function takePendingRoute(): Promise<string | null> {
+  if (!isTauriAvailable) {
+    return null;
+  }
+  const response: ApiResponse<string | null> = await invoke('take_pending_route');
+  return response.success ? response.data ?? null : null;
+}
+
+// 深链接处理失败时触发，参数为错误信息
+export async function onDeepLinkError(handler: (message: string) => void): Promise<UnlistenFn> {
+  if (!isTauriAvailable) {
+    return () => {};
+  }
+  return await listen<string>('deep-link-error', (event) => handler(event.payload));
+}
+
+// 快速添加窗口每次被唤出时触发
+export async function onQuickAddShown(handler: () => void): Promise<UnlistenFn> {
+  if (!isTauriAvailable) {
+    return () => {};
+  }
+  return await listen('quick-add-shown', () => handler());
+}
+
+// 托盘菜单请求前端执行的操作：toggle-pin、toggle-collapse
+export async function onTrayAction(handler: (action: string) => void): Promise<UnlistenFn> {
+  if (!isTauriAvailable) {
+    return () => {};
+  }
+  return await listen<string>('tray-action', (event) => handler(event.payload));
+}
+
 // 统一的API对象
 export const api = {
   tasks: taskApi,
@@ -165,4 +523,4 @@ export const api = {
   data: dataApi,
 };
 
-export default api;
\ No newline at end of file
+export default api;
@@ -0,0 +1,212 @@
+import React, { useState } from 'react';
+import { X, ArrowDownToLine, Upload, AlertTriangle } from 'lucide-react';
+import { toast } from 'sonner';
+import { csvApi } from '../api/tauri';
+import { useTaskStore } from '../store/taskStore';
+import type { ImportSource, TaskImportResult } from '../types';
+
+interface AppImportDialogProps {
+  isOpen: boolean;
+  onClose: () => void;
+}
+
+const SOURCES: { value: ImportSource; label: string; hint: string; accept: string }[] = [
+  { value: 'microsoftTodo', label: 'Microsoft To Do', hint: '通过 Microsoft Graph 导出的清单 JSON', accept: '.json' },
+  { value: 'tickTick', label: '滴答清单 / TickTick', hint: '设置 → 账户与安全 → 生成备份（CSV）', accept: '.csv' },
+  { value: 'todoist', label: 'Todoist', hint: '项目菜单 → 导出为模板（CSV）', accept: '.csv' },
+  { value: 'todoTxt', label: 'todo.txt', hint: '每行一个待办的 todo.txt 文件', accept: '.txt' }
+];
+
+const PRIORITY_LABELS = { high: '高', medium: '中', low: '低' } as const;
+
+export default function AppImportDialog({ isOpen, onClose }: AppImportDialogProps) {
+  const { loadTasks } = useTaskStore();
+  const [source, setSource] = useState<ImportSource>('microsoftTodo');
+  const [skipCompleted, setSkipCompleted] = useState(true);
+  const [content, setContent] = useState('');
+  const [fileName, setFileName] = useState('');
+  // 预览结果，导入前先确认
+  const [preview, setPreview] = useState<TaskImportResult | null>(null);
+  const [loading, setLoading] = useState(false);
+
+  if (!isOpen) return null;
+
+  const reset = () => {
+    setContent('');
+    setFileName('');
+    setPreview(null);
+  };
+
+  const handleClose = () => {
+    reset();
+    onClose();
+  };
+
+  const runImport = async (dryRun: boolean, text = content, skip = skipCompleted, from = source) => {
+    setLoading(true);
+    try {
+      const response = await csvApi.importFromApp({ source: from, content: text, skipCompleted: skip, dryRun });
+      if (!response.success || !response.data) {
+        toast.error(response.error || '导入失败，请重试');
+        setPreview(null);
+        return;
+      }
+      if (dryRun) {
+        setPreview(response.data);
+        return;
+      }
+      const { tasks, errors } = response.data;
+      await loadTasks();
+      toast.success(errors.length > 0
+        ? `已导入 ${tasks.length} 个待办，跳过 ${errors.length} 条`
+        : `已导入 ${tasks.length} 个待办`);
+      handleClose();
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  const handleFile = async (file: File) => {
+    const text = await file.text();
+    setContent(text);
+    setFileName(file.name);
+    await runImport(true, text);
+  };
+
+  // 切换来源或选项后重新预览
+  const changeSource = (value: ImportSource) => {
+    setSource(value);
+    if (content) runImport(true, content, skipCompleted, value);
+  };
+
+  const changeSkipCompleted = (value: boolean) => {
+    setSkipCompleted(value);
+    if (content) runImport(true, content, value);
+  };
+
+  const current = SOURCES.find(s => s.value === source)!;
+
+  return (
+    <div className="fixed inset-0 bg-black/50 flex items-center justify-center z-50 p-4">
+      <div className="bg-background border border-border rounded-lg shadow-xl w-full max-w-2xl max-h-[90vh] overflow-y-auto">
+        <div className="flex items-center justify-between p-6 border-b border-border">
+          <div className="flex items-center gap-2">
+            <ArrowDownToLine className="w-5 h-5 text-primary" />
+            <h2 className="text-xl font-semibold text-foreground">从其他应用导入</h2>
+          </div>
+          <button
+            onClick={handleClose}
+            className="p-1 hover:bg-accent rounded-lg transition-colors"
+          >
+            <X className="w-5 h-5" />
+          </button>
+        </div>
+
+        <div className="p-6 space-y-6">
+          {/* 来源 */}
+          <div>
+            <label className="block text-sm font-medium text-foreground mb-3">来源应用</label>
+            <div className="grid grid-cols-2 gap-2">
+              {SOURCES.map(s => (
+                <button
+                  key={s.value}
+                  onClick={() => changeSource(s.value)}
+                  className={`p-3 rounded-lg border text-sm text-left transition-colors ${
+                    source === s.value
+                      ? 'border-primary bg-primary/10 text-primary'
+                      : 'border-border hover:border-primary/50'
+                  }`}
+                >
+                  {s.label}
+                </button>
+              ))}
+            </div>
+            <p className="mt-2 text-xs text-muted-foreground">{current.hint}</p>
+          </div>
+
+          <div className="space-y-3">
+            <label className="flex items-center justify-center gap-2 p-3 border border-dashed border-border rounded-lg text-sm text-muted-foreground hover:border-primary/50 cursor-pointer transition-colors">
+              <Upload className="w-4 h-4" />
+              {fileName || '选择导出文件'}
+              <input
+                type="file"
+                accept={current.accept}
+                className="hidden"
+                onChange={(e) => {
+                  const file = e.target.files?.[0];
+                  e.target.value = '';
+                  if (file) handleFile(file);
+                }}
+              />
+            </label>
+            <label className="flex items-center gap-2 text-sm">
+              <input
+                type="checkbox"
+                checked={skipCompleted}
+                onChange={(e) => changeSkipCompleted(e.target.checked)}
+                className="rounded border-border"
+              />
+              跳过已完成的待办
+            </label>
+          </div>
+
+          {/* 预览 */}
+          {preview && (
+            <div className="space-y-3">
+              <div className="text-sm text-muted-foreground">
+                将导入 {preview.tasks.length} 个待办
+                {preview.errors.length > 0 && `，${preview.errors.length} 条无法导入`}
+              </div>
+              {preview.tasks.length > 0 && (
+                <div className="max-h-64 overflow-y-auto border border-border rounded-lg divide-y divide-border">
+                  {preview.tasks.map(task => (
+                    <div key={task.id} className="px-3 py-2 text-sm">
+                      <div className="flex items-center justify-between gap-3">
+                        <span className={task.completed ? 'line-through text-muted-foreground' : 'text-foreground'}>
+                          {task.title}
+                        </span>
+                        <span className="flex-shrink-0 text-xs text-muted-foreground">
+                          {PRIORITY_LABELS[task.priority]}
+                          {task.dueDate && ` · ${task.dueDate.replace('T', ' ')}`}
+                        </span>
+                      </div>
+                      {task.description && (
+                        <div className="text-xs text-muted-foreground whitespace-pre-line line-clamp-2">{task.description}</div>
+                      )}
+                    </div>
+                  ))}
+                </div>
+              )}
+              {preview.errors.length > 0 && (
+                <div className="max-h-32 overflow-y-auto text-sm text-red-600">
+                  {preview.errors.map(error => (
+                    <div key={error.row} className="flex items-start gap-1">
+                      <AlertTriangle className="w-3.5 h-3.5 mt-0.5 flex-shrink-0" />
+                      第 {error.row} {source === 'microsoftTodo' ? '个' : '行'}：{error.message}
+                    </div>
+                  ))}
+                </div>
+              )}
+            </div>
+          )}
+
+          <div className="flex justify-end gap-3">
+            <button
+              onClick={handleClose}
+              className="px-4 py-2 border border-border rounded-lg hover:bg-accent transition-colors text-sm"
+            >
+              取消
+            </button>
+            <button
+              onClick={() => runImport(false)}
+              disabled={loading || !preview || preview.tasks.length === 0}
+              className="px-4 py-2 bg-primary text-primary-foreground rounded-lg hover:bg-primary/90 disabled:opacity-50 disabled:cursor-not-allowed transition-colors text-sm"
+            >
+              {loading ? '处理中...' : '导入'}
+            </button>
+          </div>
+        </div>
+      </div>
+    </div>
+  );
+}
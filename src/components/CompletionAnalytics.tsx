@@ -0,0 +1,127 @@
+import React, { useEffect, useState } from 'react';
+import { Flame, Trophy, CalendarDays, Clock } from 'lucide-react';
+import { taskApi } from '../api/tauri';
+import type { HeatmapDay, Priority, ProductivityStats } from '../types';
+
+const WEEKDAYS = ['周一', '周二', '周三', '周四', '周五', '周六', '周日'];
+
+const PRIORITY_LABELS: Record<Priority, string> = {
+  high: '🔴 高',
+  medium: '🟡 中',
+  low: '🟢 低'
+};
+
+// 与 GitHub 贡献图相同的五级颜色
+const LEVEL_COLORS = [
+  'bg-accent',
+  'bg-green-200',
+  'bg-green-400',
+  'bg-green-600',
+  'bg-green-800'
+];
+
+// 按周分列，每列从周一到周日，第一列前面补空位
+function toWeeks(days: HeatmapDay[]): (HeatmapDay | null)[][] {
+  if (days.length === 0) {
+    return [];
+  }
+  const offset = (new Date(`${days[0].date}T00:00:00`).getDay() + 6) % 7;
+  const cells: (HeatmapDay | null)[] = [...Array(offset).fill(null), ...days];
+  const weeks = [];
+  for (let i = 0; i < cells.length; i += 7) {
+    weeks.push(cells.slice(i, i + 7));
+  }
+  return weeks;
+}
+
+function formatHours(hours?: number): string {
+  if (hours === undefined) {
+    return '-';
+  }
+  return hours < 24 ? `${hours} 小时` : `${(hours / 24).toFixed(1)} 天`;
+}
+
+export default function CompletionAnalytics() {
+  const [stats, setStats] = useState<ProductivityStats | null>(null);
+
+  // 仅桌面端由后端统计，默认最近一年
+  useEffect(() => {
+    if (typeof window === 'undefined' || !(window as any).__TAURI__) {
+      return;
+    }
+    taskApi.getProductivityStats().then(response => {
+      if (response.success && response.data) {
+        setStats(response.data);
+      }
+    });
+  }, []);
+
+  if (!stats) {
+    return null;
+  }
+
+  const weeks = toWeeks(stats.heatmap);
+
+  return (
+    <div className="bg-background border border-border rounded-lg p-4 space-y-4">
+      <h3 className="font-medium text-foreground flex items-center gap-2">
+        <CalendarDays className="w-4 h-4 text-primary" />
+        完成情况（{stats.startDate} - {stats.endDate}，共 {stats.totalCompleted} 个）
+      </h3>
+
+      {/* 完成热力图 */}
+      <div className="overflow-x-auto">
+        <div className="flex gap-[3px]">
+          {weeks.map((week, i) => (
+            <div key={i} className="flex flex-col gap-[3px]">
+              {week.map((day, j) => (
+                <div
+                  key={j}
+                  title={day ? `${day.date}：完成 ${day.completed} 个` : undefined}
+                  className={`w-2.5 h-2.5 rounded-sm ${day ? LEVEL_COLORS[day.level] : 'bg-transparent'}`}
+                />
+              ))}
+            </div>
+          ))}
+        </div>
+      </div>
+
+      <div className="grid grid-cols-2 lg:grid-cols-4 gap-3 text-sm">
+        <div className="flex items-center gap-2">
+          <Flame className="w-4 h-4 text-orange-500" />
+          <span className="text-muted-foreground">当前连续</span>
+          <span className="font-medium">{stats.currentStreak} 天</span>
+        </div>
+        <div className="flex items-center gap-2">
+          <Trophy className="w-4 h-4 text-yellow-500" />
+          <span className="text-muted-foreground">最长连续</span>
+          <span className="font-medium">{stats.longestStreak} 天</span>
+        </div>
+        <div className="flex items-center gap-2">
+          <CalendarDays className="w-4 h-4 text-blue-500" />
+          <span className="text-muted-foreground">最高效</span>
+          <span className="font-medium">
+            {stats.mostProductiveWeekday !== undefined ? WEEKDAYS[stats.mostProductiveWeekday] : '-'}
+          </span>
+        </div>
+        <div className="flex items-center gap-2">
+          <Clock className="w-4 h-4 text-purple-500" />
+          <span className="text-muted-foreground">高峰时段</span>
+          <span className="font-medium">
+            {stats.mostProductiveHour !== undefined ? `${stats.mostProductiveHour}:00 - ${stats.mostProductiveHour + 1}:00` : '-'}
+          </span>
+        </div>
+      </div>
+
+      {/* 各优先级从创建到完成的平均用时 */}
+      <div className="grid grid-cols-3 gap-3 text-sm">
+        {stats.averageCompletion.map(item => (
+          <div key={item.priority} className="bg-accent/30 rounded-lg p-2">
+            <div className="text-muted-foreground">{PRIORITY_LABELS[item.priority]}优先级 · {item.completed} 个</div>
+            <div className="font-medium">平均 {formatHours(item.averageHours)}</div>
+          </div>
+        ))}
+      </div>
+    </div>
+  );
+}
@@ -1,7 +1,9 @@
 import React, { useState, useEffect } from 'react';
-import { Outlet, Link } from 'react-router-dom';
+import { Outlet, Link, useNavigate } from 'react-router-dom';
 import { Settings, User, Pin, PinOff, ChevronUp, ChevronDown, X } from 'lucide-react';
 import { invoke } from '@tauri-apps/api/core';
+import { toast } from 'sonner';
+import { onNavigate, takePendingRoute, onDeepLinkError, onTrayAction } from '../api/tauri';
 import { useSettingsStore } from '../store/settingsStore';
 import { useUsageStore } from '../store/usageStore';
 import UserProfileModal from './UserProfileModal';
@@ -21,6 +23,42 @@ export default function Layout() {
 
   const { settings, toggleIsPinned, toggleIsCollapsed } = useSettingsStore();
   const { pomodoro, formatTime } = useUsageStore();
+  const navigate = useNavigate();
+
+  // 深链接跳转：加载完成时先处理冷启动留下的路由，之后按事件处理
+  useEffect(() => {
+    const openPendingRoute = async () => {
+      const route = await takePendingRoute();
+      if (route) {
+        navigate(route);
+      }
+    };
+
+    openPendingRoute();
+    const unlistenNavigate = onNavigate(openPendingRoute);
+    const unlistenError = onDeepLinkError((message) => toast.error(message));
+    return () => {
+      unlistenNavigate.then(fn => fn());
+      unlistenError.then(fn => fn());
+    };
+  }, [navigate]);
+
+  // 托盘菜单中的置顶、折叠操作
+  useEffect(() => {
+    const unlisten = onTrayAction((action) => {
+      switch (action) {
+        case 'toggle-pin':
+          useSettingsStore.getState().toggleIsPinned();
+          break;
+        case 'toggle-collapse':
+          useSettingsStore.getState().toggleIsCollapsed();
+          break;
+      }
+    });
+    return () => {
+      unlisten.then(fn => fn());
+    };
+  }, []);
 
   // 轮播励志名言
   useEffect(() => {
@@ -0,0 +1,136 @@
+import { useState, useEffect } from 'react';
+import { Globe, Copy, RefreshCw } from 'lucide-react';
+import { toast } from 'sonner';
+import { useSettingsStore } from '../store/settingsStore';
+import { settingsApi } from '../api/tauri';
+import type { ApiStatus } from '../types';
+
+// 本地 HTTP 接口设置：开关、端口、访问令牌
+export default function LocalApiCard() {
+  const { settings, updateSettings } = useSettingsStore();
+  const [status, setStatus] = useState<ApiStatus | null>(null);
+  const [port, setPort] = useState(String(settings.apiPort ?? 27485));
+
+  const loadStatus = async () => {
+    const response = await settingsApi.getApiStatus();
+    if (response.success && response.data) {
+      setStatus(response.data);
+    }
+  };
+
+  useEffect(() => {
+    loadStatus();
+  }, [settings.apiEnabled, settings.apiPort]);
+
+  useEffect(() => {
+    setPort(String(settings.apiPort ?? 27485));
+  }, [settings.apiPort]);
+
+  const handleToggle = async (apiEnabled: boolean) => {
+    await updateSettings({ apiEnabled });
+    loadStatus();
+  };
+
+  const handlePortCommit = async () => {
+    const apiPort = Number(port);
+    if (!Number.isInteger(apiPort) || apiPort < 1 || apiPort > 65535) {
+      toast.error('端口应为 1-65535 之间的整数');
+      setPort(String(settings.apiPort));
+      return;
+    }
+    if (apiPort !== settings.apiPort) {
+      await updateSettings({ apiPort });
+      loadStatus();
+    }
+  };
+
+  const handleCopyToken = async () => {
+    if (!status) return;
+    await navigator.clipboard.writeText(status.token);
+    toast.success('访问令牌已复制');
+  };
+
+  const handleResetToken = async () => {
+    const response = await settingsApi.resetApiToken();
+    if (response.success) {
+      toast.success('访问令牌已重置，旧令牌立即失效');
+      loadStatus();
+    } else {
+      toast.error(response.error || '重置访问令牌失败');
+    }
+  };
+
+  return (
+    <div className="card card-shadow hover-lift slide-up">
+      <div className="card-header py-4">
+        <div className="flex items-center justify-between w-full">
+          {/* 左侧标题和描述 */}
+          <div className="flex items-center">
+            <div className="p-2 bg-cyan-500/10 rounded-lg mr-3">
+              <Globe className="w-5 h-5 text-cyan-600" />
+            </div>
+            <div>
+              <h2 className="text-lg font-semibold text-foreground">本地接口</h2>
+              <p className="card-description text-sm">供脚本和编辑器插件通过 HTTP 管理待办</p>
+            </div>
+          </div>
+
+          {/* 右侧开关按钮 */}
+          <label className="relative inline-flex items-center cursor-pointer">
+            <input
+              type="checkbox"
+              checked={settings.apiEnabled ?? false}
+              onChange={(e) => handleToggle(e.target.checked)}
+              className="sr-only peer"
+            />
+            <div className="w-11 h-6 bg-cyan-100 peer-focus:ring-cyan-200 rounded-full peer peer-checked:bg-cyan-500 after:bg-white after:border-cyan-200 peer-checked:after:translate-x-full after:content-[''] after:absolute after:top-[2px] after:left-[2px] after:rounded-full after:h-5 after:w-5 after:transition-all"></div>
+          </label>
+        </div>
+      </div>
+
+      {settings.apiEnabled && (
+        <div className="card-content pt-0 space-y-3 text-sm">
+          <div className="flex items-center justify-between">
+            <span className="text-muted-foreground">端口</span>
+            <input
+              type="number"
+              min={1}
+              max={65535}
+              value={port}
+              onChange={(e) => setPort(e.target.value)}
+              onBlur={handlePortCommit}
+              className="w-24 px-2 py-1 rounded-lg border border-border bg-background text-foreground"
+            />
+          </div>
+
+          {status && (
+            <>
+              <div className="flex items-center justify-between">
+                <span className="text-muted-foreground">地址</span>
+                <code className="text-foreground">{status.url}</code>
+              </div>
+              {status.error && (
+                <p className="text-red-500">{status.error}</p>
+              )}
+              <div className="flex items-center justify-between gap-2">
+                <span className="text-muted-foreground">访问令牌</span>
+                <div className="flex items-center gap-2">
+                  <code className="text-foreground">{status.token.slice(0, 8)}…</code>
+                  <button onClick={handleCopyToken} className="p-1 rounded hover:bg-accent" title="复制">
+                    <Copy className="w-4 h-4" />
+                  </button>
+                  <button onClick={handleResetToken} className="p-1 rounded hover:bg-accent" title="重置">
+                    <RefreshCw className="w-4 h-4" />
+                  </button>
+                </div>
+              </div>
+              <p className="text-xs text-muted-foreground">
+                请求需携带 Authorization: Bearer &lt;令牌&gt;，仅本机可访问
+              </p>
+            </>
+          )}
+        </div>
+      )}
+    </div>
+  );
+}
@@ -0,0 +1,99 @@
+import React, { useState, useEffect } from 'react';
+import { Keyboard, X } from 'lucide-react';
+import { useSettingsStore } from '../store/settingsStore';
+import { quickAddApi } from '../api/tauri';
+import type { ShortcutStatus } from '../types';
+
+// 单独按下修饰键时不算完成录制
+const MODIFIER_CODES = ['ControlLeft', 'ControlRight', 'ShiftLeft', 'ShiftRight', 'AltLeft', 'AltRight', 'MetaLeft', 'MetaRight'];
+
+// 把按键事件转换为后端识别的快捷键写法，如 CommandOrControl+Shift+Space
+function toAccelerator(e: React.KeyboardEvent): string | null {
+  if (MODIFIER_CODES.includes(e.code)) return null;
+
+  const modifiers: string[] = [];
+  if (e.ctrlKey) modifiers.push('Control');
+  if (e.metaKey) modifiers.push('Super');
+  if (e.altKey) modifiers.push('Alt');
+  if (e.shiftKey) modifiers.push('Shift');
+
+  // 全局快捷键至少需要一个修饰键，避免占用普通按键
+  if (modifiers.length === 0) return null;
+  return [...modifiers, e.code].join('+');
+}
+
+// 快速添加窗口的全局快捷键设置
+export default function QuickAddShortcutCard() {
+  const { settings, updateSettings } = useSettingsStore();
+  const [status, setStatus] = useState<ShortcutStatus | null>(null);
+  const [recording, setRecording] = useState(false);
+
+  const loadStatus = async () => {
+    const response = await quickAddApi.getShortcutStatus();
+    if (response.success && response.data) {
+      setStatus(response.data);
+    }
+  };
+
+  useEffect(() => {
+    loadStatus();
+  }, [settings.quickAddShortcut]);
+
+  const saveShortcut = async (quickAddShortcut: string) => {
+    setRecording(false);
+    await updateSettings({ quickAddShortcut });
+    loadStatus();
+  };
+
+  const handleKeyDown = (e: React.KeyboardEvent) => {
+    e.preventDefault();
+    if (e.key === 'Escape') {
+      setRecording(false);
+      return;
+    }
+    const accelerator = toAccelerator(e);
+    if (accelerator) {
+      saveShortcut(accelerator);
+    }
+  };
+
+  // 以后端保存的为准：注册失败时前端设置不会更新
+  const shortcut = status?.shortcut ?? settings.quickAddShortcut ?? '';
+
+  return (
+    <div className="card card-shadow hover-lift slide-up">
+      <div className="card-header py-4">
+        <div className="flex items-center justify-between w-full">
+          <div className="flex items-center">
+            <div className="p-2 bg-indigo-500/10 rounded-lg mr-3">
+              <Keyboard className="w-5 h-5 text-indigo-600" />
+            </div>
+            <div>
+              <h2 className="text-lg font-semibold text-foreground">快速添加快捷键</h2>
+              <p className="card-description text-sm">在任意程序中按下即可唤出快速添加窗口</p>
+            </div>
+          </div>
+        </div>
+      </div>
+
+      <div className="card-content pt-0 space-y-2 text-sm">
+        <div className="flex items-center justify-between gap-2">
+          <button
+            onClick={() => setRecording(true)}
+            onKeyDown={recording ? handleKeyDown : undefined}
+            onBlur={() => setRecording(false)}
+            className="flex-1 px-3 py-2 rounded-lg border border-border bg-background text-left text-foreground"
+          >
+            {recording ? '请按下新的组合键…' : shortcut || '未设置'}
+          </button>
+          {shortcut && (
+            <button onClick={() => saveShortcut('')} className="p-2 rounded hover:bg-accent" title="清除">
+              <X className="w-4 h-4" />
+            </button>
+          )}
+        </div>
+        {status?.error && <p className="text-red-500">{status.error}</p>}
+      </div>
+    </div>
+  );
+}
@@ -1,5 +1,5 @@
-import React, { useState } from 'react';
-import { Calendar, FileDown, Clock, X, CheckCircle } from 'lucide-react';
+import React, { useEffect, useState } from 'react';
+import { Calendar, FileDown, Clock, X, CheckCircle, Eye } from 'lucide-react';
 import { useTaskStore } from '../store/taskStore';
 import { 
   generateReportMarkdown, 
@@ -8,7 +8,8 @@ import {
   getMonthRange,
   type ReportOptions 
 } from '../utils/reportGenerator';
-import { invoke } from '@tauri-apps/api/core';
+import { reportApi } from '../api/tauri';
+import type { ExportReportRequest, ReportFormat, ReportTemplate } from '../types';
 import { toast } from 'sonner';
 
 interface ReportExportDialogProps {
@@ -27,20 +28,99 @@ export default function ReportExportDialog({ isOpen, onClose }: ReportExportDial
     includeOverdue: true,
     includePending: true
   });
+  const [format, setFormat] = useState<ReportFormat>('markdown');
   const [exporting, setExporting] = useState(false);
+  // 自定义模板文件名，空字符串表示默认模板
+  const [template, setTemplate] = useState('');
+  const [templates, setTemplates] = useState<ReportTemplate[]>([]);
+  const [preview, setPreview] = useState<{ format: ReportFormat; content: string } | null>(null);
+  const [previewing, setPreviewing] = useState(false);
+
+  // 桌面端由后端生成报告，内容由模板决定；浏览器中按勾选的内容生成 Markdown
+  const isDesktop = typeof window !== 'undefined' && !!(window as any).__TAURI__;
+
+  // 每次打开时重新读取模板目录，方便编辑模板后直接预览
+  useEffect(() => {
+    if (!isOpen || !isDesktop) {
+      return;
+    }
+    reportApi.listTemplates().then(response => {
+      const list = response.success && response.data ? response.data : [];
+      setTemplates(list);
+      setTemplate(current => list.some(t => t.name === current) ? current : '');
+    });
+  }, [isOpen, isDesktop]);
 
   if (!isOpen) return null;
 
+  // 选择了自定义模板时格式由模板决定
+  const selectedTemplate = templates.find(t => t.name === template);
+  const effectiveFormat = selectedTemplate ? selectedTemplate.format : format;
+
+  const buildRequest = (): ExportReportRequest | null => {
+    if (customRange && (!startDate || !endDate)) {
+      toast.error('请选择完整的时间范围');
+      return null;
+    }
+    return {
+      kind: reportType,
+      format: effectiveFormat,
+      ...(customRange ? { startDate, endDate } : {}),
+      ...(selectedTemplate ? { template: selectedTemplate.name } : {})
+    };
+  };
+
+  const handlePreview = async () => {
+    const request = buildRequest();
+    if (!request) {
+      return;
+    }
+    setPreviewing(true);
+    try {
+      const response = await reportApi.previewReport(request);
+      if (!response.success) {
+        toast.error(response.error || '预览失败，请检查模板');
+        return;
+      }
+      setPreview({ format: request.format, content: response.data ?? '' });
+    } finally {
+      setPreviewing(false);
+    }
+  };
+
   const handleExport = async () => {
     setExporting(true);
     try {
+      if (customRange && (!startDate || !endDate)) {
+        toast.error('请选择完整的时间范围');
+        return;
+      }
+
+      const periodText = reportType === 'week' ? '周报' : '月报';
+
+      // 桌面端由后端按完成时间、专注与计时记录生成，并直接写入选择的路径
+      if (isDesktop) {
+        const request = buildRequest();
+        if (!request) {
+          return;
+        }
+        const response = await reportApi.exportReport(request);
+        if (!response.success) {
+          toast.error(response.error || '导出失败，请重试');
+          return;
+        }
+        // 在保存对话框中取消
+        if (!response.data) {
+          return;
+        }
+        toast.success(`${periodText}已保存到 ${response.data}`);
+        onClose();
+        return;
+      }
+
       // 确定时间范围
       let dateRange;
       if (customRange) {
-        if (!startDate || !endDate) {
-          toast.error('请选择完整的时间范围');
-          return;
-        }
         dateRange = {
           start: new Date(startDate + 'T00:00:00'),
           end: new Date(endDate + 'T23:59:59')
@@ -62,10 +142,9 @@ export default function ReportExportDialog({ isOpen, onClose }: ReportExportDial
 
       // 生成文件名和内容
       const formatDate = (date: Date) => date.toISOString().slice(0, 10);
-      const periodText = reportType === 'week' ? '周报' : '月报';
       const fileName = `事半SemiDone_${periodText}_${formatDate(dateRange.start)}_${formatDate(dateRange.end)}.md`;
 
-      // 使用浏览器API下载文件
+      // 浏览器中只能生成 Markdown，使用浏览器API下载文件
       const blob = new Blob([markdownContent], { type: 'text/markdown;charset=utf-8' });
       const url = URL.createObjectURL(blob);
       const link = document.createElement('a');
@@ -197,42 +276,122 @@ export default function ReportExportDialog({ isOpen, onClose }: ReportExportDial
             )}
           </div>
 
-          {/* 内容选项 */}
-          <div>
-            <label className="block text-sm font-medium text-foreground mb-3">包含内容</label>
-            <div className="space-y-2">
-              <label className="flex items-center gap-2 text-sm">
-                <input
-                  type="checkbox"
-                  checked={options.includeCompleted}
-                  onChange={(e) => setOptions(prev => ({ ...prev, includeCompleted: e.target.checked }))}
-                  className="rounded border-border"
-                />
-                <CheckCircle className="w-4 h-4 text-green-500" />
-                已完成任务
-              </label>
-              <label className="flex items-center gap-2 text-sm">
-                <input
-                  type="checkbox"
-                  checked={options.includePending}
-                  onChange={(e) => setOptions(prev => ({ ...prev, includePending: e.target.checked }))}
-                  className="rounded border-border"
-                />
-                <Clock className="w-4 h-4 text-blue-500" />
-                待完成任务
-              </label>
-              <label className="flex items-center gap-2 text-sm">
-                <input
-                  type="checkbox"
-                  checked={options.includeOverdue}
-                  onChange={(e) => setOptions(prev => ({ ...prev, includeOverdue: e.target.checked }))}
-                  className="rounded border-border"
+          {/* 导出格式，浏览器中只支持 Markdown */}
+          {isDesktop && (
+            <div>
+              <label className="block text-sm font-medium text-foreground mb-3">导出格式</label>
+              <div className="grid grid-cols-3 gap-3">
+                {([
+                  ['markdown', 'Markdown'],
+                  ['html', 'HTML'],
+                  ['csv', 'CSV']
+                ] as [ReportFormat, string][]).map(([value, label]) => (
+                  <button
+                    key={value}
+                    onClick={() => {
+                      setFormat(value);
+                      setTemplate('');
+                    }}
+                    className={`p-2 rounded-lg border text-sm font-medium transition-all ${
+                      effectiveFormat === value
+                        ? 'border-primary bg-primary/10 text-primary'
+                        : 'border-border hover:border-primary/50'
+                    }`}
+                  >
+                    {label}
+                  </button>
+                ))}
+              </div>
+            </div>
+          )}
+
+          {/* 报告模板，自定义模板放在数据目录的 report_templates 下 */}
+          {isDesktop && effectiveFormat !== 'csv' && (
+            <div>
+              <label className="block text-sm font-medium text-foreground mb-3">报告模板</label>
+              <select
+                value={template}
+                onChange={(e) => setTemplate(e.target.value)}
+                className="w-full p-2 border border-border rounded-lg bg-background text-sm"
+              >
+                <option value="">默认模板</option>
+                {templates.map(t => (
+                  <option key={t.name} value={t.name}>{t.name}</option>
+                ))}
+              </select>
+              {templates.length === 0 && (
+                <p className="mt-2 text-xs text-muted-foreground">
+                  将 Handlebars 模板（如 report.md、weekly.html.hbs）放入数据目录的 report_templates 文件夹即可在此选择
+                </p>
+              )}
+            </div>
+          )}
+
+          {/* 渲染结果预览 */}
+          {isDesktop && preview && (
+            <div>
+              <div className="flex items-center justify-between mb-2">
+                <label className="text-sm font-medium text-foreground">渲染预览</label>
+                <button
+                  onClick={() => setPreview(null)}
+                  className="p-1 hover:bg-accent rounded-lg transition-colors"
+                >
+                  <X className="w-4 h-4" />
+                </button>
+              </div>
+              {preview.format === 'html' ? (
+                <iframe
+                  title="报告预览"
+                  sandbox=""
+                  srcDoc={preview.content}
+                  className="w-full h-80 border border-border rounded-lg bg-white"
                 />
-                <X className="w-4 h-4 text-red-500" />
-                逾期任务
-              </label>
+              ) : (
+                <pre className="max-h-80 overflow-auto p-3 border border-border rounded-lg bg-accent/30 text-xs whitespace-pre-wrap">
+                  {preview.content}
+                </pre>
+              )}
             </div>
-          </div>
+          )}
+
+          {/* 内容选项 */}
+          {!isDesktop && (
+            <div>
+              <label className="block text-sm font-medium text-foreground mb-3">包含内容</label>
+              <div className="space-y-2">
+                <label className="flex items-center gap-2 text-sm">
+                  <input
+                    type="checkbox"
+                    checked={options.includeCompleted}
+                    onChange={(e) => setOptions(prev => ({ ...prev, includeCompleted: e.target.checked }))}
+                    className="rounded border-border"
+                  />
+                  <CheckCircle className="w-4 h-4 text-green-500" />
+                  已完成任务
+                </label>
+                <label className="flex items-center gap-2 text-sm">
+                  <input
+                    type="checkbox"
+                    checked={options.includePending}
+                    onChange={(e) => setOptions(prev => ({ ...prev, includePending: e.target.checked }))}
+                    className="rounded border-border"
+                  />
+                  <Clock className="w-4 h-4 text-blue-500" />
+                  待完成任务
+                </label>
+                <label className="flex items-center gap-2 text-sm">
+                  <input
+                    type="checkbox"
+                    checked={options.includeOverdue}
+                    onChange={(e) => setOptions(prev => ({ ...prev, includeOverdue: e.target.checked }))}
+                    className="rounded border-border"
+                  />
+                  <X className="w-4 h-4 text-red-500" />
+                  逾期任务
+                </label>
+              </div>
+            </div>
+          )}
 
           {/* 预览统计 */}
           <div className="bg-accent/30 rounded-lg p-4">
@@ -270,6 +429,16 @@ export default function ReportExportDialog({ isOpen, onClose }: ReportExportDial
           >
             取消
           </button>
+          {isDesktop && (
+            <button
+              onClick={handlePreview}
+              disabled={previewing}
+              className="flex items-center gap-2 px-4 py-2 border border-border rounded-lg hover:bg-accent disabled:opacity-50 disabled:cursor-not-allowed transition-colors"
+            >
+              <Eye className="w-4 h-4" />
+              {previewing ? '渲染中...' : '预览'}
+            </button>
+          )}
           <button
             onClick={handleExport}
             disabled={exporting}
@@ -0,0 +1,275 @@
+import React, { useEffect, useState } from 'react';
+import { X, FolderSync, FolderOpen, RefreshCw, AlertTriangle } from 'lucide-react';
+import { toast } from 'sonner';
+import { syncApi } from '../api/tauri';
+import type { SyncConflict, SyncStatus } from '../types';
+
+interface SyncDialogProps {
+  isOpen: boolean;
+  onClose: () => void;
+}
+
+// 冲突中显示的字段名，_deleted 表示一台设备删除了待办而另一台仍在修改
+const FIELD_LABELS: Record<string, string> = {
+  _deleted: '删除',
+  title: '标题',
+  description: '描述',
+  completed: '完成状态',
+  priority: '优先级',
+  dueDate: '截止时间',
+  completedAt: '完成时间',
+  createdAt: '创建时间',
+  attachments: '附件',
+  estimatedMinutes: '预估时长',
+  focusMinutes: '专注时长'
+};
+
+const PRIORITY_LABELS: Record<string, string> = {
+  high: '高',
+  medium: '中',
+  low: '低'
+};
+
+// 冲突中的值是 JSON 文本，转换为便于阅读的形式
+function formatValue(field: string, json: string): string {
+  let value: unknown;
+  try {
+    value = JSON.parse(json);
+  } catch {
+    return json;
+  }
+  if (value === null || value === '') return '（空）';
+  if (field === '_deleted') return value ? '已删除' : '保留';
+  if (field === 'completed') return value ? '已完成' : '未完成';
+  if (field === 'priority' && typeof value === 'string') return PRIORITY_LABELS[value] ?? value;
+  if (field === 'attachments' && Array.isArray(value)) return `${value.length} 个附件`;
+  if (typeof value === 'string') return value;
+  return JSON.stringify(value);
+}
+
+function formatTime(value?: string): string {
+  return value ? new Date(value).toLocaleString() : '-';
+}
+
+export default function SyncDialog({ isOpen, onClose }: SyncDialogProps) {
+  const [status, setStatus] = useState<SyncStatus | null>(null);
+  const [conflicts, setConflicts] = useState<SyncConflict[]>([]);
+  const [deviceName, setDeviceName] = useState('');
+  const [busy, setBusy] = useState(false);
+
+  const loadConflicts = async () => {
+    const response = await syncApi.getConflicts();
+    if (response.success) {
+      setConflicts(response.data ?? []);
+    }
+  };
+
+  const applyStatus = (next: SyncStatus) => {
+    setStatus(next);
+    setDeviceName(next.deviceName);
+  };
+
+  useEffect(() => {
+    if (!isOpen) return;
+    syncApi.getStatus().then(response => {
+      if (!response.success || !response.data) {
+        toast.error(response.error || '读取同步状态失败');
+        return;
+      }
+      applyStatus(response.data);
+    });
+    loadConflicts();
+  }, [isOpen]);
+
+  if (!isOpen) return null;
+
+  // 执行会修改同步状态的操作，完成后刷新冲突列表
+  const run = async (action: () => Promise<{ success: boolean; data?: SyncStatus | null; error?: string }>, message?: string) => {
+    setBusy(true);
+    try {
+      const response = await action();
+      if (!response.success || !response.data) {
+        toast.error(response.error || '同步失败');
+        return;
+      }
+      applyStatus(response.data);
+      await loadConflicts();
+      if (message) toast.success(message);
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  const handleChooseFolder = async () => {
+    const response = await syncApi.chooseFolder();
+    if (!response.success) {
+      toast.error(response.error || '选择文件夹失败');
+      return;
+    }
+    const folder = response.data;
+    if (!folder) return;
+    await run(() => syncApi.configure({ folder, deviceName }), '已开启文件夹同步');
+  };
+
+  const handleDisable = () => run(() => syncApi.configure({ deviceName }), '已关闭文件夹同步');
+
+  const handleRename = () => {
+    if (!status || !deviceName.trim() || deviceName.trim() === status.deviceName) return;
+    run(() => syncApi.configure({ folder: status.folder, deviceName }));
+  };
+
+  const handleSyncNow = () => run(() => syncApi.syncNow(), '同步完成');
+
+  const handleResolve = async (conflict: SyncConflict, useOther: boolean) => {
+    const response = await syncApi.resolveConflict(conflict.id, useOther);
+    if (!response.success) {
+      toast.error(response.error || '处理冲突失败');
+      return;
+    }
+    setConflicts(conflicts.filter(c => c.id !== conflict.id));
+  };
+
+  const enabled = !!status?.folder;
+
+  return (
+    <div className="fixed inset-0 bg-black/50 flex items-center justify-center z-50 p-4">
+      <div className="bg-background border border-border rounded-lg shadow-xl w-full max-w-2xl max-h-[90vh] overflow-y-auto">
+        <div className="flex items-center justify-between p-6 border-b border-border">
+          <div className="flex items-center gap-2">
+            <FolderSync className="w-5 h-5 text-primary" />
+            <h2 className="text-xl font-semibold text-foreground">文件夹同步</h2>
+          </div>
+          <button
+            onClick={onClose}
+            className="p-1 hover:bg-accent rounded-lg transition-colors"
+          >
+            <X className="w-5 h-5" />
+          </button>
+        </div>
+
+        <div className="p-6 space-y-6">
+          <p className="text-sm text-muted-foreground">
+            选择一个由网盘（如 OneDrive、Dropbox、坚果云）同步的文件夹，每台设备把自己的修改写入其中，并自动合并其他设备的修改。
+            同一字段在两台设备上同时被修改时保留较晚的修改，另一个值列在下方的冲突中。
+          </p>
+
+          {/* 同步文件夹 */}
+          <div>
+            <label className="block text-sm font-medium text-foreground mb-3">同步文件夹</label>
+            <div className="flex items-center gap-3">
+              <div className="flex-1 px-3 py-2 bg-accent/30 rounded-lg text-sm truncate" title={status?.folder}>
+                {status?.folder ?? '未开启'}
+              </div>
+              <button
+                onClick={handleChooseFolder}
+                disabled={busy}
+                className="flex items-center gap-2 px-4 py-2 border border-border rounded-lg hover:bg-accent disabled:opacity-50 transition-colors text-sm"
+              >
+                <FolderOpen className="w-4 h-4" />
+                {enabled ? '更换' : '选择'}
+              </button>
+              {enabled && (
+                <button
+                  onClick={handleDisable}
+                  disabled={busy}
+                  className="px-4 py-2 border border-border rounded-lg hover:bg-accent disabled:opacity-50 transition-colors text-sm"
+                >
+                  关闭
+                </button>
+              )}
+            </div>
+          </div>
+
+          {/* 本机名称，显示在其他设备的冲突中 */}
+          <div>
+            <label className="block text-sm font-medium text-foreground mb-3">本机名称</label>
+            <input
+              type="text"
+              value={deviceName}
+              onChange={(e) => setDeviceName(e.target.value)}
+              onBlur={handleRename}
+              className="w-full px-3 py-2 border border-border rounded-lg bg-background text-sm focus:outline-none focus:ring-2 focus:ring-primary/50"
+            />
+          </div>
+
+          {enabled && status && (
+            <div>
+              <div className="flex items-center justify-between mb-3">
+                <label className="text-sm font-medium text-foreground">
+                  其他设备
+                  <span className="ml-2 text-xs text-muted-foreground font-normal">上次同步：{formatTime(status.lastSyncAt)}</span>
+                </label>
+                <button
+                  onClick={handleSyncNow}
+                  disabled={busy}
+                  className="flex items-center gap-2 px-3 py-1.5 bg-primary text-primary-foreground rounded-lg hover:bg-primary/90 disabled:opacity-50 disabled:cursor-not-allowed transition-colors text-sm"
+                >
+                  <RefreshCw className={`w-4 h-4 ${busy ? 'animate-spin' : ''}`} />
+                  立即同步
+                </button>
+              </div>
+              {status.devices.length === 0 ? (
+                <p className="text-sm text-muted-foreground">还没有其他设备加入此文件夹</p>
+              ) : (
+                <div className="space-y-2">
+                  {status.devices.map(device => (
+                    <div key={device.id} className="flex items-center justify-between px-3 py-2 bg-accent/30 rounded-lg text-sm">
+                      <span className="font-medium">{device.name}</span>
+                      <span className="text-muted-foreground">
+                        {device.changes} 条修改 · 最近 {formatTime(device.lastChangeAt)}
+                      </span>
+                    </div>
+                  ))}
+                </div>
+              )}
+            </div>
+          )}
+
+          {/* 冲突 */}
+          {conflicts.length > 0 && (
+            <div>
+              <label className="flex items-center gap-2 text-sm font-medium text-foreground mb-3">
+                <AlertTriangle className="w-4 h-4 text-amber-500" />
+                待处理的冲突（{conflicts.length}）
+              </label>
+              <div className="space-y-3">
+                {conflicts.map(conflict => (
+                  <div key={conflict.id} className="p-3 border border-border rounded-lg text-sm space-y-2">
+                    <div className="font-medium">
+                      {conflict.taskTitle || conflict.taskId}
+                      <span className="ml-2 text-xs text-muted-foreground font-normal">{FIELD_LABELS[conflict.field] ?? conflict.field}</span>
+                    </div>
+                    <div className="grid grid-cols-2 gap-3">
+                      <div className="p-2 bg-primary/5 rounded">
+                        <div className="text-xs text-muted-foreground mb-1">当前：{conflict.keptDevice} · {formatTime(conflict.keptAt)}</div>
+                        <div className="break-words">{formatValue(conflict.field, conflict.keptValue)}</div>
+                      </div>
+                      <div className="p-2 bg-accent/30 rounded">
+                        <div className="text-xs text-muted-foreground mb-1">被覆盖：{conflict.otherDevice} · {formatTime(conflict.otherAt)}</div>
+                        <div className="break-words">{formatValue(conflict.field, conflict.otherValue)}</div>
+                      </div>
+                    </div>
+                    <div className="flex justify-end gap-2">
+                      <button
+                        onClick={() => handleResolve(conflict, false)}
+                        className="px-3 py-1 border border-border rounded-lg hover:bg-accent transition-colors text-xs"
+                      >
+                        保留当前
+                      </button>
+                      <button
+                        onClick={() => handleResolve(conflict, true)}
+                        className="px-3 py-1 bg-primary text-primary-foreground rounded-lg hover:bg-primary/90 transition-colors text-xs"
+                      >
+                        改用此值
+                      </button>
+                    </div>
+                  </div>
+                ))}
+              </div>
+            </div>
+          )}
+        </div>
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,257 @@
+import React, { useState } from 'react';
+import { X, FileSpreadsheet, Upload, Download, AlertTriangle } from 'lucide-react';
+import { toast } from 'sonner';
+import { csvApi } from '../api/tauri';
+import { useTaskStore } from '../store/taskStore';
+import type { CsvInspection, TaskField, TaskImportResult } from '../types';
+
+interface TaskCsvDialogProps {
+  isOpen: boolean;
+  onClose: () => void;
+}
+
+const FIELD_LABELS: Record<TaskField, string> = {
+  title: '标题',
+  description: '描述',
+  completed: '已完成',
+  priority: '优先级',
+  dueDate: '截止时间',
+  createdAt: '创建时间',
+  completedAt: '完成时间',
+  estimatedMinutes: '预计耗时（分钟）',
+  focusMinutes: '专注时长（分钟）'
+};
+
+const ALL_FIELDS = Object.keys(FIELD_LABELS) as TaskField[];
+
+export default function TaskCsvDialog({ isOpen, onClose }: TaskCsvDialogProps) {
+  const { loadTasks } = useTaskStore();
+  const [columns, setColumns] = useState<TaskField[]>(ALL_FIELDS);
+  const [exporting, setExporting] = useState(false);
+
+  // 导入分两步：读取表头后选择每列对应的字段，再预览或导入
+  const [content, setContent] = useState('');
+  const [inspection, setInspection] = useState<CsvInspection | null>(null);
+  const [mapping, setMapping] = useState<(TaskField | null)[]>([]);
+  const [result, setResult] = useState<TaskImportResult | null>(null);
+  const [importing, setImporting] = useState(false);
+
+  if (!isOpen) return null;
+
+  const reset = () => {
+    setContent('');
+    setInspection(null);
+    setMapping([]);
+    setResult(null);
+  };
+
+  const handleClose = () => {
+    reset();
+    onClose();
+  };
+
+  const toggleColumn = (field: TaskField) => {
+    setColumns(prev => prev.includes(field)
+      ? prev.filter(f => f !== field)
+      : ALL_FIELDS.filter(f => f === field || prev.includes(f)));
+  };
+
+  const handleExport = async () => {
+    setExporting(true);
+    try {
+      const response = await csvApi.exportTasks({ columns });
+      if (!response.success) {
+        toast.error(response.error || '导出失败，请重试');
+        return;
+      }
+      if (response.data) {
+        toast.success(`已保存到 ${response.data}`);
+      }
+    } finally {
+      setExporting(false);
+    }
+  };
+
+  const handleFile = async (file: File) => {
+    const text = await file.text();
+    const response = await csvApi.inspect(text);
+    if (!response.success || !response.data) {
+      toast.error(response.error || '无法读取该文件');
+      return;
+    }
+    setContent(text);
+    setInspection(response.data);
+    setMapping(response.data.suggestedMapping);
+    setResult(null);
+  };
+
+  // 同一字段只能对应一列，选中时清除其他列上的该字段
+  const changeMapping = (index: number, field: TaskField | null) => {
+    setMapping(prev => prev.map((current, i) => {
+      if (i === index) return field;
+      return field && current === field ? null : current;
+    }));
+    setResult(null);
+  };
+
+  const runImport = async (dryRun: boolean) => {
+    setImporting(true);
+    try {
+      const response = await csvApi.importTasks({ content, mapping, dryRun });
+      if (!response.success || !response.data) {
+        toast.error(response.error || '导入失败，请重试');
+        return;
+      }
+      if (dryRun) {
+        setResult(response.data);
+        return;
+      }
+      const { tasks, errors } = response.data;
+      await loadTasks();
+      toast.success(errors.length > 0
+        ? `已导入 ${tasks.length} 个待办，跳过 ${errors.length} 行`
+        : `已导入 ${tasks.length} 个待办`);
+      handleClose();
+    } finally {
+      setImporting(false);
+    }
+  };
+
+  return (
+    <div className="fixed inset-0 bg-black/50 flex items-center justify-center z-50 p-4">
+      <div className="bg-background border border-border rounded-lg shadow-xl w-full max-w-2xl max-h-[90vh] overflow-y-auto">
+        <div className="flex items-center justify-between p-6 border-b border-border">
+          <div className="flex items-center gap-2">
+            <FileSpreadsheet className="w-5 h-5 text-primary" />
+            <h2 className="text-xl font-semibold text-foreground">CSV 导入导出</h2>
+          </div>
+          <button
+            onClick={handleClose}
+            className="p-1 hover:bg-accent rounded-lg transition-colors"
+          >
+            <X className="w-5 h-5" />
+          </button>
+        </div>
+
+        <div className="p-6 space-y-6">
+          {/* 导出 */}
+          <div>
+            <label className="block text-sm font-medium text-foreground mb-3">导出的列</label>
+            <div className="grid grid-cols-3 gap-2">
+              {ALL_FIELDS.map(field => (
+                <label key={field} className="flex items-center gap-2 text-sm">
+                  <input
+                    type="checkbox"
+                    checked={columns.includes(field)}
+                    onChange={() => toggleColumn(field)}
+                    className="rounded border-border"
+                  />
+                  {FIELD_LABELS[field]}
+                </label>
+              ))}
+            </div>
+            <button
+              onClick={handleExport}
+              disabled={exporting || columns.length === 0}
+              className="mt-3 flex items-center gap-2 px-4 py-2 bg-primary text-primary-foreground rounded-lg hover:bg-primary/90 disabled:opacity-50 disabled:cursor-not-allowed transition-colors text-sm"
+            >
+              <Download className="w-4 h-4" />
+              {exporting ? '导出中...' : '导出 CSV'}
+            </button>
+          </div>
+
+          {/* 导入 */}
+          <div className="border-t border-border pt-6">
+            <label className="block text-sm font-medium text-foreground mb-3">导入</label>
+            <label className="flex items-center justify-center gap-2 p-3 border border-dashed border-border rounded-lg text-sm text-muted-foreground hover:border-primary/50 cursor-pointer transition-colors">
+              <Upload className="w-4 h-4" />
+              选择 CSV 文件（UTF-8，逗号、分号或制表符分隔）
+              <input
+                type="file"
+                accept=".csv,.tsv,.txt"
+                className="hidden"
+                onChange={(e) => {
+                  const file = e.target.files?.[0];
+                  e.target.value = '';
+                  if (file) handleFile(file);
+                }}
+              />
+            </label>
+
+            {inspection && (
+              <div className="mt-4 space-y-3">
+                <p className="text-sm text-muted-foreground">
+                  共 {inspection.totalRows} 行，请确认每列对应的字段（标题为必选）
+                </p>
+                <table className="w-full text-sm">
+                  <thead>
+                    <tr className="text-left text-muted-foreground">
+                      <th className="py-1 pr-3 font-medium">列</th>
+                      <th className="py-1 pr-3 font-medium">示例</th>
+                      <th className="py-1 font-medium">对应字段</th>
+                    </tr>
+                  </thead>
+                  <tbody>
+                    {inspection.headers.map((header, index) => (
+                      <tr key={index} className="border-t border-border">
+                        <td className="py-1 pr-3">{header || `第 ${index + 1} 列`}</td>
+                        <td className="py-1 pr-3 text-muted-foreground truncate max-w-[12rem]">
+                          {inspection.sampleRows.map(row => row[index]).find(v => v && v.trim()) ?? ''}
+                        </td>
+                        <td className="py-1">
+                          <select
+                            value={mapping[index] ?? ''}
+                            onChange={(e) => changeMapping(index, (e.target.value || null) as TaskField | null)}
+                            className="w-full p-1 border border-border rounded bg-background"
+                          >
+                            <option value="">忽略</option>
+                            {ALL_FIELDS.map(field => (
+                              <option key={field} value={field}>{FIELD_LABELS[field]}</option>
+                            ))}
+                          </select>
+                        </td>
+                      </tr>
+                    ))}
+                  </tbody>
+                </table>
+
+                {result && (
+                  <div className="bg-accent/30 rounded-lg p-3 text-sm space-y-1">
+                    <div>可导入 {result.tasks.length} 个待办</div>
+                    {result.errors.length > 0 && (
+                      <div className="max-h-32 overflow-y-auto text-red-600">
+                        {result.errors.map(error => (
+                          <div key={error.row} className="flex items-start gap-1">
+                            <AlertTriangle className="w-3.5 h-3.5 mt-0.5 flex-shrink-0" />
+                            第 {error.row} 行：{error.message}
+                          </div>
+                        ))}
+                      </div>
+                    )}
+                  </div>
+                )}
+
+                <div className="flex gap-3">
+                  <button
+                    onClick={() => runImport(true)}
+                    disabled={importing}
+                    className="px-4 py-2 border border-border rounded-lg hover:bg-accent disabled:opacity-50 transition-colors text-sm"
+                  >
+                    预览
+                  </button>
+                  <button
+                    onClick={() => runImport(false)}
+                    disabled={importing || !mapping.includes('title')}
+                    className="px-4 py-2 bg-primary text-primary-foreground rounded-lg hover:bg-primary/90 disabled:opacity-50 disabled:cursor-not-allowed transition-colors text-sm"
+                  >
+                    {importing ? '导入中...' : '导入'}
+                  </button>
+                </div>
+              </div>
+            )}
+          </div>
+        </div>
+      </div>
+    </div>
+  );
+}
@@ -14,19 +14,35 @@ import {
   Circle,
   AlertTriangle,
   Type,
-  FileText
+  FileText,
+  Timer,
+  Play,
+  Square
 } from 'lucide-react';
+import { toast } from 'sonner';
 import type { Task, Priority, UpdateTaskRequest } from '../types';
 import { useTaskStore } from '../store/taskStore';
+import { useUsageStore } from '../store/usageStore';
+import { useTimeEntryStore } from '../store/timeEntryStore';
 import DeleteConfirmDialog from './DeleteConfirmDialog';
 
 interface TaskItemProps {
   task: Task;
 }
 
+// 已计时秒数显示为 H:MM:SS
+function formatElapsed(seconds: number): string {
+  const hours = Math.floor(seconds / 3600);
+  const mins = Math.floor((seconds % 3600) / 60);
+  const secs = seconds % 60;
+  return `${hours}:${mins.toString().padStart(2, '0')}:${secs.toString().padStart(2, '0')}`;
+}
+
 export default function TaskItem({ task }: TaskItemProps) {
   const navigate = useNavigate();
   const { toggleTaskComplete, deleteTask, updateTask, editingTaskId, setEditingTaskId } = useTaskStore();
+  // 只有正在计时的待办订阅每秒变化的已用时间
+  const timerElapsed = useTimeEntryStore(state => (state.running?.taskId === task.id ? state.elapsed : null));
   const [isHovered, setIsHovered] = useState(false);
   const isEditing = editingTaskId === task.id;
   const [editTitle, setEditTitle] = useState(task.title);
@@ -34,6 +50,7 @@ export default function TaskItem({ task }: TaskItemProps) {
   const [editPriority, setEditPriority] = useState<Priority>(task.priority);
   const [editDueDate, setEditDueDate] = useState(task.dueDate || '');
   const [tempDueDate, setTempDueDate] = useState(task.dueDate || '');
+  const [editEstimate, setEditEstimate] = useState(task.estimatedMinutes?.toString() || '');
   const [showDatePicker, setShowDatePicker] = useState(false);
   const [isSaving, setIsSaving] = useState(false);
   const [showDeleteDialog, setShowDeleteDialog] = useState(false);
@@ -47,8 +64,9 @@ export default function TaskItem({ task }: TaskItemProps) {
       setEditPriority(task.priority);
       setEditDueDate(task.dueDate || '');
       setTempDueDate(task.dueDate || '');
+      setEditEstimate(task.estimatedMinutes?.toString() || '');
     }
-  }, [isEditing, task.title, task.description, task.priority, task.dueDate]);
+  }, [isEditing, task.title, task.description, task.priority, task.dueDate, task.estimatedMinutes]);
 
   const handleToggleComplete = (e: React.MouseEvent) => {
     e.stopPropagation();
@@ -64,6 +82,7 @@ export default function TaskItem({ task }: TaskItemProps) {
     setEditPriority(task.priority);
     setEditDueDate(task.dueDate || '');
     setTempDueDate(task.dueDate || '');
+    setEditEstimate(task.estimatedMinutes?.toString() || '');
     setShowDatePicker(false);
   };
 
@@ -77,6 +96,8 @@ export default function TaskItem({ task }: TaskItemProps) {
       description: editDescription.trim() || undefined,
       priority: editPriority,
       dueDate: editDueDate || undefined,
+      // 0 表示清除预计耗时
+      estimatedMinutes: Math.max(0, parseInt(editEstimate, 10) || 0),
     };
 
     try {
@@ -89,6 +110,30 @@ export default function TaskItem({ task }: TaskItemProps) {
     }
   };
 
+  // 以此待办开始番茄钟，完成的专注时长计入该待办
+  const handleStartFocus = async (e: React.MouseEvent) => {
+    e.stopPropagation();
+    await useUsageStore.getState().startPomodoro(task.id);
+    toast.success(`开始专注：${task.title}`);
+  };
+
+  // 开始或停止手动计时，同一时间只有一个待办在计时
+  const handleToggleTimer = async (e: React.MouseEvent) => {
+    e.stopPropagation();
+    const { startTimer, stopTimer } = useTimeEntryStore.getState();
+    try {
+      if (timerElapsed !== null) {
+        await stopTimer();
+        toast.success('已停止计时');
+      } else {
+        await startTimer(task.id);
+        toast.success(`开始计时：${task.title}`);
+      }
+    } catch (error) {
+      toast.error(error instanceof Error ? error.message : '计时操作失败');
+    }
+  };
+
   const handleCancel = (e: React.MouseEvent) => {
     e.stopPropagation();
     setEditingTaskId(null);
@@ -97,6 +142,7 @@ export default function TaskItem({ task }: TaskItemProps) {
     setEditPriority(task.priority);
     setEditDueDate(task.dueDate || '');
     setTempDueDate(task.dueDate || '');
+    setEditEstimate(task.estimatedMinutes?.toString() || '');
     setShowDatePicker(false);
   };
 
@@ -369,6 +415,20 @@ export default function TaskItem({ task }: TaskItemProps) {
                       </>
                     )}
                   </div>
+
+                  {/* 预计耗时 */}
+                  <div className="flex items-center space-x-1 text-xs text-muted-foreground">
+                    <Timer className="w-3 h-3" />
+                    <input
+                      type="number"
+                      min="0"
+                      value={editEstimate}
+                      onChange={(e) => setEditEstimate(e.target.value)}
+                      onClick={(e) => e.stopPropagation()}
+                      placeholder="预计分钟"
+                      className="w-20 px-2 py-1 bg-background border border-border rounded focus:outline-none focus:ring-1 focus:ring-primary text-foreground"
+                    />
+                  </div>
                 </div>
               </div>
 
@@ -448,6 +508,25 @@ export default function TaskItem({ task }: TaskItemProps) {
                     </div>
                   )}
 
+                  {/* 专注时长与预计耗时 */}
+                  {(task.focusMinutes > 0 || task.estimatedMinutes) && (
+                    <div className="flex items-center space-x-1" title="已专注 / 预计（分钟）">
+                      <Timer className="w-3 h-3" />
+                      <span>
+                        专注 {task.focusMinutes}
+                        {task.estimatedMinutes ? ` / ${task.estimatedMinutes}` : ''} 分钟
+                      </span>
+                    </div>
+                  )}
+
+                  {/* 正在进行的计时 */}
+                  {timerElapsed !== null && (
+                    <div className="flex items-center space-x-1 text-green-600 dark:text-green-400" title="计时中">
+                      <Play className="w-3 h-3" />
+                      <span>计时 {formatElapsed(timerElapsed)}</span>
+                    </div>
+                  )}
+
                   {/* 创建时间 */}
                   <div className="flex items-center space-x-1">
                     <Clock className="w-4 h-4" />
@@ -460,6 +539,26 @@ export default function TaskItem({ task }: TaskItemProps) {
                   flex items-center space-x-1 transition-opacity duration-200
                   ${isHovered ? 'opacity-100' : 'opacity-0'}
                 `}>
+                  {!task.completed && (
+                    <button
+                      onClick={handleStartFocus}
+                      className="p-2 rounded text-muted-foreground hover:text-orange-600 hover:bg-orange-50 transition-colors"
+                      title="开始专注"
+                    >
+                      <Timer className="w-4 h-4" />
+                    </button>
+                  )}
+
+                  {(!task.completed || timerElapsed !== null) && (
+                    <button
+                      onClick={handleToggleTimer}
+                      className="p-2 rounded text-muted-foreground hover:text-green-600 hover:bg-green-50 transition-colors"
+                      title={timerElapsed !== null ? '停止计时' : '开始计时'}
+                    >
+                      {timerElapsed !== null ? <Square className="w-4 h-4" /> : <Play className="w-4 h-4" />}
+                    </button>
+                  )}
+
                   <button
                     onClick={handleEdit}
                     className="p-2 rounded text-muted-foreground hover:text-primary hover:bg-primary/10 transition-colors"
@@ -0,0 +1,164 @@
+import React, { useEffect, useState } from 'react';
+import { X, ListChecks, Copy, Download } from 'lucide-react';
+import { toast } from 'sonner';
+import { taskListApi } from '../api/tauri';
+import type { TaskListFormat, TaskListGroup, TaskListRequest } from '../types';
+
+interface TaskListExportDialogProps {
+  isOpen: boolean;
+  onClose: () => void;
+}
+
+const FORMATS: { value: TaskListFormat; label: string }[] = [
+  { value: 'markdown', label: 'Markdown' },
+  { value: 'todoTxt', label: 'todo.txt' }
+];
+
+const GROUPS: { value: TaskListGroup; label: string }[] = [
+  { value: 'none', label: '不分组' },
+  { value: 'priority', label: '按优先级' },
+  { value: 'dueDate', label: '按截止日期' }
+];
+
+export default function TaskListExportDialog({ isOpen, onClose }: TaskListExportDialogProps) {
+  const [format, setFormat] = useState<TaskListFormat>('markdown');
+  const [groupBy, setGroupBy] = useState<TaskListGroup>('priority');
+  const [includeCompleted, setIncludeCompleted] = useState(true);
+  const [content, setContent] = useState('');
+  const [exporting, setExporting] = useState(false);
+
+  const request: TaskListRequest = { format, groupBy, includeCompleted };
+
+  // 选项变化时重新生成预览
+  useEffect(() => {
+    if (!isOpen) return;
+    taskListApi.render({ format, groupBy, includeCompleted }).then(response => {
+      if (!response.success) {
+        toast.error(response.error || '生成待办清单失败');
+        return;
+      }
+      setContent(response.data ?? '');
+    });
+  }, [isOpen, format, groupBy, includeCompleted]);
+
+  if (!isOpen) return null;
+
+  const handleCopy = async () => {
+    await navigator.clipboard.writeText(content);
+    toast.success('已复制到剪贴板');
+  };
+
+  const handleExport = async () => {
+    setExporting(true);
+    try {
+      const response = await taskListApi.exportList(request);
+      if (!response.success) {
+        toast.error(response.error || '导出失败，请重试');
+        return;
+      }
+      if (response.data) {
+        toast.success(`已保存到 ${response.data}`);
+        onClose();
+      }
+    } finally {
+      setExporting(false);
+    }
+  };
+
+  return (
+    <div className="fixed inset-0 bg-black/50 flex items-center justify-center z-50 p-4">
+      <div className="bg-background border border-border rounded-lg shadow-xl w-full max-w-2xl max-h-[90vh] overflow-y-auto">
+        <div className="flex items-center justify-between p-6 border-b border-border">
+          <div className="flex items-center gap-2">
+            <ListChecks className="w-5 h-5 text-primary" />
+            <h2 className="text-xl font-semibold text-foreground">导出待办清单</h2>
+          </div>
+          <button
+            onClick={onClose}
+            className="p-1 hover:bg-accent rounded-lg transition-colors"
+          >
+            <X className="w-5 h-5" />
+          </button>
+        </div>
+
+        <div className="p-6 space-y-6">
+          {/* 格式 */}
+          <div>
+            <label className="block text-sm font-medium text-foreground mb-3">格式</label>
+            <div className="grid grid-cols-2 gap-3">
+              {FORMATS.map(f => (
+                <button
+                  key={f.value}
+                  onClick={() => setFormat(f.value)}
+                  className={`p-3 rounded-lg border text-sm transition-colors ${
+                    format === f.value
+                      ? 'border-primary bg-primary/10 text-primary'
+                      : 'border-border hover:border-primary/50'
+                  }`}
+                >
+                  {f.label}
+                </button>
+              ))}
+            </div>
+          </div>
+
+          {/* 分组，todo.txt 只影响顺序 */}
+          <div>
+            <label className="block text-sm font-medium text-foreground mb-3">
+              分组{format === 'todoTxt' && <span className="ml-2 text-xs text-muted-foreground font-normal">todo.txt 没有分组标题，仅按分组排序</span>}
+            </label>
+            <div className="grid grid-cols-3 gap-3">
+              {GROUPS.map(g => (
+                <button
+                  key={g.value}
+                  onClick={() => setGroupBy(g.value)}
+                  className={`p-3 rounded-lg border text-sm transition-colors ${
+                    groupBy === g.value
+                      ? 'border-primary bg-primary/10 text-primary'
+                      : 'border-border hover:border-primary/50'
+                  }`}
+                >
+                  {g.label}
+                </button>
+              ))}
+            </div>
+          </div>
+
+          <label className="flex items-center gap-2 text-sm">
+            <input
+              type="checkbox"
+              checked={includeCompleted}
+              onChange={(e) => setIncludeCompleted(e.target.checked)}
+              className="rounded border-border"
+            />
+            包含已完成的待办
+          </label>
+
+          {/* 预览 */}
+          <pre className="max-h-72 overflow-auto bg-accent/30 rounded-lg p-3 text-xs whitespace-pre-wrap font-mono">
+            {content}
+          </pre>
+
+          <div className="flex justify-end gap-3">
+            <button
+              onClick={handleCopy}
+              disabled={!content}
+              className="flex items-center gap-2 px-4 py-2 border border-border rounded-lg hover:bg-accent disabled:opacity-50 transition-colors text-sm"
+            >
+              <Copy className="w-4 h-4" />
+              复制
+            </button>
+            <button
+              onClick={handleExport}
+              disabled={exporting}
+              className="flex items-center gap-2 px-4 py-2 bg-primary text-primary-foreground rounded-lg hover:bg-primary/90 disabled:opacity-50 disabled:cursor-not-allowed transition-colors text-sm"
+            >
+              <Download className="w-4 h-4" />
+              {exporting ? '导出中...' : '保存为文件'}
+            </button>
+          </div>
+        </div>
+      </div>
+    </div>
+  );
+}
@@ -10,22 +10,22 @@ export default function UsageButton() {
     isTrackingEnabled,
     formatMinutes,
     loadUsageData,
-    calculateStats,
+    refreshStats,
     pomodoro,
     formatTime
   } = useUsageStore();
 
   useEffect(() => {
     loadUsageData();
-    calculateStats();
+    refreshStats();
     
     // 每分钟更新一次
     const interval = setInterval(() => {
-      calculateStats();
+      refreshStats();
     }, 60000);
 
     return () => clearInterval(interval);
-  }, [loadUsageData, calculateStats]);
+  }, [loadUsageData, refreshStats]);
 
   // 番茄钟状态变化时强制更新
   useEffect(() => {
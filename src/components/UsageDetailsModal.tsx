@@ -1,6 +1,7 @@
 import React, { useState, useEffect } from 'react';
 import { X, Calendar, Clock, BarChart3, TrendingUp, Trophy } from 'lucide-react';
-import { useUsageStore } from '../store/usageStore';
+import { useUsageStore, toDateString } from '../store/usageStore';
+import type { UsageDetail } from '../types';
 
 interface UsageDetailsModalProps {
   isOpen: boolean;
@@ -8,17 +9,21 @@ interface UsageDetailsModalProps {
 }
 
 export default function UsageDetailsModal({ isOpen, onClose }: UsageDetailsModalProps) {
-  const { stats, getUsageDetails, formatMinutes, loadUsageData } = useUsageStore();
+  const { stats, getUsageDetails, formatMinutes, refreshStats } = useUsageStore();
   const [selectedPeriod, setSelectedPeriod] = useState<'week' | 'month'>('week');
-  const [usageDetails, setUsageDetails] = useState<ReturnType<typeof getUsageDetails>>([]);
+  const [usageDetails, setUsageDetails] = useState<UsageDetail[]>([]);
 
   useEffect(() => {
     if (isOpen) {
-      loadUsageData();
-      const details = getUsageDetails(selectedPeriod === 'week' ? 7 : 30);
-      setUsageDetails(details);
+      refreshStats();
+      // 最近7天或30天，最新的一天排在最前
+      const end = new Date();
+      const start = new Date(end.getFullYear(), end.getMonth(), end.getDate() - (selectedPeriod === 'week' ? 6 : 29));
+      getUsageDetails(toDateString(start), toDateString(end)).then(details => {
+        setUsageDetails([...details].reverse());
+      });
     }
-  }, [isOpen, selectedPeriod, getUsageDetails, loadUsageData]);
+  }, [isOpen, selectedPeriod, getUsageDetails, refreshStats]);
 
   if (!isOpen) return null;
 
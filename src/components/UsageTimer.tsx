@@ -1,6 +1,7 @@
 import React, { useEffect, useState } from 'react';
-import { Play, Pause, RotateCcw, Settings, BarChart3, Timer } from 'lucide-react';
+import { Play, Pause, RotateCcw, SkipForward, Settings, BarChart3, Timer, X } from 'lucide-react';
 import { useUsageStore } from '../store/usageStore';
+import { useTaskStore } from '../store/taskStore';
 import { toast } from 'sonner';
 
 interface UsageTimerProps {
@@ -14,12 +15,15 @@ export default function UsageTimer({ onShowDetails }: UsageTimerProps) {
     startPomodoro,
     pausePomodoro,
     resetPomodoro,
+    skipPomodoro,
+    setPomodoroTask,
     formatTime,
     formatMinutes,
     loadUsageData
   } = useUsageStore();
 
   const [showSettings, setShowSettings] = useState(false);
+  const focusTask = useTaskStore(state => state.tasks.find(t => t.id === pomodoro.taskId));
 
   useEffect(() => {
     loadUsageData();
@@ -79,8 +83,23 @@ export default function UsageTimer({ onShowDetails }: UsageTimerProps) {
             
             <div className="text-sm text-muted-foreground mb-4">
               第 {pomodoro.cycle + 1} 个番茄钟
+              {pomodoro.interruptions > 0 && ` · 中断 ${pomodoro.interruptions} 次`}
             </div>
 
+            {/* 当前专注的待办，在待办列表中点击“开始专注”设置 */}
+            {focusTask && (
+              <div className="flex items-center justify-center gap-1 text-sm text-foreground mb-4">
+                <span className="truncate max-w-[16rem]">专注：{focusTask.title}</span>
+                <button
+                  onClick={() => setPomodoroTask(null)}
+                  className="p-1 text-muted-foreground hover:text-foreground rounded"
+                  title="不再关联此待办"
+                >
+                  <X className="w-3 h-3" />
+                </button>
+              </div>
+            )}
+
             {/* 进度条 */}
             <div className="w-full bg-gray-200 rounded-full h-2 mb-4">
               <div 
@@ -99,7 +118,7 @@ export default function UsageTimer({ onShowDetails }: UsageTimerProps) {
           {/* 控制按钮 */}
           <div className="flex items-center justify-center gap-3">
             <button
-              onClick={pomodoro.isActive ? pausePomodoro : startPomodoro}
+              onClick={() => (pomodoro.isActive ? pausePomodoro() : startPomodoro())}
               className={`flex items-center gap-2 px-4 py-2 rounded-lg font-medium transition-colors ${
                 pomodoro.isActive 
                   ? 'bg-red-500 hover:bg-red-600 text-white' 
@@ -127,6 +146,14 @@ export default function UsageTimer({ onShowDetails }: UsageTimerProps) {
               <RotateCcw className="w-4 h-4" />
             </button>
 
+            <button
+              onClick={skipPomodoro}
+              className="p-2 text-muted-foreground hover:text-foreground hover:bg-accent rounded-lg transition-colors"
+              title="跳过当前阶段"
+            >
+              <SkipForward className="w-4 h-4" />
+            </button>
+
             <button
               onClick={() => setShowSettings(!showSettings)}
               className="p-2 text-muted-foreground hover:text-foreground hover:bg-accent rounded-lg transition-colors"
@@ -167,8 +194,8 @@ function PomodoroSettings({ onClose }: { onClose: () => void }) {
     cyclesBeforeLongBreak: pomodoro.cyclesBeforeLongBreak
   });
 
-  const handleSave = () => {
-    updatePomodoroSettings(settings);
+  const handleSave = async () => {
+    await updatePomodoroSettings(settings);
     toast.success('番茄钟设置已保存');
     onClose();
   };
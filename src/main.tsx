@@ -1,10 +1,21 @@
 import { StrictMode } from "react";
 import { createRoot } from "react-dom/client";
+import { getCurrentWindow } from "@tauri-apps/api/window";
 import App from "./App";
+import QuickAddWindow from "./pages/QuickAddWindow";
 import "./index.css";
 
+// 快速添加窗口与主窗口共用同一页面，按窗口标签区分（浏览器中没有 Tauri 窗口）
+function currentWindowLabel(): string | null {
+  try {
+    return getCurrentWindow().label;
+  } catch {
+    return null;
+  }
+}
+
 createRoot(document.getElementById("root")!).render(
   <StrictMode>
-    <App />
+    {currentWindowLabel() === "quick-add" ? <QuickAddWindow /> : <App />}
   </StrictMode>
 );
@@ -1,12 +1,18 @@
 import React, { useState, useEffect } from 'react';
 import { getVersion } from '@tauri-apps/api/app';
-import { Settings, Palette, Save, Sun, Moon, Sparkles, Heart, ArrowLeft, Pin, Trash2, Database, Eye, Circle, FileDown, QrCode } from 'lucide-react';
+import { Settings, Palette, Save, Sun, Moon, Sparkles, Heart, ArrowLeft, Pin, Trash2, Database, Eye, Circle, FileDown, QrCode, Languages, Power, FileSpreadsheet, ArrowDownToLine, ListChecks, FolderSync } from 'lucide-react';
 import { useSettingsStore } from '../store/settingsStore';
 import { useTaskStore } from '../store/taskStore';
 import { toast } from 'sonner';
 import ClearCacheDialog from '../components/ClearCacheDialog';
 import ReportExportDialog from '../components/ReportExportDialog';
-import type { Theme } from '../types';
+import TaskCsvDialog from '../components/TaskCsvDialog';
+import AppImportDialog from '../components/AppImportDialog';
+import TaskListExportDialog from '../components/TaskListExportDialog';
+import SyncDialog from '../components/SyncDialog';
+import LocalApiCard from '../components/LocalApiCard';
+import QuickAddShortcutCard from '../components/QuickAddShortcutCard';
+import type { Theme, Locale } from '../types';
 
 export default function Other() {
   const { settings, updateSettings, toggleIsPinned, setTransparency, applyTransparency, toggleCapsuleMode } = useSettingsStore();
@@ -14,6 +20,11 @@ export default function Other() {
   const [appVersion, setAppVersion] = useState('');
   const [showClearDialog, setShowClearDialog] = useState(false);
   const [showExportDialog, setShowExportDialog] = useState(false);
+  const [showCsvDialog, setShowCsvDialog] = useState(false);
+  const [showAppImportDialog, setShowAppImportDialog] = useState(false);
+  const [showTaskListDialog, setShowTaskListDialog] = useState(false);
+  const [showSyncDialog, setShowSyncDialog] = useState(false);
+  const isTauri = typeof window !== 'undefined' && !!(window as any).__TAURI__;
 
   useEffect(() => {
     const fetchVersion = async () => {
@@ -50,6 +61,14 @@ export default function Other() {
     updateSettings({ autoSave });
   };
 
+  const handleLocaleChange = (locale: Locale) => {
+    updateSettings({ locale });
+  };
+
+  const handleLaunchAtLoginChange = (launchAtLogin: boolean) => {
+    updateSettings({ launchAtLogin });
+  };
+
   const handleTransparencyToggle = async (enabled: boolean) => {
     // 只有深色模式才能开启透明
     if (enabled && settings.theme !== 'dark') {
@@ -283,6 +302,69 @@ export default function Other() {
             </div>
           </div>
 
+          {/* 语言设置（托盘菜单、系统提示等） */}
+          <div className="card card-shadow hover-lift slide-up">
+            <div className="card-header py-4">
+              <div className="flex items-center justify-between w-full">
+                {/* 左侧标题和描述 */}
+                <div className="flex items-center">
+                  <div className="p-2 bg-green-500/10 rounded-lg mr-3">
+                    <Languages className="w-5 h-5 text-green-600" />
+                  </div>
+                  <div>
+                    <h2 className="text-lg font-semibold text-foreground">语言 / Language</h2>
+                    <p className="card-description text-sm">托盘菜单与系统提示的语言</p>
+                  </div>
+                </div>
+
+                {/* 右侧语言选择 */}
+                <select
+                  value={settings.locale ?? 'zh-CN'}
+                  onChange={(e) => handleLocaleChange(e.target.value as Locale)}
+                  className="px-3 py-1.5 text-sm rounded-lg border border-border bg-background text-foreground"
+                >
+                  <option value="zh-CN">简体中文</option>
+                  <option value="en-US">English</option>
+                </select>
+              </div>
+            </div>
+          </div>
+
+          {/* 开机启动 */}
+          <div className="card card-shadow hover-lift slide-up">
+            <div className="card-header py-4">
+              <div className="flex items-center justify-between w-full">
+                {/* 左侧标题和描述 */}
+                <div className="flex items-center">
+                  <div className="p-2 bg-orange-500/10 rounded-lg mr-3">
+                    <Power className="w-5 h-5 text-orange-600" />
+                  </div>
+                  <div>
+                    <h2 className="text-lg font-semibold text-foreground">开机启动</h2>
+                    <p className="card-description text-sm">登录系统后自动在托盘中运行，不弹出窗口</p>
+                  </div>
+                </div>
+
+                {/* 右侧开关按钮 */}
+                <label className="relative inline-flex items-center cursor-pointer">
+                  <input
+                    type="checkbox"
+                    checked={settings.launchAtLogin ?? false}
+                    onChange={(e) => handleLaunchAtLoginChange(e.target.checked)}
+                    className="sr-only peer"
+                  />
+                  <div className="w-11 h-6 bg-orange-100 peer-focus:ring-orange-200 rounded-full peer peer-checked:bg-orange-500 after:bg-white after:border-orange-200 peer-checked:after:translate-x-full after:content-[''] after:absolute after:top-[2px] after:left-[2px] after:rounded-full after:h-5 after:w-5 after:transition-all"></div>
+                </label>
+              </div>
+            </div>
+          </div>
+
+          {/* 本地 HTTP 接口 */}
+          <LocalApiCard />
+
+          {/* 快速添加全局快捷键 */}
+          <QuickAddShortcutCard />
+
           {/* 胶囊折叠模式设置 */}
           <div className="card card-shadow hover-lift slide-up">
             <div className="card-header py-4">
@@ -337,6 +419,110 @@ export default function Other() {
           
           </div>
 
+          {/* CSV 导入导出卡片，仅桌面端 */}
+          {isTauri && (
+            <div className="card card-shadow slide-up">
+              <div className="card-header">
+                <div className="flex items-center justify-between w-full">
+                  <div className="flex items-center">
+                    <div className="p-2 bg-green-500/10 rounded-lg mr-3">
+                      <FileSpreadsheet className="w-5 h-5 text-green-600" />
+                    </div>
+                    <div>
+                      <h2 className="text-lg font-semibold text-foreground">CSV 导入导出</h2>
+                      <p className="card-description text-sm">与 Excel 等表格互相导入导出待办</p>
+                    </div>
+                  </div>
+                  <button
+                    onClick={() => setShowCsvDialog(true)}
+                    className="flex items-center gap-2 px-4 py-2 bg-green-500 text-white rounded-lg hover:bg-green-600 transition-colors text-sm font-medium"
+                  >
+                    <FileSpreadsheet className="w-4 h-4" />
+                    打开
+                  </button>
+                </div>
+              </div>
+            </div>
+          )}
+
+          {/* 从其他应用导入卡片，仅桌面端 */}
+          {isTauri && (
+            <div className="card card-shadow slide-up">
+              <div className="card-header">
+                <div className="flex items-center justify-between w-full">
+                  <div className="flex items-center">
+                    <div className="p-2 bg-indigo-500/10 rounded-lg mr-3">
+                      <ArrowDownToLine className="w-5 h-5 text-indigo-600" />
+                    </div>
+                    <div>
+                      <h2 className="text-lg font-semibold text-foreground">从其他应用导入</h2>
+                      <p className="card-description text-sm">支持 Microsoft To Do、滴答清单、Todoist 与 todo.txt</p>
+                    </div>
+                  </div>
+                  <button
+                    onClick={() => setShowAppImportDialog(true)}
+                    className="flex items-center gap-2 px-4 py-2 bg-indigo-500 text-white rounded-lg hover:bg-indigo-600 transition-colors text-sm font-medium"
+                  >
+                    <ArrowDownToLine className="w-4 h-4" />
+                    导入
+                  </button>
+                </div>
+              </div>
+            </div>
+          )}
+
+          {/* 导出待办清单卡片，仅桌面端 */}
+          {isTauri && (
+            <div className="card card-shadow slide-up">
+              <div className="card-header">
+                <div className="flex items-center justify-between w-full">
+                  <div className="flex items-center">
+                    <div className="p-2 bg-teal-500/10 rounded-lg mr-3">
+                      <ListChecks className="w-5 h-5 text-teal-600" />
+                    </div>
+                    <div>
+                      <h2 className="text-lg font-semibold text-foreground">导出待办清单</h2>
+                      <p className="card-description text-sm">生成 Markdown 任务列表或 todo.txt，可复制或保存为文件</p>
+                    </div>
+                  </div>
+                  <button
+                    onClick={() => setShowTaskListDialog(true)}
+                    className="flex items-center gap-2 px-4 py-2 bg-teal-500 text-white rounded-lg hover:bg-teal-600 transition-colors text-sm font-medium"
+                  >
+                    <ListChecks className="w-4 h-4" />
+                    导出
+                  </button>
+                </div>
+              </div>
+            </div>
+          )}
+
+          {/* 文件夹同步卡片，仅桌面端 */}
+          {isTauri && (
+            <div className="card card-shadow slide-up">
+              <div className="card-header">
+                <div className="flex items-center justify-between w-full">
+                  <div className="flex items-center">
+                    <div className="p-2 bg-sky-500/10 rounded-lg mr-3">
+                      <FolderSync className="w-5 h-5 text-sky-600" />
+                    </div>
+                    <div>
+                      <h2 className="text-lg font-semibold text-foreground">文件夹同步</h2>
+                      <p className="card-description text-sm">通过网盘等共享文件夹在多台设备间同步待办</p>
+                    </div>
+                  </div>
+                  <button
+                    onClick={() => setShowSyncDialog(true)}
+                    className="flex items-center gap-2 px-4 py-2 bg-sky-500 text-white rounded-lg hover:bg-sky-600 transition-colors text-sm font-medium"
+                  >
+                    <FolderSync className="w-4 h-4" />
+                    设置
+                  </button>
+                </div>
+              </div>
+            </div>
+          )}
+
           {/* 数据管理卡片 */}
           <div className="card card-shadow slide-up">
             <div className="card-header">
@@ -435,6 +621,26 @@ export default function Other() {
         onConfirm={handleClearCache}
       />
 
+      <TaskCsvDialog
+        isOpen={showCsvDialog}
+        onClose={() => setShowCsvDialog(false)}
+      />
+
+      <AppImportDialog
+        isOpen={showAppImportDialog}
+        onClose={() => setShowAppImportDialog(false)}
+      />
+
+      <TaskListExportDialog
+        isOpen={showTaskListDialog}
+        onClose={() => setShowTaskListDialog(false)}
+      />
+
+      <SyncDialog
+        isOpen={showSyncDialog}
+        onClose={() => setShowSyncDialog(false)}
+      />
+
       {/* 导出报告对话框 */}
       <ReportExportDialog
         isOpen={showExportDialog}
@@ -0,0 +1,111 @@
+import React, { useState, useRef, useEffect } from 'react';
+import { Plus } from 'lucide-react';
+import { quickAddApi, settingsApi, onQuickAddShown } from '../api/tauri';
+import type { Priority } from '../types';
+
+const PRIORITIES: { value: Priority; label: string; color: string }[] = [
+  { value: 'high', label: '高', color: 'border-red-200 bg-red-50 text-red-700' },
+  { value: 'medium', label: '中', color: 'border-yellow-200 bg-yellow-50 text-yellow-700' },
+  { value: 'low', label: '低', color: 'border-green-200 bg-green-50 text-green-700' },
+];
+
+// 全局快捷键唤出的快速添加窗口：回车保存后自动收起，Esc 直接收起
+export default function QuickAddWindow() {
+  const [title, setTitle] = useState('');
+  const [priority, setPriority] = useState<Priority>('medium');
+  const [error, setError] = useState('');
+  const [isSubmitting, setIsSubmitting] = useState(false);
+  const titleInputRef = useRef<HTMLInputElement>(null);
+
+  const reset = () => {
+    setTitle('');
+    setPriority('medium');
+    setError('');
+    titleInputRef.current?.focus();
+  };
+
+  useEffect(() => {
+    // 跟随主窗口的主题
+    settingsApi.getSettings().then((response) => {
+      if (response.success && response.data) {
+        const theme = response.data.theme;
+        document.documentElement.classList.toggle('dark', theme === 'dark');
+        document.documentElement.setAttribute('data-theme', theme);
+      }
+    });
+
+    reset();
+    const unlisten = onQuickAddShown(reset);
+    return () => {
+      unlisten.then(fn => fn());
+    };
+  }, []);
+
+  const handleSubmit = async (e: React.FormEvent) => {
+    e.preventDefault();
+    if (!title.trim() || isSubmitting) {
+      return;
+    }
+
+    setIsSubmitting(true);
+    try {
+      const response = await quickAddApi.submit({ title: title.trim(), priority });
+      if (response.success) {
+        reset();
+      } else {
+        setError(response.error || '创建待办失败');
+      }
+    } finally {
+      setIsSubmitting(false);
+    }
+  };
+
+  const handleKeyDown = (e: React.KeyboardEvent) => {
+    if (e.key === 'Escape') {
+      quickAddApi.hide();
+    }
+  };
+
+  return (
+    <form
+      onSubmit={handleSubmit}
+      onKeyDown={handleKeyDown}
+      className="h-screen flex flex-col justify-center gap-3 p-4 bg-card border border-border rounded-lg"
+    >
+      <div className="flex items-center gap-2">
+        <div className="w-8 h-8 shrink-0 rounded bg-primary flex items-center justify-center">
+          <Plus className="w-5 h-5 text-primary-foreground" />
+        </div>
+        <input
+          ref={titleInputRef}
+          type="text"
+          placeholder="输入待办标题，回车保存"
+          value={title}
+          onChange={(e) => setTitle(e.target.value)}
+          className="flex-1 px-3 py-2 bg-background border border-border rounded-lg focus:outline-none focus:ring-2 focus:ring-primary focus:border-transparent text-foreground placeholder-muted-foreground"
+          maxLength={100}
+        />
+      </div>
+
+      <div className="flex items-center justify-between gap-2 text-sm">
+        <div className="flex gap-2">
+          {PRIORITIES.map((p) => (
+            <button
+              key={p.value}
+              type="button"
+              onClick={() => setPriority(p.value)}
+              className={`px-3 py-1 rounded-lg border transition-colors ${
+                priority === p.value ? p.color : 'border-border bg-background text-muted-foreground hover:bg-muted'
+              }`}
+            >
+              {p.label}
+            </button>
+          ))}
+        </div>
+        <span className="text-xs text-muted-foreground">Esc 收起</span>
+      </div>
+
+      {error && <p className="text-xs text-red-500">{error}</p>}
+    </form>
+  );
+}
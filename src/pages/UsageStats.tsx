@@ -1,9 +1,10 @@
 import React, { useEffect, useState } from 'react';
 import { ArrowLeft, Clock, Timer, TrendingUp, Calendar, BarChart3 } from 'lucide-react';
 import { useNavigate } from 'react-router-dom';
-import { useUsageStore } from '../store/usageStore';
+import { useUsageStore, toDateString } from '../store/usageStore';
 import UsageTimer from '../components/UsageTimer';
 import UsageChart from '../components/UsageChart';
+import CompletionAnalytics from '../components/CompletionAnalytics';
 
 export default function UsageStats() {
   const navigate = useNavigate();
@@ -11,64 +12,47 @@ export default function UsageStats() {
     stats,
     formatMinutes,
     loadUsageData,
-    calculateStats,
+    refreshStats,
+    getUsageDetails,
     isTrackingEnabled
   } = useUsageStore();
 
   const [selectedPeriod, setSelectedPeriod] = useState<'week' | 'month'>('week');
   const [chartType, setChartType] = useState<'bar' | 'line'>('bar');
+  const [usageHistory, setUsageHistory] = useState<{ date: string; minutes: number; dayName?: string }[]>([]);
 
   useEffect(() => {
     loadUsageData();
-    calculateStats();
+    refreshStats();
 
     // 每分钟更新一次
     const interval = setInterval(() => {
-      calculateStats();
+      refreshStats();
     }, 60000);
 
     return () => clearInterval(interval);
-  }, [loadUsageData, calculateStats]);
-
-  const getUsageHistory = () => {
-    const weekData = JSON.parse(localStorage.getItem('weekly_usage') || '{}');
-    const monthData = JSON.parse(localStorage.getItem('monthly_usage') || '{}');
-
-    if (selectedPeriod === 'week') {
-      const result = [];
-      for (let i = 6; i >= 0; i--) {
-        const date = new Date();
-        date.setDate(date.getDate() - i);
-        const dateStr = date.toISOString().split('T')[0];
-        result.push({
-          date: dateStr,
-          minutes: weekData[dateStr] || 0,
-          dayName: date.toLocaleDateString('zh-CN', { weekday: 'short' })
-        });
-      }
-      return result;
-    } else {
-      // 月视图显示整个月的数据
-      const result = [];
-      const now = new Date();
-      const year = now.getFullYear();
-      const month = now.getMonth();
-      const daysInMonth = new Date(year, month + 1, 0).getDate();
-
-      for (let day = 1; day <= daysInMonth; day++) {
-        const date = new Date(year, month, day);
-        const dateStr = date.toISOString().split('T')[0];
-        result.push({
-          date: dateStr,
-          minutes: monthData[dateStr] || 0
-        });
-      }
-      return result;
-    }
-  };
-
-  const usageHistory = getUsageHistory();
-  const maxMinutes = Math.max(...usageHistory.map(h => h.minutes), 1);
+  }, [loadUsageData, refreshStats]);
+
+  // 周视图为最近7天，月视图为本月整月
+  useEffect(() => {
+    const now = new Date();
+    const start = selectedPeriod === 'week'
+      ? new Date(now.getFullYear(), now.getMonth(), now.getDate() - 6)
+      : new Date(now.getFullYear(), now.getMonth(), 1);
+    const end = selectedPeriod === 'week'
+      ? now
+      : new Date(now.getFullYear(), now.getMonth() + 1, 0);
+
+    getUsageDetails(toDateString(start), toDateString(end)).then(details => {
+      setUsageHistory(details.map(detail => ({
+        date: detail.date,
+        minutes: detail.totalMinutes,
+        dayName: selectedPeriod === 'week'
+          ? new Date(`${detail.date}T00:00:00`).toLocaleDateString('zh-CN', { weekday: 'short' })
+          : undefined
+      })));
+    });
+  }, [selectedPeriod, stats, getUsageDetails]);
 
   return (
     <div className="min-h-screen bg-background p-4">
@@ -184,6 +168,9 @@ export default function UsageStats() {
           period={selectedPeriod}
         />
 
+        {/* 完成热力图与连续天数 */}
+        <CompletionAnalytics />
+
         {/* 使用建议 */}
         {stats.today > 0 && (
           <div className="bg-blue-50 border border-blue-200 rounded-lg p-4">
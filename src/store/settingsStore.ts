@@ -35,6 +35,11 @@ const defaultSettings: Settings = {
   transparentLevel: 100,
   isEdgeSnapped: false,
   edgePosition: 'right',
+  locale: 'zh-CN',
+  apiEnabled: false,
+  apiPort: 27485,
+  quickAddShortcut: 'CommandOrControl+Shift+Space',
+  launchAtLogin: false,
 };
 
 export const useSettingsStore = create<SettingsState>()(devtools(
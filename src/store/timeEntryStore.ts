@@ -0,0 +1,106 @@
+import { create } from 'zustand';
+import { timeEntryApi } from '../api/tauri';
+import type {
+  TimeEntry,
+  AddTimeEntryRequest,
+  UpdateTimeEntryRequest,
+  ApiResponse
+} from '../types';
+
+interface TimeEntryStore {
+  // 正在进行的计时，由后端保存，应用重启后恢复
+  running: TimeEntry | null;
+
+  // 正在计时的已用秒数，每秒刷新
+  elapsed: number;
+
+  // Actions
+  loadRunning: () => Promise<void>;
+  startTimer: (taskId: string) => Promise<void>;
+  stopTimer: () => Promise<void>;
+  getEntries: (taskId?: string) => Promise<TimeEntry[]>;
+  addEntry: (request: AddTimeEntryRequest) => Promise<TimeEntry | null>;
+  updateEntry: (id: string, updates: UpdateTimeEntryRequest) => Promise<TimeEntry | null>;
+  deleteEntry: (id: string) => Promise<boolean>;
+}
+
+function elapsedSeconds(entry: TimeEntry | null): number {
+  if (!entry) {
+    return 0;
+  }
+  return Math.max(0, Math.floor((Date.now() - new Date(entry.start).getTime()) / 1000));
+}
+
+function unwrap<T>(response: ApiResponse<T>): T | null {
+  if (!response.success) {
+    console.error('计时操作失败:', response.error);
+    throw new Error(response.error || '计时操作失败');
+  }
+  return response.data;
+}
+
+export const useTimeEntryStore = create<TimeEntryStore>((set, get) => ({
+  running: null,
+
+  elapsed: 0,
+
+  loadRunning: async () => {
+    const response = await timeEntryApi.getRunning();
+    if (response.success) {
+      const running = response.data ?? null;
+      set({ running, elapsed: elapsedSeconds(running) });
+    }
+  },
+
+  startTimer: async (taskId: string) => {
+    const running = unwrap(await timeEntryApi.start(taskId));
+    set({ running, elapsed: elapsedSeconds(running) });
+  },
+
+  stopTimer: async () => {
+    unwrap(await timeEntryApi.stop());
+    set({ running: null, elapsed: 0 });
+  },
+
+  getEntries: async (taskId?: string) => {
+    const response = await timeEntryApi.getEntries(taskId);
+    return response.success && response.data ? response.data : [];
+  },
+
+  addEntry: async (request: AddTimeEntryRequest) => {
+    return unwrap(await timeEntryApi.add(request));
+  },
+
+  updateEntry: async (id: string, updates: UpdateTimeEntryRequest) => {
+    const entry = unwrap(await timeEntryApi.update(id, updates));
+    // 修改的是正在计时的记录时同步显示，设置了结束时间即已停止
+    if (entry && get().running?.id === id) {
+      const running = entry.end ? null : entry;
+      set({ running, elapsed: elapsedSeconds(running) });
+    }
+    return entry;
+  },
+
+  deleteEntry: async (id: string) => {
+    unwrap(await timeEntryApi.delete(id));
+    if (get().running?.id === id) {
+      set({ running: null, elapsed: 0 });
+    }
+    return true;
+  }
+}));
+
+// 应用启动时恢复上次未停止的计时
+if (typeof window !== 'undefined') {
+  window.addEventListener('load', () => {
+    useTimeEntryStore.getState().loadRunning().catch(error => console.error('加载计时失败:', error));
+  });
+
+  // 计时中每秒刷新显示的已用时间
+  setInterval(() => {
+    const { running } = useTimeEntryStore.getState();
+    if (running) {
+      useTimeEntryStore.setState({ elapsed: elapsedSeconds(running) });
+    }
+  }, 1000);
+}
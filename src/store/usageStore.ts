@@ -1,41 +1,36 @@
 import { create } from 'zustand';
-import { toast } from 'sonner';
+import { usageApi, pomodoroApi, onPomodoroChanged } from '../api/tauri';
 import type { 
-  UsageRecord, 
   UsageStats, 
   UsageDetail, 
-  PomodoroState 
+  PomodoroState,
+  PomodoroSettings,
+  ApiResponse
 } from '../types';
 
 interface UsageStore {
-  // 使用时长数据
-  usageRecords: UsageRecord[];
-  currentSession: UsageRecord | null;
+  // 使用时长统计，由后端记录
   stats: UsageStats;
   
-  // 番茄钟状态
+  // 番茄钟状态，由后端计时，这里只按结束时间刷新剩余秒数
   pomodoro: PomodoroState;
-  pomodoroInterval: NodeJS.Timeout | null;
   
-  // 追踪状态
+  // 后端可用时即在记录
   isTrackingEnabled: boolean;
-  sessionStartTime: number;
-  dailyStartTime: number; // 今日应用启动时间
   
   // Actions
-  startTracking: () => void;
-  stopTracking: () => void;
-  saveCurrentSession: () => void;
   loadUsageData: () => void;
-  calculateStats: () => void;
-  getUsageDetails: (days: number) => UsageDetail[];
+  refreshStats: () => Promise<void>;
+  getUsageDetails: (startDate: string, endDate: string) => Promise<UsageDetail[]>;
   
   // 番茄钟Actions
-  startPomodoro: () => void;
-  pausePomodoro: () => void;
-  resetPomodoro: () => void;
-  switchPomodoroMode: () => void;
-  updatePomodoroSettings: (settings: Partial<Pick<PomodoroState, 'workDuration' | 'breakDuration' | 'longBreakDuration' | 'cyclesBeforeLongBreak'>>) => void;
+  loadPomodoro: () => Promise<void>;
+  startPomodoro: (taskId?: string) => Promise<void>;
+  setPomodoroTask: (taskId: string | null) => Promise<void>;
+  pausePomodoro: () => Promise<void>;
+  resetPomodoro: () => Promise<void>;
+  skipPomodoro: () => Promise<void>;
+  updatePomodoroSettings: (settings: PomodoroSettings) => Promise<void>;
   
   // 工具方法
   formatTime: (seconds: number) => string;
@@ -50,12 +45,57 @@ const DEFAULT_POMODORO: PomodoroState = {
   workDuration: 25,
   breakDuration: 5,
   longBreakDuration: 15,
-  cyclesBeforeLongBreak: 4
+  cyclesBeforeLongBreak: 4,
+  interruptions: 0,
+  focusedSeconds: 0
 };
 
+// 旧版本保存在浏览器缓存中的番茄钟设置与状态，迁移到后端后删除
+const LEGACY_POMODORO_SETTINGS_KEY = 'pomodoro_settings';
+const LEGACY_POMODORO_STATE_KEY = 'pomodoro_current_state';
+
+// 运行中按后端给出的结束时间计算剩余秒数，不依赖定时器累加
+function withTimeLeft(pomodoro: PomodoroState): PomodoroState {
+  if (!pomodoro.isActive || !pomodoro.endsAt) {
+    return pomodoro;
+  }
+  const timeLeft = Math.max(0, Math.ceil((new Date(pomodoro.endsAt).getTime() - Date.now()) / 1000));
+  return { ...pomodoro, timeLeft };
+}
+
+// 旧版本保存在浏览器缓存中的使用记录，导入后端后删除
+const LEGACY_USAGE_KEYS = ['usage_records', 'weekly_usage', 'monthly_usage', 'daily_start_date', 'daily_start_time'];
+
+// 本地日期 YYYY-MM-DD
+export function toDateString(date: Date): string {
+  const month = (date.getMonth() + 1).toString().padStart(2, '0');
+  const day = date.getDate().toString().padStart(2, '0');
+  return `${date.getFullYear()}-${month}-${day}`;
+}
+
+// 把旧版本按天保存的使用分钟数导入后端
+async function migrateLegacyUsage() {
+  const weekData = JSON.parse(localStorage.getItem('weekly_usage') || '{}');
+  const monthData = JSON.parse(localStorage.getItem('monthly_usage') || '{}');
+  const daily: Record<string, number> = { ...weekData, ...monthData };
+  if (Object.keys(daily).length > 0) {
+    const response = await usageApi.importLegacyUsage(daily);
+    if (!response.success) {
+      return;
+    }
+  }
+  LEGACY_USAGE_KEYS.forEach(key => localStorage.removeItem(key));
+}
+
+function applyPomodoroResponse(response: ApiResponse<PomodoroState>) {
+  if (response.success && response.data) {
+    useUsageStore.setState({ pomodoro: withTimeLeft(response.data) });
+  } else {
+    console.error('番茄钟操作失败:', response.error);
+  }
+}
+
 export const useUsageStore = create<UsageStore>((set, get) => ({
-  usageRecords: [],
-  currentSession: null,
   stats: {
     today: 0,
     thisWeek: 0,
@@ -66,383 +106,75 @@ export const useUsageStore = create<UsageStore>((set, get) => ({
   },
   
   pomodoro: DEFAULT_POMODORO,
-  pomodoroInterval: null,
   
   isTrackingEnabled: false,
-  sessionStartTime: 0,
-  dailyStartTime: 0,
-
-  startTracking: () => {
-    const now = Date.now();
-    const today = new Date().toISOString().split('T')[0];
-    
-    // 检查是否为新的一天，如果是则重置dailyStartTime
-    const storedDate = localStorage.getItem('daily_start_date');
-    let dailyStart = now;
-    
-    if (storedDate === today) {
-      // 同一天，获取之前保存的启动时间
-      const storedStartTime = localStorage.getItem('daily_start_time');
-      if (storedStartTime) {
-        dailyStart = parseInt(storedStartTime, 10);
-      }
-    } else {
-      // 新的一天，保存新的启动时间和日期
-      localStorage.setItem('daily_start_date', today);
-      localStorage.setItem('daily_start_time', now.toString());
-    }
-
-    set({
-      isTrackingEnabled: true,
-      sessionStartTime: now,
-      dailyStartTime: dailyStart
-    });
-
-    // 每分钟更新一次统计
-    const trackingInterval = setInterval(() => {
-      const state = get();
-      if (state.isTrackingEnabled) {
-        state.calculateStats();
-      }
-    }, 60000); // 每分钟检查一次
-
-    // 保存interval引用用于清理
-    (window as any).usageTrackingInterval = trackingInterval;
-  },
-
-  stopTracking: () => {
-    const state = get();
-    
-    if (state.currentSession && state.isTrackingEnabled) {
-      const now = Date.now();
-      const duration = Math.floor((now - state.sessionStartTime) / (1000 * 60));
-      
-      if (duration >= 1) { // 至少使用1分钟才记录
-        const completedSession: UsageRecord = {
-          ...state.currentSession,
-          endTime: now,
-          duration
-        };
-
-        const updatedRecords = [...state.usageRecords, completedSession];
-        localStorage.setItem('usage_records', JSON.stringify(updatedRecords));
-
-        set({
-          usageRecords: updatedRecords,
-          currentSession: null,
-          isTrackingEnabled: false,
-          sessionStartTime: 0
-        });
-
-        state.calculateStats();
-        toast.success(`本次使用 ${state.formatMinutes(duration)}`);
-      }
-    }
-
-    // 清理tracking interval
-    if ((window as any).usageTrackingInterval) {
-      clearInterval((window as any).usageTrackingInterval);
-      (window as any).usageTrackingInterval = null;
-    }
-
-    set({
-      isTrackingEnabled: false,
-      currentSession: null,
-      sessionStartTime: 0
-    });
-  },
-
-  saveCurrentSession: () => {
-    const state = get();
-    if (state.currentSession) {
-      const existingRecords = [...state.usageRecords];
-      const existingIndex = existingRecords.findIndex(r => r.id === state.currentSession!.id);
-      
-      if (existingIndex >= 0) {
-        existingRecords[existingIndex] = state.currentSession;
-      } else {
-        existingRecords.push(state.currentSession);
-      }
-      
-      localStorage.setItem('usage_records', JSON.stringify(existingRecords));
-      set({ usageRecords: existingRecords });
-    }
-  },
 
   loadUsageData: () => {
     try {
-      const stored = localStorage.getItem('usage_records');
-      if (stored) {
-        const records: UsageRecord[] = JSON.parse(stored);
-        set({ usageRecords: records });
-        get().calculateStats();
+      if (localStorage.getItem('monthly_usage') || localStorage.getItem('weekly_usage')) {
+        migrateLegacyUsage()
+          .then(() => get().refreshStats())
+          .catch(error => console.error('导入旧版使用记录失败:', error));
       }
 
-      // 首先尝试加载当前番茄钟状态
-      const currentPomodoroState = localStorage.getItem('pomodoro_current_state');
-      if (currentPomodoroState) {
-        const savedState = JSON.parse(currentPomodoroState);
-        set({ pomodoro: savedState });
-        
-        // 如果番茄钟之前是运行状态，重新启动定时器
-        if (savedState.isActive) {
-          get().startPomodoro();
-        }
-      } else {
-        // 加载番茄钟设置（仅在没有当前状态时）
-        const pomodoroSettings = localStorage.getItem('pomodoro_settings');
-        if (pomodoroSettings) {
-          const settings = JSON.parse(pomodoroSettings);
-          set({
-            pomodoro: {
-              ...DEFAULT_POMODORO,
-              ...settings,
-              timeLeft: settings.workDuration * 60
-            }
-          });
-        }
-      }
+      get().loadPomodoro().catch(error => console.error('加载番茄钟状态失败:', error));
     } catch (error) {
       console.error('加载使用数据失败:', error);
     }
   },
 
-  calculateStats: () => {
-    const { dailyStartTime } = get();
-    const now = Date.now();
-    const today = new Date().toISOString().split('T')[0];
-    
-    // 计算今日应用运行总时长（分钟）
-    const todayMinutes = dailyStartTime > 0 ? Math.floor((now - dailyStartTime) / (1000 * 60)) : 0;
-    
-    // 从localStorage获取历史数据
-    const weekData = JSON.parse(localStorage.getItem('weekly_usage') || '{}');
-    const monthData = JSON.parse(localStorage.getItem('monthly_usage') || '{}');
-    
-    // 更新今日数据
-    const todayUsage: Record<string, number> = { [today]: todayMinutes };
-    const updatedWeekData: Record<string, number> = { ...weekData, ...todayUsage };
-    const updatedMonthData: Record<string, number> = { ...monthData, ...todayUsage };
-    
-    // 计算本周总时长
-    const startOfWeek = new Date();
-    const day = startOfWeek.getDay();
-    const diff = startOfWeek.getDate() - day + (day === 0 ? -6 : 1);
-    startOfWeek.setDate(diff);
-    
-    let weekMinutes = 0;
-    for (let i = 0; i < 7; i++) {
-      const date = new Date(startOfWeek);
-      date.setDate(startOfWeek.getDate() + i);
-      const dateStr = date.toISOString().split('T')[0];
-      weekMinutes += updatedWeekData[dateStr] || 0;
-    }
-    
-    // 计算本月总时长
-    const startOfMonth = new Date(new Date().getFullYear(), new Date().getMonth(), 1);
-    let monthMinutes = 0;
-    const daysInMonth = new Date(new Date().getFullYear(), new Date().getMonth() + 1, 0).getDate();
-    
-    for (let i = 1; i <= daysInMonth; i++) {
-      const date = new Date(new Date().getFullYear(), new Date().getMonth(), i);
-      const dateStr = date.toISOString().split('T')[0];
-      monthMinutes += updatedMonthData[dateStr] || 0;
+  refreshStats: async () => {
+    const response = await usageApi.getUsageStats();
+    if (response.success && response.data) {
+      set({ stats: response.data, isTrackingEnabled: true });
+    } else {
+      set({ isTrackingEnabled: false });
     }
-    
-    // 计算平均和其他统计
-    const allDays = Object.keys(updatedMonthData);
-    const activeDays = allDays.filter(date => (updatedMonthData[date] as number) > 0);
-    const totalMinutes = Object.values(updatedMonthData).reduce((a, b) => a + (typeof b === 'number' ? b : 0), 0);
-    const averageDaily = activeDays.length > 0 ? Math.round(totalMinutes / activeDays.length) : 0;
-    const longestSession = Math.max(...Object.values(updatedMonthData).map(v => typeof v === 'number' ? v : 0), 0);
-    
-    // 保存数据
-    localStorage.setItem('weekly_usage', JSON.stringify(updatedWeekData));
-    localStorage.setItem('monthly_usage', JSON.stringify(updatedMonthData));
-
-    set({
-      stats: {
-        today: todayMinutes,
-        thisWeek: weekMinutes,
-        thisMonth: monthMinutes,
-        averageDaily,
-        totalSessions: activeDays.length,
-        longestSession
-      }
-    });
   },
 
-  getUsageDetails: (days: number): UsageDetail[] => {
-    const { usageRecords } = get();
-    const result: UsageDetail[] = [];
-    
-    for (let i = 0; i < days; i++) {
-      const date = new Date();
-      date.setDate(date.getDate() - i);
-      const dateStr = date.toISOString().split('T')[0];
-      
-      const dayRecords = usageRecords.filter(r => r.date === dateStr);
-      const totalMinutes = dayRecords.reduce((sum, r) => sum + r.duration, 0);
-      
-      if (dayRecords.length > 0) {
-        const firstUse = new Date(Math.min(...dayRecords.map(r => r.startTime))).toLocaleTimeString('zh-CN', { hour12: false });
-        const lastUse = new Date(Math.max(...dayRecords.map(r => r.endTime))).toLocaleTimeString('zh-CN', { hour12: false });
-        
-        result.push({
-          date: dateStr,
-          totalMinutes,
-          sessions: dayRecords.length,
-          firstUse,
-          lastUse
-        });
-      } else {
-        result.push({
-          date: dateStr,
-          totalMinutes: 0,
-          sessions: 0
-        });
-      }
-    }
-    
-    return result;
+  getUsageDetails: async (startDate: string, endDate: string): Promise<UsageDetail[]> => {
+    const response = await usageApi.getUsageDetails(startDate, endDate);
+    return response.success && response.data ? response.data : [];
   },
 
-  startPomodoro: () => {
-    const { pomodoro } = get();
-    
-    // 如果已经在运行，不执行任何操作
-    if (pomodoro.isActive) {
+  loadPomodoro: async () => {
+    const response = await pomodoroApi.getState();
+    if (!response.success) {
       return;
     }
+    applyPomodoroResponse(response);
 
-    // 开始或继续
-    const newPomodoroState = { ...pomodoro, isActive: true };
-    set({ pomodoro: newPomodoroState });
-    
-    // 保存番茄钟状态到localStorage
-    localStorage.setItem('pomodoro_current_state', JSON.stringify(newPomodoroState));
-
-    const interval = setInterval(() => {
-      const currentState = get();
-      const { pomodoro: currentPomodoro } = currentState;
-      
-      if (currentPomodoro.timeLeft <= 0) {
-        // 时间到，切换模式
-        currentState.switchPomodoroMode();
-        return;
-      }
-
-      const updatedPomodoro = {
-        ...currentPomodoro,
-        timeLeft: currentPomodoro.timeLeft - 1
-      };
-      
-      set({ pomodoro: updatedPomodoro });
-      
-      // 每秒保存当前状态
-      localStorage.setItem('pomodoro_current_state', JSON.stringify(updatedPomodoro));
-    }, 1000);
-
-    set({ pomodoroInterval: interval });
-  },
-
-  pausePomodoro: () => {
-    const { pomodoro, pomodoroInterval } = get();
-    
-    const pausedPomodoro = { ...pomodoro, isActive: false };
-    set({ pomodoro: pausedPomodoro });
-    
-    // 保存暂停状态
-    localStorage.setItem('pomodoro_current_state', JSON.stringify(pausedPomodoro));
-
-    if (pomodoroInterval) {
-      clearInterval(pomodoroInterval);
-      set({ pomodoroInterval: null });
+    const legacySettings = localStorage.getItem(LEGACY_POMODORO_SETTINGS_KEY);
+    if (legacySettings) {
+      const { workDuration, breakDuration, longBreakDuration, cyclesBeforeLongBreak } = { ...DEFAULT_POMODORO, ...JSON.parse(legacySettings) };
+      applyPomodoroResponse(await pomodoroApi.updateSettings({ workDuration, breakDuration, longBreakDuration, cyclesBeforeLongBreak }));
     }
+    localStorage.removeItem(LEGACY_POMODORO_SETTINGS_KEY);
+    localStorage.removeItem(LEGACY_POMODORO_STATE_KEY);
   },
 
-  resetPomodoro: () => {
-    const { pomodoro, pomodoroInterval } = get();
-
-    if (pomodoroInterval) {
-      clearInterval(pomodoroInterval);
-      set({ pomodoroInterval: null });
-    }
-
-    const resetState = {
-      ...pomodoro,
-      isActive: false,
-      currentMode: 'work' as const,
-      timeLeft: pomodoro.workDuration * 60,
-      cycle: 0
-    };
-
-    set({ pomodoro: resetState });
-    
-    // 清除持久化的当前状态
-    localStorage.removeItem('pomodoro_current_state');
+  startPomodoro: async (taskId?: string) => {
+    applyPomodoroResponse(await pomodoroApi.start(taskId));
   },
 
-  switchPomodoroMode: () => {
-    const { pomodoro, pomodoroInterval } = get();
-
-    if (pomodoroInterval) {
-      clearInterval(pomodoroInterval);
-      set({ pomodoroInterval: null });
-    }
-
-    let newMode: PomodoroState['currentMode'] = 'work';
-    let newCycle = pomodoro.cycle;
-    let newTimeLeft = pomodoro.workDuration * 60;
-
-    if (pomodoro.currentMode === 'work') {
-      newCycle += 1;
-      
-      if (newCycle % pomodoro.cyclesBeforeLongBreak === 0) {
-        newMode = 'longBreak';
-        newTimeLeft = pomodoro.longBreakDuration * 60;
-        toast.success(`🎉 完成${newCycle}个番茄钟！开始长休息`);
-      } else {
-        newMode = 'break';
-        newTimeLeft = pomodoro.breakDuration * 60;
-        toast.success('✅ 番茄钟完成！开始休息');
-      }
-    } else {
-      newMode = 'work';
-      newTimeLeft = pomodoro.workDuration * 60;
-      toast.success('⏰ 休息结束！开始专注工作');
-    }
-
-    const newPomodoroState = {
-      ...pomodoro,
-      isActive: true,
-      currentMode: newMode,
-      timeLeft: newTimeLeft,
-      cycle: newCycle
-    };
+  setPomodoroTask: async (taskId: string | null) => {
+    applyPomodoroResponse(await pomodoroApi.setTask(taskId));
+  },
 
-    set({ pomodoro: newPomodoroState });
-    
-    // 保存切换后的状态
-    localStorage.setItem('pomodoro_current_state', JSON.stringify(newPomodoroState));
+  pausePomodoro: async () => {
+    applyPomodoroResponse(await pomodoroApi.pause());
+  },
 
-    // 继续下一阶段
-    get().startPomodoro();
+  resetPomodoro: async () => {
+    applyPomodoroResponse(await pomodoroApi.reset());
   },
 
-  updatePomodoroSettings: (settings) => {
-    const { pomodoro } = get();
-    const newPomodoro = { ...pomodoro, ...settings };
-    
-    // 如果当前不在运行状态，更新时间
-    if (!pomodoro.isActive) {
-      newPomodoro.timeLeft = newPomodoro.workDuration * 60;
-    }
+  skipPomodoro: async () => {
+    applyPomodoroResponse(await pomodoroApi.skip());
+  },
 
-    set({ pomodoro: newPomodoro });
-    
-    // 保存设置
-    localStorage.setItem('pomodoro_settings', JSON.stringify(settings));
+  updatePomodoroSettings: async (settings) => {
+    applyPomodoroResponse(await pomodoroApi.updateSettings(settings));
   },
 
   formatTime: (seconds: number): string => {
@@ -466,36 +198,22 @@ export const useUsageStore = create<UsageStore>((set, get) => ({
   }
 }));
 
-// 页面可见性变化处理
+// 应用启动时加载番茄钟状态并导入旧版使用记录
 if (typeof window !== 'undefined') {
-  document.addEventListener('visibilitychange', () => {
-    const store = useUsageStore.getState();
-    
-    if (document.hidden) {
-      // 页面隐藏，暂停追踪但不停止
-      if (store.isTrackingEnabled) {
-        // 记录隐藏时间，但继续会话
-      }
-    } else {
-      // 页面显示，如果之前在追踪则继续
-      if (!store.isTrackingEnabled) {
-        store.startTracking();
-      }
-    }
+  window.addEventListener('load', () => {
+    useUsageStore.getState().loadUsageData();
   });
 
-  // 页面关闭时保存数据
-  window.addEventListener('beforeunload', () => {
-    const store = useUsageStore.getState();
-    if (store.isTrackingEnabled) {
-      store.stopTracking();
-    }
+  // 托盘操作、阶段切换等由后端推送
+  onPomodoroChanged((pomodoro) => {
+    useUsageStore.setState({ pomodoro: withTimeLeft(pomodoro) });
   });
 
-  // 应用启动时自动开始追踪
-  window.addEventListener('load', () => {
-    const store = useUsageStore.getState();
-    store.loadUsageData();
-    store.startTracking();
-  });
+  // 运行中每秒刷新显示的剩余时间
+  setInterval(() => {
+    const { pomodoro } = useUsageStore.getState();
+    if (pomodoro.isActive) {
+      useUsageStore.setState({ pomodoro: withTimeLeft(pomodoro) });
+    }
+  }, 1000);
 }
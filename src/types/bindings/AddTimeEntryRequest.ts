@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 事后补录一段计时
+ */
+export type AddTimeEntryRequest = { taskId: string, start: string, end: string, note?: string, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 本地 HTTP 接口的运行状态
+ */
+export type ApiStatus = { running: boolean, url: string, token: string, error?: string, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 设置同步文件夹与本机名称；folder 为空时关闭同步，device_name 为空时保持不变
+ */
+export type ConfigureSyncRequest = { folder?: string, deviceName?: string, };
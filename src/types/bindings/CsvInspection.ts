@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TaskField } from "./TaskField";
+
+/**
+ * CSV 的表头与前几行，以及按表头推测的各列对应字段（无法对应时为 null）
+ */
+export type CsvInspection = { headers: Array<string>, sampleRows: Array<Array<string>>, totalRows: number, suggestedMapping: Array<TaskField | null>, };
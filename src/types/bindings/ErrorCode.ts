@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 前端可识别的错误码，随错误信息一起返回
+ */
+export type ErrorCode = "not_found" | "invalid_input" | "corrupt_data" | "disk_full" | "permission_denied" | "io" | "internal" | "unauthorized";
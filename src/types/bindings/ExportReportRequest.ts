@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ReportFormat } from "./ReportFormat";
+import type { ReportKind } from "./ReportKind";
+
+/**
+ * 导出或预览周报、月报；导出时未传保存路径则弹出保存对话框
+ */
+export type ExportReportRequest = { kind: ReportKind, format: ReportFormat, startDate?: string, endDate?: string, template?: string, path?: string, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TaskField } from "./TaskField";
+
+/**
+ * 导出待办为 CSV；未传列时导出全部字段，未传保存路径则弹出保存对话框
+ */
+export type ExportTasksCsvRequest = { columns?: Array<TaskField>, path?: string, };
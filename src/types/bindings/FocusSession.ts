@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 一次完整的番茄钟专注，保存在数据目录的 focus_sessions.json
+ */
+export type FocusSession = { id: string, taskId?: string, start: string, end: string, minutes: number, interruptions: number, };
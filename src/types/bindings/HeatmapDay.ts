@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 热力图中的一天，level 为 0-4 的颜色深浅，按区间内完成最多的一天划分
+ */
+export type HeatmapDay = { date: string, completed: number, level: number, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ImportSource } from "./ImportSource";
+
+/**
+ * 从其他应用的导出文件导入待办；dry_run 时只返回解析结果供预览
+ */
+export type ImportFromAppRequest = { source: ImportSource, content: string, skipCompleted: boolean, dryRun: boolean, };
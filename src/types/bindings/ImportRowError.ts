@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 导入时无法识别的一行，row 为文件中的行号（CSV 表头为第 1 行）；JSON 中为第几个待办
+ */
+export type ImportRowError = { row: number, message: string, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 可导入的其他待办应用导出格式
+ */
+export type ImportSource = "microsoftTodo" | "tickTick" | "todoist" | "todoTxt";
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TaskField } from "./TaskField";
+
+/**
+ * 按列映射导入 CSV，mapping 与表头逐列对应，null 表示忽略该列
+ */
+export type ImportTasksCsvRequest = { content: string, mapping: Array<TaskField | null>, dryRun: boolean, };
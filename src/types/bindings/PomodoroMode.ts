@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 番茄钟阶段
+ */
+export type PomodoroMode = "work" | "break" | "longBreak";
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 番茄钟各阶段时长（分钟）与长休息间隔
+ */
+export type PomodoroSettings = { workDuration: number, breakDuration: number, longBreakDuration: number, cyclesBeforeLongBreak: number, };
@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PomodoroMode } from "./PomodoroMode";
+
+/**
+ * 番茄钟状态，保存在数据目录的 pomodoro.json
+ *
+ * 运行中以 `endsAt` 为准计算剩余时间，窗口隐藏、应用重启都不会造成偏差；暂停时以 `timeLeft` 为准
+ */
+export type PomodoroState = { isActive: boolean, currentMode: PomodoroMode, timeLeft: number, cycle: number, endsAt?: string, taskId?: string, startedAt?: string, interruptions: number, focusedSeconds: number, workDuration: number, breakDuration: number, longBreakDuration: number, cyclesBeforeLongBreak: number, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Priority } from "./Priority";
+
+/**
+ * 某一优先级待办从创建到完成的平均用时
+ */
+export type PriorityCompletionTime = { priority: Priority, completed: number, averageHours?: number, };
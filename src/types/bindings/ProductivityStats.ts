@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { HeatmapDay } from "./HeatmapDay";
+import type { PriorityCompletionTime } from "./PriorityCompletionTime";
+
+/**
+ * 一段时间内的完成情况分析，均按完成时间的本地日期、星期与小时统计
+ */
+export type ProductivityStats = { startDate: string, endDate: string, totalCompleted: number, heatmap: Array<HeatmapDay>, currentStreak: number, longestStreak: number, averageCompletion: Array<PriorityCompletionTime>, byWeekday: Array<number>, byHour: Array<number>, mostProductiveWeekday?: number, mostProductiveHour?: number, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 报告周期，未指定日期时取本周（周一起）或本月
+ */
+export type ReportKind = "week" | "month";
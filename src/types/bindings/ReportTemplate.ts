@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ReportFormat } from "./ReportFormat";
+
+/**
+ * 用户放在数据目录 report_templates 下的报告模板
+ */
+export type ReportTemplate = { name: string, format: ReportFormat, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CollapseMode } from "./CollapseMode";
+import type { EdgePosition } from "./EdgePosition";
+import type { Locale } from "./Locale";
+import type { Theme } from "./Theme";
+
+export type Settings = { theme: Theme, notifications: boolean, autoSave: boolean, isPinned: boolean, isCollapsed: boolean, collapseMode: CollapseMode, useCapsuleMode: boolean, username?: string, avatar?: string, transparentEnabled: boolean, transparentLevel: number, isEdgeSnapped: boolean, edgePosition: EdgePosition, locale: Locale, apiEnabled: boolean, apiPort: number, quickAddShortcut: string, launchAtLogin: boolean, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 全局快捷键的注册状态
+ */
+export type ShortcutStatus = { shortcut: string, registered: boolean, error?: string, };
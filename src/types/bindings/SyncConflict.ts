@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 两台设备在互不知情时修改了同一待办的同一字段，按最后写入的保留，另一个值记录在这里
+ *
+ * field 为前端使用的字段名，`_deleted` 表示一台设备删除了待办而另一台仍在修改；
+ * 两个值均为 JSON 文本
+ */
+export type SyncConflict = { id: string, taskId: string, taskTitle: string, field: string, keptValue: string, keptDevice: string, keptAt: string, otherValue: string, otherDevice: string, otherAt: string, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 参与文件夹同步的其他设备
+ */
+export type SyncDevice = { id: string, name: string, changes: number, lastChangeAt?: string, };
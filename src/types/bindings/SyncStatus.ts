@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SyncDevice } from "./SyncDevice";
+
+/**
+ * 文件夹同步的状态，未启用时 folder 为空
+ */
+export type SyncStatus = { folder?: string, deviceId: string, deviceName: string, devices: Array<SyncDevice>, lastSyncAt?: string, conflicts: number, };
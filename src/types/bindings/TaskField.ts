@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 可导入导出的待办字段，对应 CSV 中的一列
+ */
+export type TaskField = "title" | "description" | "completed" | "priority" | "dueDate" | "createdAt" | "completedAt" | "estimatedMinutes" | "focusMinutes";
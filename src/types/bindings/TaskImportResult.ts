@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ImportRowError } from "./ImportRowError";
+import type { Task } from "./Task";
+
+/**
+ * 导入结果：成功解析的待办与跳过的行
+ */
+export type TaskImportResult = { tasks: Array<Task>, errors: Array<ImportRowError>, };
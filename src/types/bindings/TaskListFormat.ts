@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 待办清单的文本格式
+ */
+export type TaskListFormat = "markdown" | "todoTxt";
@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 待办清单的分组方式
+ *
+ * SemiDone 的待办没有所属清单，也没有子任务，因此只能按优先级或截止日期分组，
+ * 无法按清单分组或把子任务缩进在父任务下
+ */
+export type TaskListGroup = "none" | "priority" | "dueDate";
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TaskListFormat } from "./TaskListFormat";
+import type { TaskListGroup } from "./TaskListGroup";
+
+/**
+ * 生成 Markdown 或 todo.txt 格式的待办清单；导出时未传保存路径则弹出保存对话框
+ */
+export type TaskListRequest = { format: TaskListFormat, groupBy?: TaskListGroup, includeCompleted?: boolean, path?: string, };
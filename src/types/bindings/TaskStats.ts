@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type TaskStats = { total: number, completed: number, pending: number, overdue: number, today: number, highPriority: number, mediumPriority: number, lowPriority: number, };
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 在待办上手动计时的一段时间，保存在数据目录的 time_entries.json
+ *
+ * 结束时间为空表示正在计时，同一时间最多只有一条
+ */
+export type TimeEntry = { id: string, taskId: string, start: string, end?: string, note?: string, };
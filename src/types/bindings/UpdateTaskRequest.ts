@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Attachment } from "./Attachment";
+import type { Priority } from "./Priority";
+
+export type UpdateTaskRequest = { title?: string, description?: string, completed?: boolean, priority?: Priority, dueDate?: string, attachments?: Array<Attachment>, estimatedMinutes?: number, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 修改计时记录，未传的字段保持不变；给正在计时的记录设置结束时间即停止计时
+ */
+export type UpdateTimeEntryRequest = { taskId?: string, start?: string, end?: string, note?: string, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 某一天的使用情况，跨零点的使用时段分别计入两天
+ */
+export type UsageDetail = { date: string, totalMinutes: number, sessions: number, firstUse?: string, lastUse?: string, };
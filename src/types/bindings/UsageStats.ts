@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * 使用时长汇总，单位均为分钟
+ */
+export type UsageStats = { today: number, thisWeek: number, thisMonth: number, averageDaily: number, totalSessions: number, longestSession: number, };
@@ -1,11 +1,58 @@
-// 待办优先级枚举
-export type Priority = 'high' | 'medium' | 'low';
-
-// 主题类型枚举
-export type Theme = 'light' | 'dark' | 'pink';
-
-// 折叠模式类型枚举
-export type CollapseMode = 'expanded' | 'bar' | 'floating';
+// 以下类型由 Rust 模型（src-tauri/src/models.rs）通过 ts-rs 生成，请勿手动修改
+// 修改 Rust 模型后在 src-tauri 目录运行 `cargo test` 重新生成 src/types/bindings
+export type { Priority } from './bindings/Priority';
+export type { Attachment } from './bindings/Attachment';
+export type { Task } from './bindings/Task';
+export type { CreateTaskRequest } from './bindings/CreateTaskRequest';
+export type { UpdateTaskRequest } from './bindings/UpdateTaskRequest';
+export type { TaskStats } from './bindings/TaskStats';
+export type { TaskTrendPoint } from './bindings/TaskTrendPoint';
+export type { HeatmapDay } from './bindings/HeatmapDay';
+export type { PriorityCompletionTime } from './bindings/PriorityCompletionTime';
+export type { ProductivityStats } from './bindings/ProductivityStats';
+export type { ApiResponse } from './bindings/ApiResponse';
+export type { ErrorCode } from './bindings/ErrorCode';
+export type { Theme } from './bindings/Theme';
+export type { CollapseMode } from './bindings/CollapseMode';
+export type { EdgePosition } from './bindings/EdgePosition';
+export type { Locale } from './bindings/Locale';
+export type { Settings } from './bindings/Settings';
+export type { ApiStatus } from './bindings/ApiStatus';
+export type { ShortcutStatus } from './bindings/ShortcutStatus';
+export type { UsageStats } from './bindings/UsageStats';
+export type { UsageDetail } from './bindings/UsageDetail';
+export type { PomodoroMode } from './bindings/PomodoroMode';
+export type { PomodoroSettings } from './bindings/PomodoroSettings';
+export type { PomodoroState } from './bindings/PomodoroState';
+export type { FocusSession } from './bindings/FocusSession';
+export type { TimeEntry } from './bindings/TimeEntry';
+export type { AddTimeEntryRequest } from './bindings/AddTimeEntryRequest';
+export type { UpdateTimeEntryRequest } from './bindings/UpdateTimeEntryRequest';
+export type { ReportKind } from './bindings/ReportKind';
+export type { ReportFormat } from './bindings/ReportFormat';
+export type { ExportReportRequest } from './bindings/ExportReportRequest';
+export type { ReportTemplate } from './bindings/ReportTemplate';
+export type { TaskField } from './bindings/TaskField';
+export type { ExportTasksCsvRequest } from './bindings/ExportTasksCsvRequest';
+export type { CsvInspection } from './bindings/CsvInspection';
+export type { ImportTasksCsvRequest } from './bindings/ImportTasksCsvRequest';
+export type { ImportRowError } from './bindings/ImportRowError';
+export type { TaskImportResult } from './bindings/TaskImportResult';
+export type { ImportSource } from './bindings/ImportSource';
+export type { ImportFromAppRequest } from './bindings/ImportFromAppRequest';
+export type { TaskListFormat } from './bindings/TaskListFormat';
+export type { TaskListGroup } from './bindings/TaskListGroup';
+export type { TaskListRequest } from './bindings/TaskListRequest';
+export type { SyncDevice } from './bindings/SyncDevice';
+export type { SyncConflict } from './bindings/SyncConflict';
+export type { SyncStatus } from './bindings/SyncStatus';
+export type { ConfigureSyncRequest } from './bindings/ConfigureSyncRequest';
+
+import type { Task } from './bindings/Task';
+import type { TaskStats } from './bindings/TaskStats';
+import type { ApiResponse } from './bindings/ApiResponse';
+import type { Settings } from './bindings/Settings';
+import type { UsageStats } from './bindings/UsageStats';
 
 // 待办状态筛选类型
 export type TaskFilter = 'all' | 'pending' | 'completed' | 'overdue' | 'today';
@@ -13,129 +60,9 @@ export type TaskFilter = 'all' | 'pending' | 'completed' | 'overdue' | 'today';
 // 待办筛选类型（用于组件）
 export type TaskFilterType = TaskFilter;
 
-// 附件数据结构
-export interface Attachment {
-  id: string;
-  name: string;
-  size: number;
-  type: string;
-  data: string; // Base64 encoded file data
-  createdAt: string;
-}
-
-// 待办数据结构
-export interface Task {
-  id: string;
-  title: string;
-  description?: string;
-  completed: boolean;
-  priority: Priority;
-  dueDate?: string;
-  createdAt: string;
-  updatedAt: string;
-  attachments?: Attachment[];
-}
-
-// 设置数据结构
-export interface Settings {
-  theme: Theme;
-  notifications: boolean;
-  autoSave: boolean;
-  isPinned: boolean;
-  isCollapsed: boolean;
-  collapseMode: CollapseMode;    // 折叠模式：展开/条状/悬浮球
-  useCapsuleMode: boolean;       // 是否启用胶囊模式
-  username?: string;
-  avatar?: string;
-  transparentEnabled?: boolean;  // 是否开启透明模式
-  transparentLevel?: number;     // 透明度级别 0-100 (100为完全不透明)
-  isEdgeSnapped?: boolean;       // 是否吸附到边缘
-  edgePosition?: 'left' | 'right'; // 吸附边缘位置
-}
-
-// 创建待办的请求参数
-export interface CreateTaskRequest {
-  title: string;
-  description?: string;
-  priority?: Priority;
-  dueDate?: string;
-  attachments?: Attachment[];
-}
-
-// 更新待办的请求参数
-export interface UpdateTaskRequest {
-  title?: string;
-  description?: string;
-  completed?: boolean;
-  priority?: Priority;
-  dueDate?: string;
-  attachments?: Attachment[];
-}
-
-// 待办统计信息
-export interface TaskStats {
-  total: number;
-  completed: number;
-  pending: number;
-  overdue: number;
-  today: number;
-  highPriority: number;
-  mediumPriority: number;
-  lowPriority: number;
-}
-
-// API响应基础结构
-export interface ApiResponse<T = any> {
-  success: boolean;
-  data?: T;
-  error?: string;
-}
-
-// 使用时长记录
-export interface UsageRecord {
-  id: string;
-  date: string; // YYYY-MM-DD格式
-  startTime: number; // 时间戳
-  endTime: number; // 时间戳
-  duration: number; // 持续时长（分钟）
-  type: 'active' | 'background'; // 使用类型
-}
-
-// 使用时长统计
-export interface UsageStats {
-  today: number; // 今日使用分钟数
-  thisWeek: number; // 本周使用分钟数
-  thisMonth: number; // 本月使用分钟数
-  averageDaily: number; // 日平均使用分钟数
-  totalSessions: number; // 总使用次数
-  longestSession: number; // 最长单次使用分钟数
-}
-
-// 使用详情数据
-export interface UsageDetail {
-  date: string;
-  totalMinutes: number;
-  sessions: number;
-  firstUse?: string; // 首次使用时间
-  lastUse?: string; // 最后使用时间
-}
-
-// 番茄钟状态
-export interface PomodoroState {
-  isActive: boolean;
-  currentMode: 'work' | 'break' | 'longBreak';
-  timeLeft: number; // 剩余秒数
-  cycle: number; // 当前循环次数
-  workDuration: number; // 工作时长（分钟）
-  breakDuration: number; // 短休息时长（分钟）
-  longBreakDuration: number; // 长休息时长（分钟）
-  cyclesBeforeLongBreak: number; // 长休息前的循环次数
-}
-
 // Tauri命令响应类型
 export interface TasksResponse extends ApiResponse<Task[]> {}
 export interface TaskResponse extends ApiResponse<Task> {}
 export interface SettingsResponse extends ApiResponse<Settings> {}
 export interface StatsResponse extends ApiResponse<TaskStats> {}
-export interface UsageResponse extends ApiResponse<UsageRecord[]> {}
 export interface UsageStatsResponse extends ApiResponse<UsageStats> {}
\ No newline at end of file